@@ -0,0 +1,126 @@
+use hybrid_casual_balancing::{
+    models::{Enemy, Player, Point, SKILL_SLOT_COUNT},
+    utils::{GameCatalog, SimulationConfig},
+};
+
+/// Removes `name` from `args` and returns whether it was there.
+pub fn take_switch(args: &mut Vec<String>, name: &str) -> bool {
+    let count = args.len();
+    args.retain(|arg| arg != name);
+
+    args.len() != count
+}
+
+/// Removes `name` and the value following it from `args` and returns the value (if `name` was there).
+///
+/// Returns an error if `name` isn't followed by a value.
+pub fn take_flag(args: &mut Vec<String>, name: &str) -> Result<Option<String>, ()> {
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+
+    if index + 1 >= args.len() {
+        return Err(());
+    }
+
+    let value = args.remove(index + 1);
+    args.remove(index);
+
+    Ok(Some(value))
+}
+
+/// Removes `name` and the number following it from `args` and returns the number (if `name` was there).
+///
+/// Prints an error and returns the exit code 2 if `name` isn't followed by a number.
+pub fn take_number_flag<T: std::str::FromStr>(args: &mut Vec<String>, name: &str) -> Result<Option<T>, i32> {
+    match take_flag(args, name) {
+        Ok(None) => Ok(None),
+        Ok(Some(value)) => match value.parse::<T>() {
+            Ok(number) => Ok(Some(number)),
+            Err(_) => {
+                eprintln!("error: {} must be followed by a number", name);
+                Err(2)
+            }
+        },
+        Err(()) => {
+            eprintln!("error: {} must be followed by a number", name);
+            Err(2)
+        }
+    }
+}
+
+/// Removes the `--time-step <seconds>` and `--max-duration <seconds>` flags from `args` and applies them to `config`.
+///
+/// Prints an error and returns the exit code 2 if a flag isn't followed by a number.
+pub fn take_simulation_flags(args: &mut Vec<String>, config: &mut SimulationConfig) -> Result<(), i32> {
+    config.time_step = take_number_flag(args, "--time-step")?.unwrap_or(config.time_step);
+    config.max_duration = take_number_flag(args, "--max-duration")?.unwrap_or(config.max_duration);
+
+    Ok(())
+}
+
+/// Builds the participants of a fight from `<player level> <weapon> <weapon level> <enemy> <enemy level> [enemy count] [--skills <skill>:<level>,...]` arguments.
+///
+/// The player has the weapon equipped and the skills (at most one per skill slot) slotted in the order they're given, regardless of their level requirements.
+///
+/// Prints an error and returns the exit code (1 if something doesn't exist in the catalog, 2 if the arguments are invalid) otherwise.
+pub fn parse_matchup(catalog: &GameCatalog, args: &mut Vec<String>, usage: &str) -> Result<(Player, Vec<Enemy>), i32> {
+    let Ok(skills) = take_flag(args, "--skills") else {
+        eprintln!("error: --skills must be followed by a list of skills");
+        return Err(2);
+    };
+    let [player_level, weapon_name, weapon_level, enemy_name, enemy_level, rest @ ..] = args.as_slice() else {
+        eprintln!("{}", usage);
+        return Err(2);
+    };
+
+    let (Ok(player_level), Ok(weapon_level), Ok(enemy_level)) = (player_level.parse::<u8>(), weapon_level.parse::<u8>(), enemy_level.parse::<u8>()) else {
+        eprintln!("error: levels must be numbers from 0 to 255");
+        return Err(2);
+    };
+    let Ok(enemy_count) = rest.first().map(|count| count.parse::<usize>()).unwrap_or(Ok(1)) else {
+        eprintln!("error: enemy count must be a number");
+        return Err(2);
+    };
+
+    let Some(player_stats) = catalog.player_level(player_level) else {
+        eprintln!("error: player level {} doesn't exist", player_level);
+        return Err(1);
+    };
+    let Some(weapon) = catalog.weapon(weapon_name).and_then(|weapon| weapon.at_level(weapon_level)) else {
+        eprintln!("error: weapon `{}` at level {} doesn't exist", weapon_name, weapon_level);
+        return Err(1);
+    };
+    let Some(enemy) = catalog.enemy(enemy_name).and_then(|enemy| enemy.at_level(enemy_level, Point { x: 0.0, y: 0.0 }, 0)) else {
+        eprintln!("error: enemy `{}` at level {} doesn't exist", enemy_name, enemy_level);
+        return Err(1);
+    };
+
+    let mut player = Player::new(player_stats, &catalog.player_levels);
+    player.equip_weapon_unchecked(weapon);
+
+    let skills: Vec<&str> = skills.iter().flat_map(|skills| skills.split(',')).collect();
+
+    if skills.len() > SKILL_SLOT_COUNT {
+        eprintln!("error: at most {} skills can be slotted", SKILL_SLOT_COUNT);
+        return Err(2);
+    }
+
+    for (slot, skill) in skills.into_iter().enumerate() {
+        let Some((skill_name, Ok(skill_level))) = skill.rsplit_once(':').map(|(name, level)| (name.trim(), level.trim().parse::<u8>())) else {
+            eprintln!("error: skills must be given as <skill>:<level>");
+            return Err(2);
+        };
+        let Some(skill) = catalog.skill(skill_name).and_then(|skill| skill.at_level(skill_level)) else {
+            eprintln!("error: skill `{}` at level {} doesn't exist", skill_name, skill_level);
+            return Err(1);
+        };
+
+        if let Err(err) = player.equip_skill_unchecked(skill, slot) {
+            eprintln!("error: {}", err);
+            return Err(2);
+        }
+    }
+
+    Ok((player, vec![enemy; enemy_count]))
+}
@@ -0,0 +1,54 @@
+use hybrid_casual_balancing::utils::{load_catalog, run_monte_carlo, MonteCarloConfig, CATALOG_ROOT};
+
+use super::{parse_matchup, take_number_flag, take_simulation_flags};
+
+const USAGE: &str = "usage: batch <player level> <weapon> <weapon level> <enemy> <enemy level> [enemy count] [--skills <skill>:<level>,...] [--runs <runs>] [--seed <seed>] [--threads <threads>] [--time-step <seconds>] [--max-duration <seconds>]";
+
+/// Runs the `batch` subcommand and returns the process' exit code.
+///
+/// Usage: `batch <player level> <weapon> <weapon level> <enemy> <enemy level> [enemy count] [--skills <skill>:<level>,...] [--runs <runs>] [--seed <seed>] [--threads <threads>] [--time-step <seconds>] [--max-duration <seconds>]`
+///
+/// Simulates `runs` (defaults to 1000) fights of the same matchup as `simulate`, each with its own seed derived from `seed` (defaults to 0),
+/// and prints the spread of their results (time to kill, win rate, HP remaining and heals) as JSON.
+/// Each fight's time step and maximum duration can be set like with `simulate`.
+pub fn run_batch(args: &[String]) -> i32 {
+    let mut args = args.to_vec();
+    let mut config = MonteCarloConfig::default();
+
+    match (take_number_flag(&mut args, "--runs"), take_number_flag(&mut args, "--seed"), take_number_flag(&mut args, "--threads")) {
+        (Ok(runs), Ok(seed), Ok(threads)) => {
+            config.runs = runs.unwrap_or(config.runs);
+            config.seed = seed.unwrap_or(config.seed);
+            config.threads = threads.unwrap_or(config.threads);
+        }
+        (Err(exit_code), _, _) | (_, Err(exit_code), _) | (_, _, Err(exit_code)) => return exit_code,
+    }
+
+    if let Err(exit_code) = take_simulation_flags(&mut args, &mut config.simulation) {
+        return exit_code;
+    }
+
+    let catalog = match load_catalog(CATALOG_ROOT) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    let (player, enemies) = match parse_matchup(&catalog, &mut args, USAGE) {
+        Ok(matchup) => matchup,
+        Err(exit_code) => return exit_code,
+    };
+    let report = match run_monte_carlo(&player, &enemies, &config) {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    println!("{}", serde_json::to_string_pretty(&report).expect("Failed to serialize Monte Carlo report"));
+
+    0
+}
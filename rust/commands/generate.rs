@@ -0,0 +1,49 @@
+use hybrid_casual_balancing::utils::{generate_level_tables, load_catalog, CATALOG_ROOT};
+
+use super::take_switch;
+
+/// Runs the `generate` subcommand and returns the process' exit code.
+/// 
+/// Usage: `generate [catalog root] [--check]`
+/// 
+/// Regenerates the player's, enemies', weapons' and skills' level tables within the catalog (defaults to `mechanics/`) from its `growth.json`,
+/// rewriting only the files whose contents changed. If `--check` is passed, nothing is written; instead, every outdated file is listed
+/// and the process exits with 1 if there are any.
+pub fn run_generate(args: &[String]) -> i32 {
+    let mut args = args.to_vec();
+    let check = take_switch(&mut args, "--check");
+    let root = args.first().map(String::as_str).unwrap_or(CATALOG_ROOT);
+
+    let catalog = match load_catalog(root) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    let files = match generate_level_tables(&catalog) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    let outdated: Vec<_> = files.iter().filter(|file| !file.is_up_to_date()).collect();
+
+    for file in &outdated {
+        if check {
+            println!("outdated: {}", file.path.display());
+        } else if let Err(err) = file.write() {
+            eprintln!("error: {}", err);
+            return 1;
+        } else {
+            println!("wrote {}", file.path.display());
+        }
+    }
+
+    println!("{} of {} generated file(s) {}", outdated.len(), files.len(), if check { "outdated" } else { "rewritten" });
+
+    if check && !outdated.is_empty() { 1 } else { 0 }
+}
@@ -0,0 +1,15 @@
+pub mod args;
+pub mod validate;
+pub mod simulate;
+pub mod ttk;
+pub mod batch;
+pub mod generate;
+pub mod upgrade_costs;
+
+pub use args::*;
+pub use validate::*;
+pub use simulate::*;
+pub use ttk::*;
+pub use batch::*;
+pub use generate::*;
+pub use upgrade_costs::*;
@@ -0,0 +1,50 @@
+use hybrid_casual_balancing::utils::{load_catalog, simulate_combat, GameRng, SimulationConfig, CATALOG_ROOT};
+
+use super::{parse_matchup, take_number_flag, take_simulation_flags};
+
+const USAGE: &str = "usage: simulate <player level> <weapon> <weapon level> <enemy> <enemy level> [enemy count] [--skills <skill>:<level>,...] [--seed <seed>] [--time-step <seconds>] [--max-duration <seconds>]";
+
+/// Runs the `simulate` subcommand and returns the process' exit code.
+///
+/// Usage: `simulate <player level> <weapon> <weapon level> <enemy> <enemy level> [enemy count] [--skills <skill>:<level>,...] [--seed <seed>] [--time-step <seconds>] [--max-duration <seconds>]`
+///
+/// Simulates a fight between a player wielding the weapon (with the skills slotted in the order they're given) and `enemy count` (defaults to 1) enemies,
+/// and prints the fight's report as JSON.
+/// The same seed (defaults to 0) always results in the same fight.
+/// The simulation's time step and maximum duration (in seconds) default to those of `SimulationConfig::default`.
+pub fn run_simulate(args: &[String]) -> i32 {
+    let mut args = args.to_vec();
+    let mut config = SimulationConfig::default();
+    let seed = match take_number_flag::<u64>(&mut args, "--seed") {
+        Ok(seed) => seed.unwrap_or(0),
+        Err(exit_code) => return exit_code,
+    };
+
+    if let Err(exit_code) = take_simulation_flags(&mut args, &mut config) {
+        return exit_code;
+    }
+
+    let catalog = match load_catalog(CATALOG_ROOT) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    let (player, mut enemies) = match parse_matchup(&catalog, &mut args, USAGE) {
+        Ok(matchup) => matchup,
+        Err(exit_code) => return exit_code,
+    };
+    let report = match simulate_combat(&player, &mut enemies, &config, &mut GameRng::new(seed)) {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    println!("{}", serde_json::to_string_pretty(&report).expect("Failed to serialize combat report"));
+
+    0
+}
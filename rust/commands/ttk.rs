@@ -0,0 +1,95 @@
+use std::ops::RangeInclusive;
+
+use hybrid_casual_balancing::utils::{load_catalog, sweep_time_to_kill, time_to_kill_csv, SimulationConfig, TimeToKillSweep, CATALOG_ROOT};
+
+use super::{take_flag, take_number_flag, take_simulation_flags, take_switch};
+
+const USAGE: &str = "usage: ttk <weapon> <enemy> [--player-levels <from>-<to>] [--weapon-levels <from>-<to>] [--enemy-levels <from>-<to>] [--runs <runs>] [--seed <seed>] [--time-step <seconds>] [--max-duration <seconds>] [--json]";
+
+/// Runs the `ttk` subcommand and returns the process' exit code.
+///
+/// Usage: `ttk <weapon> <enemy> [--player-levels <from>-<to>] [--weapon-levels <from>-<to>] [--enemy-levels <from>-<to>] [--runs <runs>] [--seed <seed>] [--time-step <seconds>] [--max-duration <seconds>] [--json]`
+///
+/// Sweeps one-on-one fights between a player wielding the weapon and the enemy across the level ranges (defaults to every level in the catalog),
+/// and prints each combination's DPS, time to kill, time to die, survival margin and win rate as a CSV table (or JSON if `--json` is passed).
+/// Each fight's time step and maximum duration can be set like with `simulate`.
+pub fn run_ttk(args: &[String]) -> i32 {
+    let mut args = args.to_vec();
+    let as_json = take_switch(&mut args, "--json");
+
+    let (runs, seed) = match (take_number_flag::<u32>(&mut args, "--runs"), take_number_flag::<u64>(&mut args, "--seed")) {
+        (Ok(runs), Ok(seed)) => (runs, seed),
+        (Err(exit_code), _) | (_, Err(exit_code)) => return exit_code,
+    };
+    let mut config = SimulationConfig::default();
+
+    if let Err(exit_code) = take_simulation_flags(&mut args, &mut config) {
+        return exit_code;
+    }
+
+    let flags = (take_flag(&mut args, "--player-levels"), take_flag(&mut args, "--weapon-levels"), take_flag(&mut args, "--enemy-levels"));
+    let (Ok(player_levels), Ok(weapon_levels), Ok(enemy_levels)) = flags else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+
+    let [weapon_name, enemy_name] = args.as_slice() else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+
+    let catalog = match load_catalog(CATALOG_ROOT) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    let mut sweep = match TimeToKillSweep::all_levels(&catalog, weapon_name, enemy_name) {
+        Ok(sweep) => sweep,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    for (range, value) in [(&mut sweep.player_levels, player_levels), (&mut sweep.weapon_levels, weapon_levels), (&mut sweep.enemy_levels, enemy_levels)] {
+        match value.map(|value| parse_level_range(&value)) {
+            Some(Some(levels)) => *range = levels,
+            Some(None) => {
+                eprintln!("error: level ranges must look like `<from>-<to>` (or a single level), with levels from 0 to 255");
+                return 2;
+            }
+            None => {}
+        }
+    }
+
+    sweep.runs = runs.unwrap_or(sweep.runs);
+    sweep.seed = seed.unwrap_or(sweep.seed);
+    sweep.config = config;
+
+    let rows = match sweep_time_to_kill(&catalog, &sweep) {
+        Ok(rows) => rows,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&rows).expect("Failed to serialize time to kill table"));
+    } else {
+        print!("{}", time_to_kill_csv(&rows));
+    }
+
+    0
+}
+
+/// parses `<from>-<to>` or a single level into a range of levels
+fn parse_level_range(value: &str) -> Option<RangeInclusive<u8>> {
+    match value.split_once('-') {
+        Some((from, to)) => Some(from.parse().ok()?..=to.parse().ok()?),
+        None => value.parse().ok().map(|level| level..=level),
+    }
+}
@@ -0,0 +1,37 @@
+use hybrid_casual_balancing::utils::{load_catalog, upgrade_cost_csv, upgrade_cost_reports, CATALOG_ROOT};
+
+use super::take_switch;
+
+/// Runs the `upgrade-costs` subcommand and returns the process' exit code.
+///
+/// Usage: `upgrade-costs [--json]`
+///
+/// Prints what it costs to upgrade every weapon and skill from level 1 to its max level as a CSV table,
+/// or the full level-by-level report as JSON if `--json` is passed.
+pub fn run_upgrade_costs(args: &[String]) -> i32 {
+    let mut args = args.to_vec();
+    let as_json = take_switch(&mut args, "--json");
+
+    if !args.is_empty() {
+        eprintln!("usage: upgrade-costs [--json]");
+        return 2;
+    }
+
+    let catalog = match load_catalog(CATALOG_ROOT) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    let reports = upgrade_cost_reports(&catalog);
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&reports).expect("Failed to serialize upgrade cost reports"));
+    } else {
+        print!("{}", upgrade_cost_csv(&reports));
+    }
+
+    0
+}
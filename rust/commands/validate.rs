@@ -0,0 +1,34 @@
+use hybrid_casual_balancing::utils::{has_errors, load_catalog, validate_catalog, CATALOG_ROOT};
+
+/// Runs the `validate` subcommand and returns the process' exit code.
+/// 
+/// Usage: `validate [catalog root] [--json]`
+/// 
+/// Loads the catalog (defaults to `mechanics/`), validates it and prints every diagnostic found (as JSON if `--json` is passed).
+/// Exits with 1 if the catalog couldn't be loaded or any error was found, 0 otherwise.
+pub fn run_validate(args: &[String]) -> i32 {
+    let as_json = args.iter().any(|arg| arg == "--json");
+    let root = args.iter().find(|arg| !arg.starts_with("--")).map(String::as_str).unwrap_or(CATALOG_ROOT);
+
+    let catalog = match load_catalog(root) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+
+    let diagnostics = validate_catalog(&catalog);
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&diagnostics).expect("Failed to serialize diagnostics"));
+    } else {
+        for diagnostic in &diagnostics {
+            println!("{}", diagnostic);
+        }
+
+        println!("{} diagnostic(s) found in {}", diagnostics.len(), root);
+    }
+
+    if has_errors(&diagnostics) { 1 } else { 0 }
+}
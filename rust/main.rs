@@ -1,8 +1,7 @@
-mod models;
-mod utils;
+mod commands;
 
 use salvo::prelude::*;
-use utils::{get_pfufu_location, convert_coord_system};
+use hybrid_casual_balancing::utils::{get_pfufu_location, convert_coord_system};
 
 /// Checks to see if Salvo is running
 #[handler]
@@ -14,6 +13,26 @@ async fn run_salvo() -> &'static str {
 async fn main() {
     tracing_subscriber::fmt().init();
 
+    // run a subcommand instead of the server if one is given
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if let Some((subcommand, subcommand_args)) = args.split_first() {
+        let exit_code = match subcommand.as_str() {
+            "validate" => commands::run_validate(subcommand_args),
+            "simulate" => commands::run_simulate(subcommand_args),
+            "ttk" => commands::run_ttk(subcommand_args),
+            "batch" => commands::run_batch(subcommand_args),
+            "generate" => commands::run_generate(subcommand_args),
+            "upgrade-costs" => commands::run_upgrade_costs(subcommand_args),
+            _ => {
+                eprintln!("unknown subcommand: {}", subcommand);
+                2
+            }
+        };
+
+        std::process::exit(exit_code);
+    }
+
     get_pfufu_location();
 
     convert_coord_system();
@@ -21,4 +40,4 @@ async fn main() {
     let router = Router::new().get(run_salvo);
     let acceptor = TcpListener::new("127.0.0.1:5800").bind().await;
     Server::new(acceptor).serve(router).await;
-}
\ No newline at end of file
+}
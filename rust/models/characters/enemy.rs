@@ -1,61 +1,98 @@
-use crate::models::{RangeType, EnemySkill, KillRewards, StatusEffect, Point};
-use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+
+use crate::models::{RangeType, EnemySkill, EnemySkillDefinition, KillRewards, ActiveStatusEffects, Point, GameTime};
 use serde::{Deserialize, Serialize};
 
 /// Represents an instance of an enemy with all relevant data and stats.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Enemy {
     /// the enemy's name
-    name: String,
+    pub name: String,
     /// a description explaining the enemy
-    description: String,
+    pub description: String,
     /// a Point instance representing where this particular enemy spawns (relative to the map)
     /// 
     /// note that, with this logic, the spawn position will always be the same for each enemy
     #[serde(rename = "spawnPosition")]
-    spawn_position: Point,
+    pub spawn_position: Point,
     /// the amount of time (in seconds) it takes for a defeated enemy to be respawned
     #[serde(rename = "respawnTime")]
-    respawn_time: u8,
+    pub respawn_time: u8,
     /// the range type of the enemy
     #[serde(rename = "rangeType")]
-    range_type: RangeType,
+    pub range_type: RangeType,
     /// the enemy's current level
-    level: u8,
+    pub level: u8,
     /// represents the live state of the enemy (with real time data)
     #[serde(rename = "liveState")]
-    live_state: EnemyState,
+    pub live_state: EnemyState,
     /// the enemy's base hp (essentially its max hp for this level)
     #[serde(rename = "baseHp")]
-    base_hp: f64,
+    pub base_hp: f64,
     /// the enemy's hp regen per second
     #[serde(rename = "baseHpRegen")]
-    base_hp_regen: f64,
+    pub base_hp_regen: f64,
     /// how fast the enemy moves (units per second)
     #[serde(rename = "baseMovementSpeed")]
-    base_movement_speed: f64,
+    pub base_movement_speed: f64,
     /// how much damage the enemy deals per attack
     #[serde(rename = "baseDamage")]
-    base_damage: f64,
+    pub base_damage: f64,
     /// how fast the enemy shoots its projectile (only if ranged; 0 if melee)
-    #[serde(rename = "baseProjectileSpeed")]
-    base_projectile_velocity: f64,
+    #[serde(rename = "baseProjectileVelocity")]
+    pub base_projectile_velocity: f64,
     /// how far the enemy can start attacking (melee will also have an attack range)
     #[serde(rename = "baseAttackRange")]
-    base_attack_range: f64,
+    pub base_attack_range: f64,
     /// how fast each subsequent attack happens (in seconds).
     /// 
     /// note that the first attack will always be half of this (due to the attack animation; ~50% through, attack happens)
     #[serde(rename = "baseAttackTime")]
-    base_attack_time: f64,
+    pub base_attack_time: f64,
     /// the probability of dealing a critical hit (in a ratio from 0 to 1)
     #[serde(rename = "critChance")]
-    crit_chance: f64,
+    pub crit_chance: f64,
     /// the enemy's skill
-    skill: EnemySkill,
+    pub skill: EnemySkill,
     /// the rewards for killing this enemy
     #[serde(rename = "killRewards")]
-    kill_rewards: KillRewards,
+    pub kill_rewards: KillRewards,
+}
+
+impl Enemy {
+    /// Checks whether the enemy can move at `now`, i.e. it's alive and not stunned.
+    pub fn can_move(&self, now: GameTime) -> bool {
+        !self.live_state.is_dead && !self.live_state.current_status_effects.is_stunned(now)
+    }
+
+    /// Records that the enemy attacked at `now`.
+    ///
+    /// When the enemy attacks next is up to whoever runs the fight (see `simulate_combat`), which also accounts for stuns.
+    pub fn record_attack(&mut self, now: GameTime) {
+        self.live_state.last_attack = Some(now);
+    }
+
+    /// Marks the enemy as defeated at `now`.
+    pub fn die(&mut self, now: GameTime) {
+        self.live_state.is_dead = true;
+        self.live_state.current_hp = 0.0;
+        self.live_state.current_status_effects.clear();
+        self.live_state.last_death = Some(now);
+    }
+
+    /// Checks whether the enemy is defeated and `respawn_time` has passed since its death at `now`.
+    pub fn can_respawn(&self, now: GameTime) -> bool {
+        self.live_state.is_dead
+            && self.live_state.last_death.is_none_or(|last_death| now.seconds_since(last_death) >= f64::from(self.respawn_time))
+    }
+
+    /// Respawns the enemy at its spawn position with full HP (the time of its last death is kept).
+    pub fn respawn(&mut self) {
+        let last_death = self.live_state.last_death;
+
+        self.live_state = EnemyState::new(self.base_hp, self.spawn_position);
+        self.live_state.last_death = last_death;
+    }
 }
 
 /// Represents the state of an `Enemy` instance at real time during gameplay.
@@ -69,19 +106,137 @@ pub struct EnemyState {
     /// 
     /// if true, this enemy can respawn again after `respawn_time` in `Enemy`
     #[serde(rename = "isDead")]
-    is_dead: bool,
+    pub is_dead: bool,
     /// the enemy's current hp
     #[serde(rename = "currentHp")]
-    current_hp: f64,
+    pub current_hp: f64,
     /// the status effects inflicted to the enemy (if any; can be multiple)
-    #[serde(rename = "currentStatusEffects")]
-    current_status_effects: Option<Vec<StatusEffect>>,
+    #[serde(rename = "currentStatusEffects", default)]
+    pub current_status_effects: ActiveStatusEffects,
     /// whenever the enemy moves, this field will be updated to reflect its current position
     #[serde(rename = "currentPosition")]
-    current_position: Point,
-    /// a timestamp to reflect the enemy's last death. this is used to determine when the enemy can respawn again, based on `respawn_time` in `Enemy`
-    last_death: DateTime<Utc>,
-    /// a timestamp to reflect the enemy's last attack. since this is a purely coded version of the game, attack animations don't really "play out"; instead, we use this field to determine when the enemy can attack again
+    pub current_position: Point,
+    /// the game time of the enemy's last death (if any). this is used to determine when the enemy can respawn again, based on `respawn_time` in `Enemy`
+    #[serde(rename = "lastDeath")]
+    pub last_death: Option<GameTime>,
+    /// the game time of the enemy's last attack (if any). since this is a purely coded version of the game, attack animations don't really "play out"; instead, we use this field to determine when the enemy can attack again
     #[serde(rename = "lastAttack")]
-    last_attack: DateTime<Utc>,
-}
\ No newline at end of file
+    pub last_attack: Option<GameTime>,
+}
+
+impl EnemyState {
+    /// Creates the state of a freshly spawned enemy with `hp` HP at `position`.
+    pub fn new(hp: f64, position: Point) -> Self {
+        EnemyState {
+            is_dead: false,
+            current_hp: hp,
+            current_status_effects: ActiveStatusEffects::default(),
+            current_position: position,
+            last_death: None,
+            last_attack: None,
+        }
+    }
+}
+
+/// Represents an enemy's full data across all of its levels (as stored in `mechanics/enemies`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemyDefinition {
+    /// the enemy's name
+    pub name: String,
+    /// a description explaining the enemy
+    pub description: String,
+    /// the range type of the enemy
+    #[serde(rename = "rangeType")]
+    pub range_type: RangeType,
+    /// the enemy's mechanics level by level
+    #[serde(rename = "levelMechanics")]
+    pub level_mechanics: Vec<EnemyLevelMechanics>,
+    /// the file this enemy was loaded from (set when loading the catalog)
+    #[serde(skip)]
+    pub source: PathBuf,
+}
+
+impl EnemyDefinition {
+    /// Gets the enemy's mechanics at level `level`.
+    pub fn level_mechanics(&self, level: u8) -> Option<&EnemyLevelMechanics> {
+        self.level_mechanics.iter().find(|mechanics| mechanics.level == level)
+    }
+
+    /// Gets the highest level this enemy can be.
+    pub fn max_level(&self) -> u8 {
+        self.level_mechanics.iter().map(|mechanics| mechanics.level).max().unwrap_or_default()
+    }
+
+    /// Creates an `Enemy` instance of this enemy at level `level` that spawns at `spawn_position` and respawns after `respawn_time` seconds.
+    /// 
+    /// The enemy starts alive with full HP at its spawn position. Returns `None` if the enemy has no mechanics for that level.
+    pub fn at_level(&self, level: u8, spawn_position: Point, respawn_time: u8) -> Option<Enemy> {
+        let mechanics = self.level_mechanics(level)?;
+
+        Some(Enemy {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            spawn_position,
+            respawn_time,
+            range_type: self.range_type,
+            level,
+            live_state: EnemyState::new(mechanics.base_hp, spawn_position),
+            base_hp: mechanics.base_hp,
+            base_hp_regen: mechanics.base_hp_regen,
+            base_movement_speed: mechanics.base_movement_speed,
+            base_damage: mechanics.base_damage,
+            base_projectile_velocity: mechanics.base_projectile_velocity,
+            base_attack_range: mechanics.base_attack_range,
+            base_attack_time: mechanics.base_attack_time,
+            crit_chance: mechanics.crit_chance,
+            skill: mechanics.skill.at_level(level)?,
+            kill_rewards: mechanics.kill_rewards.clone(),
+        })
+    }
+}
+
+/// Represents an enemy's stats at a specific level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemyLevelMechanics {
+    /// the enemy's level
+    pub level: u8,
+    /// the enemy's base hp (essentially its max hp for this level)
+    #[serde(rename = "baseHp")]
+    pub base_hp: f64,
+    /// the enemy's hp regen per second
+    #[serde(rename = "baseHpRegen")]
+    pub base_hp_regen: f64,
+    /// how fast the enemy moves (units per second)
+    #[serde(rename = "baseMovementSpeed")]
+    pub base_movement_speed: f64,
+    /// how much damage the enemy deals per attack
+    #[serde(rename = "baseDamage")]
+    pub base_damage: f64,
+    /// how fast the enemy shoots its projectile (only if ranged; 0 if melee)
+    #[serde(rename = "baseProjectileVelocity")]
+    pub base_projectile_velocity: f64,
+    /// how far the enemy can start attacking
+    #[serde(rename = "baseAttackRange")]
+    pub base_attack_range: f64,
+    /// how fast each subsequent attack happens (in seconds)
+    #[serde(rename = "baseAttackTime")]
+    pub base_attack_time: f64,
+    /// the probability of dealing a critical hit (in a ratio from 0 to 1)
+    #[serde(rename = "critChance")]
+    pub crit_chance: f64,
+    /// the enemy's skill at this level
+    pub skill: EnemySkillDefinition,
+    /// the rewards for killing this enemy at this level
+    #[serde(rename = "killRewards")]
+    pub kill_rewards: KillRewards,
+}
+
+/// Represents an amount of a specific enemy a quest requires the player to defeat.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemiesRequired {
+    /// the name of the enemy (e.g. `Golem`)
+    #[serde(rename = "type")]
+    pub enemy: String,
+    /// the amount of enemies required to be defeated
+    pub amount: u32,
+}
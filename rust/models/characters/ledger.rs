@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::models::Currency;
+
+/// Represents an append-only record of every change made to a `Wallet`, in the order they happened.
+///
+/// This allows the economy of a (simulated) session to be audited after the fact, e.g. where coins came from and what they were spent on.
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct Ledger {
+    entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    /// Gets every entry in the order they were recorded.
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    /// Gets every entry of `currency` in the order they were recorded.
+    pub fn entries_of(&self, currency: Currency) -> impl Iterator<Item = &LedgerEntry> {
+        self.entries.iter().filter(move |entry| entry.currency == currency)
+    }
+
+    /// Summarizes how much of each currency was earned and spent, and through what.
+    pub fn summary(&self) -> BTreeMap<Currency, LedgerSummary> {
+        let mut summaries: BTreeMap<Currency, LedgerSummary> = BTreeMap::new();
+
+        for entry in &self.entries {
+            let summary = summaries.entry(entry.currency).or_default();
+            let amount = entry.amount.unsigned_abs();
+
+            if entry.amount >= 0 {
+                summary.earned += amount;
+                *summary.sources.entry(entry.reason.category()).or_default() += amount;
+            } else {
+                summary.spent += amount;
+                *summary.sinks.entry(entry.reason.category()).or_default() += amount;
+            }
+
+            summary.net += entry.amount;
+        }
+
+        summaries
+    }
+
+    /// appends an entry (only a `Wallet` records entries, so that the ledger always agrees with its balances)
+    pub(crate) fn record(&mut self, entry: LedgerEntry) {
+        self.entries.push(entry);
+    }
+}
+
+/// Represents a single change made to a wallet's balance.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct LedgerEntry {
+    /// the currency that changed
+    pub currency: Currency,
+    /// how much the balance changed (positive if earned, negative if spent)
+    pub amount: i64,
+    /// the balance after the change
+    pub balance: u32,
+    /// why the balance changed
+    pub reason: LedgerReason,
+}
+
+/// A list of all reasons a wallet's balance can change.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum LedgerReason {
+    /// defeating an enemy
+    Kill { enemy: String, level: u8 },
+    /// completing a quest
+    Quest { chapter: u8, id: u8 },
+    /// collecting an item (e.g. a `Blueberry`)
+    Item { name: String },
+    /// purchasing a weapon, skill or breathable area
+    Purchase { name: String },
+    /// upgrading a weapon or skill to `level`
+    Upgrade { name: String, level: u8 },
+    /// anything else (e.g. a starting balance or a manual correction)
+    Adjustment { note: String },
+}
+
+impl LedgerReason {
+    /// Gets the name of the reason's category, which sources and sinks are grouped by.
+    pub fn category(&self) -> &'static str {
+        match self {
+            LedgerReason::Kill { .. } => "kill",
+            LedgerReason::Quest { .. } => "quest",
+            LedgerReason::Item { .. } => "item",
+            LedgerReason::Purchase { .. } => "purchase",
+            LedgerReason::Upgrade { .. } => "upgrade",
+            LedgerReason::Adjustment { .. } => "adjustment",
+        }
+    }
+}
+
+/// Represents how much of a currency was earned and spent according to a ledger.
+#[derive(Clone, Default, Serialize, Debug)]
+pub struct LedgerSummary {
+    /// the total amount earned
+    pub earned: u64,
+    /// the total amount spent
+    pub spent: u64,
+    /// the amount earned minus the amount spent
+    pub net: i64,
+    /// the amount earned per reason category (e.g. `kill`)
+    pub sources: BTreeMap<&'static str, u64>,
+    /// the amount spent per reason category (e.g. `upgrade`)
+    pub sinks: BTreeMap<&'static str, u64>,
+}
@@ -0,0 +1,380 @@
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+use crate::models::{Player, PlayerLevelStats, Skill, SkillLevelRequirement, Weapon, WeaponLevelRequirement};
+
+/// The amount of skills the player can have slotted at once.
+pub const SKILL_SLOT_COUNT: usize = 3;
+
+/// Represents what the player currently fights with: an active weapon and a fixed number of skill slots.
+///
+/// Weapons and skills are referred to by name and must be within the player's inventory to be equipped.
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
+pub struct Loadout {
+    /// the name of the equipped weapon (if any)
+    #[serde(rename = "activeWeapon")]
+    pub active_weapon: Option<String>,
+    /// the name of the skill within each slot (if any)
+    #[serde(rename = "skillSlots")]
+    pub skill_slots: [Option<String>; SKILL_SLOT_COUNT],
+}
+
+impl Loadout {
+    /// Gets the slot the skill with the name `name` is in (if it's slotted).
+    pub fn skill_slot(&self, name: &str) -> Option<usize> {
+        self.skill_slots.iter().position(|slotted| slotted.as_deref() == Some(name))
+    }
+}
+
+/// Represents why a weapon or skill couldn't be equipped.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LoadoutError {
+    /// the weapon or skill isn't in the player's inventory
+    NotInInventory { name: String },
+    /// the skill slot doesn't exist
+    InvalidSlot { slot: usize },
+    /// the skill is already in another slot
+    AlreadySlotted { name: String, slot: usize },
+    /// the weapon's or skill's level isn't allowed at the player's level
+    LevelRequirement { name: String, error: LevelRequirementError },
+}
+
+impl fmt::Display for LoadoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadoutError::NotInInventory { name } => write!(f, "`{}` isn't in the player's inventory", name),
+            LoadoutError::InvalidSlot { slot } => write!(f, "skill slot {} doesn't exist (there are {} slots)", slot, SKILL_SLOT_COUNT),
+            LoadoutError::AlreadySlotted { name, slot } => write!(f, "`{}` is already in skill slot {}", name, slot),
+            LoadoutError::LevelRequirement { name, error } => write!(f, "`{}` {}", name, error),
+        }
+    }
+}
+
+impl std::error::Error for LoadoutError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadoutError::LevelRequirement { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Represents why a weapon or skill level isn't allowed at the player's level (when equipping or upgrading it).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LevelRequirementError {
+    /// the level has no entry in the level requirement table
+    UnknownLevel { level: u8 },
+    /// the player's level is below the minimum player level required for the level
+    LevelRequirementNotMet { level: u8, required_player_level: u8, player_level: u8 },
+    /// the level is above the max weapon or skill level allowed at the player's level
+    ExceedsMaxLevel { level: u8, max_level: u8 },
+}
+
+impl fmt::Display for LevelRequirementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelRequirementError::UnknownLevel { level } => write!(f, "level {} has no level requirement", level),
+            LevelRequirementError::LevelRequirementNotMet { level, required_player_level, player_level } => {
+                write!(f, "level {} requires player level {} (player is level {})", level, required_player_level, player_level)
+            }
+            LevelRequirementError::ExceedsMaxLevel { level, max_level } => {
+                write!(f, "level {} is above the max level allowed at the player's level ({})", level, max_level)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelRequirementError {}
+
+impl Player {
+    /// Gets the equipped weapon (if any).
+    pub fn equipped_weapon(&self) -> Option<&Weapon> {
+        let name = self.loadout.active_weapon.as_deref()?;
+
+        self.inventory.weapons.iter().find(|weapon| weapon.name == name)
+    }
+
+    /// Gets the skill within slot `slot` (if any).
+    pub fn equipped_skill(&self, slot: usize) -> Option<&Skill> {
+        let name = self.loadout.skill_slots.get(slot)?.as_deref()?;
+
+        self.inventory.skills.iter().find(|skill| skill.name == name)
+    }
+
+    /// Equips the weapon with the name `name` from the inventory, checking its level against `requirements`
+    /// (i.e. the weapon level requirement table) and the max weapon level of the player's level within `levels` (i.e. the player level table).
+    ///
+    /// Returns the name of the weapon that was equipped before (if any).
+    pub fn equip_weapon(&mut self, name: &str, levels: &[PlayerLevelStats], requirements: &[WeaponLevelRequirement]) -> Result<Option<String>, LoadoutError> {
+        self.check_weapon(name, levels, requirements)?;
+
+        Ok(self.loadout.active_weapon.replace(name.to_string()))
+    }
+
+    /// Adds `weapon` to the inventory (replacing any weapon with the same name) and equips it without checking its level
+    /// (e.g. to simulate a matchup the player can't reach yet).
+    ///
+    /// Returns the name of the weapon that was equipped before (if any).
+    pub fn equip_weapon_unchecked(&mut self, weapon: Weapon) -> Option<String> {
+        let name = weapon.name.clone();
+        self.inventory.weapons.retain(|owned| owned.name != name);
+        self.inventory.weapons.push(weapon);
+
+        self.loadout.active_weapon.replace(name)
+    }
+
+    /// Unequips the active weapon and returns its name (if any).
+    pub fn unequip_weapon(&mut self) -> Option<String> {
+        self.loadout.active_weapon.take()
+    }
+
+    /// Puts the skill with the name `name` from the inventory into slot `slot`, checking its level against `requirements`
+    /// (i.e. the skill level requirement table) and the max skill level of the player's level within `levels` (i.e. the player level table).
+    ///
+    /// A skill can only be in one slot at a time. Returns the name of the skill that was in the slot before (if any).
+    pub fn equip_skill(
+        &mut self,
+        name: &str,
+        slot: usize,
+        levels: &[PlayerLevelStats],
+        requirements: &[SkillLevelRequirement],
+    ) -> Result<Option<String>, LoadoutError> {
+        if slot >= SKILL_SLOT_COUNT {
+            return Err(LoadoutError::InvalidSlot { slot });
+        }
+
+        if let Some(current_slot) = self.loadout.skill_slot(name).filter(|&current_slot| current_slot != slot) {
+            return Err(LoadoutError::AlreadySlotted { name: name.to_string(), slot: current_slot });
+        }
+
+        self.check_skill(name, levels, requirements)?;
+
+        Ok(self.loadout.skill_slots[slot].replace(name.to_string()))
+    }
+
+    /// Adds `skill` to the inventory (replacing any skill with the same name) and puts it into slot `slot` without checking its level
+    /// (e.g. to simulate a matchup the player can't reach yet).
+    ///
+    /// A skill can only be in one slot at a time. Returns the name of the skill that was in the slot before (if any).
+    pub fn equip_skill_unchecked(&mut self, skill: Skill, slot: usize) -> Result<Option<String>, LoadoutError> {
+        if slot >= SKILL_SLOT_COUNT {
+            return Err(LoadoutError::InvalidSlot { slot });
+        }
+
+        if let Some(current_slot) = self.loadout.skill_slot(&skill.name).filter(|&current_slot| current_slot != slot) {
+            return Err(LoadoutError::AlreadySlotted { name: skill.name, slot: current_slot });
+        }
+
+        let name = skill.name.clone();
+        self.inventory.skills.retain(|owned| owned.name != name);
+        self.inventory.skills.push(skill);
+
+        Ok(self.loadout.skill_slots[slot].replace(name))
+    }
+
+    /// Empties slot `slot` and returns the name of the skill that was in it (if any).
+    pub fn unequip_skill(&mut self, slot: usize) -> Result<Option<String>, LoadoutError> {
+        let slotted = self.loadout.skill_slots.get_mut(slot).ok_or(LoadoutError::InvalidSlot { slot })?;
+
+        Ok(slotted.take())
+    }
+
+    /// Checks that everything within the loadout can still be equipped (e.g. after the loadout was loaded from a save or an item left the inventory).
+    ///
+    /// Returns the first problem found (if any).
+    pub fn check_loadout(
+        &self,
+        levels: &[PlayerLevelStats],
+        weapon_requirements: &[WeaponLevelRequirement],
+        skill_requirements: &[SkillLevelRequirement],
+    ) -> Result<(), LoadoutError> {
+        if let Some(name) = &self.loadout.active_weapon {
+            self.check_weapon(name, levels, weapon_requirements)?;
+        }
+
+        for name in self.loadout.skill_slots.iter().flatten() {
+            self.check_skill(name, levels, skill_requirements)?;
+        }
+
+        Ok(())
+    }
+
+    /// checks that the weapon with the name `name` is in the inventory and its level is allowed at the player's level
+    fn check_weapon(&self, name: &str, levels: &[PlayerLevelStats], requirements: &[WeaponLevelRequirement]) -> Result<(), LoadoutError> {
+        let weapon = self.inventory.weapons
+            .iter()
+            .find(|weapon| weapon.name == name)
+            .ok_or_else(|| LoadoutError::NotInInventory { name: name.to_string() })?;
+        let requirement = requirements.iter().find(|requirement| requirement.level == weapon.level);
+        let max_level = self.current_level_stats(levels).map(|stats| stats.max_weapon_level);
+
+        self.check_level_requirement(weapon.level, requirement.map(|requirement| requirement.min_player_level_required), max_level)
+            .map_err(|error| LoadoutError::LevelRequirement { name: name.to_string(), error })
+    }
+
+    /// checks that the skill with the name `name` is in the inventory and its level is allowed at the player's level
+    fn check_skill(&self, name: &str, levels: &[PlayerLevelStats], requirements: &[SkillLevelRequirement]) -> Result<(), LoadoutError> {
+        let skill = self.inventory.skills
+            .iter()
+            .find(|skill| skill.name == name)
+            .ok_or_else(|| LoadoutError::NotInInventory { name: name.to_string() })?;
+        let requirement = requirements.iter().find(|requirement| requirement.level == skill.level);
+        let max_level = self.current_level_stats(levels).map(|stats| stats.max_skill_level);
+
+        self.check_level_requirement(skill.level, requirement.map(|requirement| requirement.min_player_level_required), max_level)
+            .map_err(|error| LoadoutError::LevelRequirement { name: name.to_string(), error })
+    }
+
+    /// Checks a weapon or skill level against its minimum player level required (`None` if the level has no entry in the level requirement table)
+    /// and the max weapon or skill level allowed at the player's level (`None` if the player's level isn't within the player level table, which allows nothing).
+    pub fn check_level_requirement(&self, level: u8, required_player_level: Option<u8>, max_level: Option<u8>) -> Result<(), LevelRequirementError> {
+        let player_level = self.player_level_mechanics.current_level;
+        let required_player_level = required_player_level.ok_or(LevelRequirementError::UnknownLevel { level })?;
+        let max_level = max_level.unwrap_or_default();
+
+        if player_level < required_player_level {
+            return Err(LevelRequirementError::LevelRequirementNotMet { level, required_player_level, player_level });
+        }
+
+        if level > max_level {
+            return Err(LevelRequirementError::ExceedsMaxLevel { level, max_level });
+        }
+
+        Ok(())
+    }
+
+    /// gets the player's stats at their current level within `levels`
+    fn current_level_stats<'a>(&self, levels: &'a [PlayerLevelStats]) -> Option<&'a PlayerLevelStats> {
+        levels.iter().find(|stats| stats.level == self.player_level_mechanics.current_level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{load_catalog, GameCatalog, CATALOG_ROOT};
+
+    use super::*;
+
+    /// a player at level `level` who owns the rusty sword at level `weapon_level` and every skill at level `skill_level`
+    fn player(catalog: &GameCatalog, level: u8, weapon_level: u8, skill_level: u8) -> Player {
+        let mut player = Player::new(catalog.player_level(level).unwrap(), &catalog.player_levels);
+        player.inventory.weapons.push(catalog.weapon("Common Rusty Sword").unwrap().at_level(weapon_level).unwrap());
+        player.inventory.skills.extend(catalog.skills.iter().map(|skill| skill.at_level(skill_level).unwrap()));
+
+        player
+    }
+
+    fn equip_skill(player: &mut Player, catalog: &GameCatalog, name: &str, slot: usize) -> Result<Option<String>, LoadoutError> {
+        player.equip_skill(name, slot, &catalog.player_levels, &catalog.skill_level_requirements)
+    }
+
+    #[test]
+    fn skills_can_only_go_into_existing_slots() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player(&catalog, 1, 1, 1);
+
+        assert_eq!(equip_skill(&mut player, &catalog, "Fireball", SKILL_SLOT_COUNT), Err(LoadoutError::InvalidSlot { slot: SKILL_SLOT_COUNT }));
+        assert_eq!(player.unequip_skill(SKILL_SLOT_COUNT), Err(LoadoutError::InvalidSlot { slot: SKILL_SLOT_COUNT }));
+    }
+
+    #[test]
+    fn a_skill_can_only_be_in_one_slot() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player(&catalog, 1, 1, 1);
+
+        assert_eq!(equip_skill(&mut player, &catalog, "Fireball", 0), Ok(None));
+        assert_eq!(equip_skill(&mut player, &catalog, "Fireball", 1), Err(LoadoutError::AlreadySlotted { name: "Fireball".to_string(), slot: 0 }));
+        // putting it back into its own slot is fine
+        assert_eq!(equip_skill(&mut player, &catalog, "Fireball", 0), Ok(Some("Fireball".to_string())));
+        assert_eq!(equip_skill(&mut player, &catalog, "Sword Slam", 0), Ok(Some("Fireball".to_string())));
+        assert_eq!(equip_skill(&mut player, &catalog, "Fireball", 1), Ok(None));
+        assert_eq!(player.equipped_skill(0).map(|skill| skill.name.as_str()), Some("Sword Slam"));
+        assert_eq!(player.equipped_skill(1).map(|skill| skill.name.as_str()), Some("Fireball"));
+    }
+
+    #[test]
+    fn only_owned_weapons_and_skills_can_be_equipped() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = Player::new(catalog.player_level(1).unwrap(), &catalog.player_levels);
+
+        assert_eq!(
+            player.equip_weapon("Common Rusty Sword", &catalog.player_levels, &catalog.weapon_level_requirements),
+            Err(LoadoutError::NotInInventory { name: "Common Rusty Sword".to_string() }),
+        );
+        assert_eq!(equip_skill(&mut player, &catalog, "Fireball", 0), Err(LoadoutError::NotInInventory { name: "Fireball".to_string() }));
+    }
+
+    #[test]
+    fn levels_above_the_player_requirement_cant_be_equipped() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player(&catalog, 1, 2, 2);
+
+        // weapon level 2 requires player level 2, skill level 2 requires player level 5
+        assert_eq!(
+            player.equip_weapon("Common Rusty Sword", &catalog.player_levels, &catalog.weapon_level_requirements),
+            Err(LoadoutError::LevelRequirement {
+                name: "Common Rusty Sword".to_string(),
+                error: LevelRequirementError::LevelRequirementNotMet { level: 2, required_player_level: 2, player_level: 1 },
+            }),
+        );
+        assert_eq!(
+            equip_skill(&mut player, &catalog, "Fireball", 0),
+            Err(LoadoutError::LevelRequirement {
+                name: "Fireball".to_string(),
+                error: LevelRequirementError::LevelRequirementNotMet { level: 2, required_player_level: 5, player_level: 1 },
+            }),
+        );
+
+        let mut player = self::player(&catalog, 5, 2, 2);
+
+        assert_eq!(player.equip_weapon("Common Rusty Sword", &catalog.player_levels, &catalog.weapon_level_requirements), Ok(None));
+        assert_eq!(equip_skill(&mut player, &catalog, "Fireball", 0), Ok(None));
+    }
+
+    #[test]
+    fn levels_above_the_max_level_of_the_player_level_cant_be_equipped() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player(&catalog, 1, 1, 2);
+        // a table that lets level 1 players use level 2 skills, although the player level table caps them at level 1
+        let requirements = [
+            SkillLevelRequirement { level: 1, min_player_level_required: 1 },
+            SkillLevelRequirement { level: 2, min_player_level_required: 1 },
+        ];
+
+        assert_eq!(
+            player.equip_skill("Fireball", 0, &catalog.player_levels, &requirements),
+            Err(LoadoutError::LevelRequirement { name: "Fireball".to_string(), error: LevelRequirementError::ExceedsMaxLevel { level: 2, max_level: 1 } }),
+        );
+    }
+
+    #[test]
+    fn levels_missing_from_the_requirement_table_cant_be_equipped() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player(&catalog, 5, 2, 1);
+        let requirements = [WeaponLevelRequirement { level: 1, min_player_level_required: 1 }];
+
+        assert_eq!(
+            player.equip_weapon("Common Rusty Sword", &catalog.player_levels, &requirements),
+            Err(LoadoutError::LevelRequirement { name: "Common Rusty Sword".to_string(), error: LevelRequirementError::UnknownLevel { level: 2 } }),
+        );
+    }
+
+    #[test]
+    fn loadout_check_catches_items_that_left_the_inventory() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player(&catalog, 1, 1, 1);
+        let check = |player: &Player| player.check_loadout(&catalog.player_levels, &catalog.weapon_level_requirements, &catalog.skill_level_requirements);
+
+        player.equip_weapon("Common Rusty Sword", &catalog.player_levels, &catalog.weapon_level_requirements).unwrap();
+        equip_skill(&mut player, &catalog, "Fireball", 2).unwrap();
+        assert_eq!(check(&player), Ok(()));
+
+        player.inventory.skills.retain(|skill| skill.name != "Fireball");
+        assert_eq!(check(&player), Err(LoadoutError::NotInInventory { name: "Fireball".to_string() }));
+
+        player.inventory.weapons.clear();
+        assert_eq!(check(&player), Err(LoadoutError::NotInInventory { name: "Common Rusty Sword".to_string() }));
+        assert!(player.equipped_weapon().is_none());
+    }
+}
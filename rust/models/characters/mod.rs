@@ -1,5 +1,13 @@
 pub mod enemy;
 pub mod player;
+pub mod loadout;
+pub mod wallet;
+pub mod ledger;
+pub mod quest_log;
 
 pub use enemy::*;
-pub use player::*;
\ No newline at end of file
+pub use player::*;
+pub use loadout::*;
+pub use wallet::*;
+pub use ledger::*;
+pub use quest_log::*;
\ No newline at end of file
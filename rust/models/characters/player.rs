@@ -1,24 +1,153 @@
+use std::collections::BTreeSet;
+
 use serde::{Serialize, Deserialize};
 
-use crate::models::{Weapon, Skill, Item};
+use crate::models::{Weapon, Skill, Item, KillRewards, QuestCompletionRewards, Loadout, Wallet, Enemy, Quest, QuestLog};
 
 /// Represents the player's character and all data related to it.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Player {
     /// the player's level mechanics
     #[serde(rename = "levelMechanics")]
-    player_level_mechanics: PlayerLevelMechanics,
+    pub player_level_mechanics: PlayerLevelMechanics,
     /// the player's base HP at this level
     #[serde(rename = "baseHp")]
-    base_hp: f64,
+    pub base_hp: f64,
     /// how much HP the player regens per second this level
     #[serde(rename = "baseHpRegen")]
-    base_hp_regen: f64,
+    pub base_hp_regen: f64,
     /// how fast the player moves (units per second)
     #[serde(rename = "baseMovementSpeed")]
-    base_movement_speed: f64,
+    pub base_movement_speed: f64,
     /// the player's inventory
-    inventory: PlayerInventory,
+    pub inventory: PlayerInventory,
+    /// the weapon and skills the player currently fights with (out of `inventory`)
+    #[serde(default)]
+    pub loadout: Loadout,
+    /// the currencies and resources the player owns
+    #[serde(default)]
+    pub wallet: Wallet,
+    /// the player's progress through the quests
+    #[serde(default, rename = "questLog")]
+    pub quest_log: QuestLog,
+    /// the names of the breathable areas the player paid to unlock (areas unlocked by quests follow the quest log instead)
+    #[serde(default, rename = "unlockedAreas")]
+    pub unlocked_areas: BTreeSet<String>,
+}
+
+impl Player {
+    /// Creates a new player at the level described by `stats`, with no XP earned towards the next level, an empty inventory and wallet, nothing equipped, and no quests completed or areas unlocked.
+    /// 
+    /// The XP needed for the next level comes from `levels` (i.e. the player level table), like when levelling up (see `apply_level_stats`).
+    pub fn new(stats: &PlayerLevelStats, levels: &[PlayerLevelStats]) -> Self {
+        let mut player = Player {
+            player_level_mechanics: PlayerLevelMechanics {
+                current_level: stats.level,
+                next_level: stats.level,
+                total_xp_earned: 0,
+                current_level_xp: 0,
+                level_up_xp_required: 0,
+            },
+            base_hp: stats.base_hp,
+            base_hp_regen: stats.base_hp_regen,
+            base_movement_speed: stats.base_movement_speed,
+            inventory: PlayerInventory { weapons: Vec::new(), skills: Vec::new(), items: Vec::new() },
+            loadout: Loadout::default(),
+            wallet: Wallet::default(),
+            quest_log: QuestLog::default(),
+            unlocked_areas: BTreeSet::new(),
+        };
+        player.apply_level_stats(stats, levels);
+
+        player
+    }
+
+    /// Gives the player `xp` XP, levelling up as many times as it allows (up to the highest level of `levels`, i.e. the player level table).
+    /// 
+    /// Every level up recomputes the player's base stats from `levels`. Once the player is at max level,
+    /// XP is still added to `total_xp_earned` but no longer carried towards a next level.
+    /// 
+    /// Returns the level up that happened (if any).
+    pub fn gain_xp(&mut self, xp: u32, levels: &[PlayerLevelStats]) -> Option<LevelUp> {
+        let mechanics = &mut self.player_level_mechanics;
+        let from = mechanics.current_level;
+        mechanics.total_xp_earned = mechanics.total_xp_earned.saturating_add(xp);
+
+        if self.is_max_level(levels) {
+            return None;
+        }
+
+        self.player_level_mechanics.current_level_xp = self.player_level_mechanics.current_level_xp.saturating_add(xp);
+
+        while self.player_level_mechanics.current_level_xp >= self.player_level_mechanics.level_up_xp_required {
+            let next_level = self.player_level_mechanics.current_level.saturating_add(1);
+            let Some(stats) = levels.iter().find(|stats| stats.level == next_level) else {
+                break;
+            };
+
+            self.player_level_mechanics.current_level_xp -= self.player_level_mechanics.level_up_xp_required;
+            self.apply_level_stats(stats, levels);
+
+            if self.is_max_level(levels) {
+                self.player_level_mechanics.current_level_xp = 0;
+                break;
+            }
+        }
+
+        let to = self.player_level_mechanics.current_level;
+
+        (to > from).then_some(LevelUp { from, to })
+    }
+
+    /// Gives the player the XP of `rewards` for defeating an enemy (see `gain_xp`).
+    pub fn claim_kill_xp(&mut self, rewards: &KillRewards, levels: &[PlayerLevelStats]) -> Option<LevelUp> {
+        self.gain_xp(u32::from(rewards.xp), levels)
+    }
+
+    /// Gives the player the coins and XP for defeating `enemy` (see `gain_xp`), recording the coins in the wallet's ledger.
+    pub fn claim_kill(&mut self, enemy: &Enemy, levels: &[PlayerLevelStats]) -> Option<LevelUp> {
+        self.wallet.claim_kill_rewards(&enemy.kill_rewards, &enemy.name, enemy.level);
+        self.claim_kill_xp(&enemy.kill_rewards, levels)
+    }
+
+    /// Gives the player the coins, currencies and XP for completing `quest` (see `gain_xp`), recording the currencies in the wallet's ledger.
+    /// 
+    /// Skill and weapon rewards aren't granted here.
+    pub fn claim_quest(&mut self, quest: &Quest, levels: &[PlayerLevelStats]) -> Option<LevelUp> {
+        self.wallet.claim_quest_rewards(quest);
+        self.claim_quest_xp(&quest.completion_rewards, levels)
+    }
+
+    /// Gives the player the XP of `rewards` for completing a quest (see `gain_xp`).
+    pub fn claim_quest_xp(&mut self, rewards: &QuestCompletionRewards, levels: &[PlayerLevelStats]) -> Option<LevelUp> {
+        self.gain_xp(rewards.xp, levels)
+    }
+
+    /// Checks whether the player is at the highest level of `levels` (i.e. the player level table).
+    pub fn is_max_level(&self, levels: &[PlayerLevelStats]) -> bool {
+        levels.iter().all(|stats| stats.level <= self.player_level_mechanics.current_level)
+    }
+
+    /// Moves the player to the level of `stats`, recomputing their base stats and the XP needed for the next level in `levels` (none at max level).
+    pub fn apply_level_stats(&mut self, stats: &PlayerLevelStats, levels: &[PlayerLevelStats]) {
+        let has_next_level = levels.iter().any(|next| next.level > stats.level);
+
+        self.player_level_mechanics.current_level = stats.level;
+        self.player_level_mechanics.next_level = if has_next_level { stats.level.saturating_add(1) } else { stats.level };
+        self.player_level_mechanics.level_up_xp_required = if has_next_level { stats.level_up_xp_required } else { 0 };
+        self.base_hp = stats.base_hp;
+        self.base_hp_regen = stats.base_hp_regen;
+        self.base_movement_speed = stats.base_movement_speed;
+    }
+}
+
+/// Represents the player levelling up from `from` to `to` (which can be more than 1 level apart).
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct LevelUp {
+    /// the player's level before levelling up
+    pub from: u8,
+    /// the player's level after levelling up
+    pub to: u8,
 }
 
 /// Represents the mechanics of the player's level (incl. requirements to level up).
@@ -26,16 +155,19 @@ pub struct Player {
 pub struct PlayerLevelMechanics {
     /// the player's current level
     #[serde(rename = "currentLevel")]
-    current_level: u8,
+    pub current_level: u8,
     /// the player's next level
     #[serde(rename = "nextLevel")]
-    next_level: u8,
+    pub next_level: u8,
     /// the amount of xp the player has accumulated thus far
     #[serde(rename = "totalXpEarned")]
-    total_xp_earned: u32,
+    pub total_xp_earned: u32,
+    /// the amount of xp the player has accumulated towards `next_level`
+    #[serde(rename = "currentLevelXp", default)]
+    pub current_level_xp: u32,
     /// the amount of xp needed to level up to `next_level`
     #[serde(rename = "levelUpXpRequired")]
-    level_up_xp_required: u32,
+    pub level_up_xp_required: u32,
 }
 
 /// Represents the player's inventory.
@@ -44,9 +176,126 @@ pub struct PlayerLevelMechanics {
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct PlayerInventory {
     /// the player's weapons
-    weapons: Vec<Weapon>,
+    pub weapons: Vec<Weapon>,
     /// the player's skills
-    skills: Vec<Skill>,
+    pub skills: Vec<Skill>,
     /// the player's items
-    items: Vec<Item>,
-}
\ No newline at end of file
+    pub items: Vec<Item>,
+}
+
+/// Represents the player's stats and limits at a specific level (as stored in `mechanics/player/playerLevelMechanics.json`).
+/// 
+/// This is NOT the same as `PlayerLevelMechanics`, which represents the live level state of a `Player` instance.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PlayerLevelStats {
+    /// the player's level
+    pub level: u8,
+    /// the player's base HP at this level
+    #[serde(rename = "baseHp")]
+    pub base_hp: f64,
+    /// how much HP the player regens per second at this level
+    #[serde(rename = "baseHpRegen")]
+    pub base_hp_regen: f64,
+    /// how fast the player moves (units per second) at this level
+    #[serde(rename = "baseMovementSpeed")]
+    pub base_movement_speed: f64,
+    /// the amount of xp needed to level up from this level to the next level
+    #[serde(rename = "levelUpXpRequired")]
+    pub level_up_xp_required: u32,
+    /// the maximum skill level allowed (for any skill) at this level
+    #[serde(rename = "maxSkillLevel")]
+    pub max_skill_level: u8,
+    /// the maximum weapon level allowed (for any weapon) at this level
+    #[serde(rename = "maxWeaponLevel")]
+    pub max_weapon_level: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// levels 1 to 3, which need 100 and 200 XP to level up from (level 3 is the max level)
+    fn levels() -> Vec<PlayerLevelStats> {
+        [(1, 100), (2, 200), (3, 300)]
+            .into_iter()
+            .map(|(level, level_up_xp_required)| PlayerLevelStats {
+                level,
+                base_hp: f64::from(level) * 100.0,
+                base_hp_regen: f64::from(level),
+                base_movement_speed: 150.0,
+                level_up_xp_required,
+                max_skill_level: level,
+                max_weapon_level: level,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn new_player_needs_the_level_up_xp_of_its_level() {
+        let levels = levels();
+        let player = Player::new(&levels[0], &levels);
+
+        assert_eq!(player.player_level_mechanics.next_level, 2);
+        assert_eq!(player.player_level_mechanics.level_up_xp_required, 100);
+        assert_eq!(player.base_hp, 100.0);
+    }
+
+    #[test]
+    fn new_player_at_max_level_has_no_next_level() {
+        let levels = levels();
+        let player = Player::new(&levels[2], &levels);
+
+        assert_eq!(player.player_level_mechanics.next_level, 3);
+        assert_eq!(player.player_level_mechanics.level_up_xp_required, 0);
+        assert!(player.is_max_level(&levels));
+    }
+
+    #[test]
+    fn xp_below_the_requirement_doesnt_level_up() {
+        let levels = levels();
+        let mut player = Player::new(&levels[0], &levels);
+
+        assert_eq!(player.gain_xp(99, &levels), None);
+        assert_eq!(player.player_level_mechanics.current_level, 1);
+        assert_eq!(player.player_level_mechanics.current_level_xp, 99);
+    }
+
+    #[test]
+    fn xp_can_level_up_several_times_at_once() {
+        let levels = levels();
+        let mut player = Player::new(&levels[0], &levels);
+
+        player.gain_xp(50, &levels);
+
+        assert_eq!(player.gain_xp(260, &levels), Some(LevelUp { from: 1, to: 3 }));
+        assert_eq!(player.player_level_mechanics.total_xp_earned, 310);
+        assert_eq!(player.base_hp, 300.0);
+        assert_eq!(player.base_hp_regen, 3.0);
+    }
+
+    #[test]
+    fn leftover_xp_carries_over_to_the_next_level() {
+        let levels = levels();
+        let mut player = Player::new(&levels[0], &levels);
+
+        assert_eq!(player.gain_xp(150, &levels), Some(LevelUp { from: 1, to: 2 }));
+        assert_eq!(player.player_level_mechanics.current_level_xp, 50);
+        assert_eq!(player.player_level_mechanics.next_level, 3);
+        assert_eq!(player.player_level_mechanics.level_up_xp_required, 200);
+    }
+
+    #[test]
+    fn xp_stops_counting_towards_a_level_at_the_level_cap() {
+        let levels = levels();
+        let mut player = Player::new(&levels[0], &levels);
+
+        assert_eq!(player.gain_xp(10_000, &levels), Some(LevelUp { from: 1, to: 3 }));
+        assert_eq!(player.player_level_mechanics.current_level_xp, 0);
+        assert_eq!(player.player_level_mechanics.level_up_xp_required, 0);
+
+        assert_eq!(player.gain_xp(500, &levels), None);
+        assert_eq!(player.player_level_mechanics.current_level, 3);
+        assert_eq!(player.player_level_mechanics.current_level_xp, 0);
+        assert_eq!(player.player_level_mechanics.total_xp_earned, 10_500);
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Serialize, Deserialize};
+
+use crate::models::{Quest, Resource};
+
+/// Represents the player's progress through the quests of `mechanics/quests.json`.
+///
+/// Quests unlock one at a time by chapter, then by ID (regardless of the order they are defined in),
+/// so the log only has to keep track of the completed quests and the progress towards the active one.
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
+pub struct QuestLog {
+    /// the progress made towards the active quest
+    pub progress: QuestProgress,
+    /// the amount of enemies the player has defeated in total
+    #[serde(rename = "totalKills")]
+    pub total_kills: u32,
+    /// the chapter and ID of every completed quest
+    pub completed: BTreeSet<(u8, u8)>,
+}
+
+impl QuestLog {
+    /// Gets the quest the player is currently working towards among `quests` (`None` once every quest is completed).
+    ///
+    /// This is the first quest (by chapter, then by ID) that isn't completed yet.
+    pub fn active_quest<'a>(&self, quests: &'a [Quest]) -> Option<&'a Quest> {
+        quests.iter().filter(|quest| !self.is_completed(quest)).min_by_key(|quest| (quest.chapter, quest.id))
+    }
+
+    /// Checks whether `quest` is completed.
+    pub fn is_completed(&self, quest: &Quest) -> bool {
+        self.completed.contains(&(quest.chapter, quest.id))
+    }
+}
+
+/// Represents the progress made towards a quest since it became active.
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
+pub struct QuestProgress {
+    /// the amount of each item obtained (by name)
+    pub items: BTreeMap<String, u32>,
+    /// the amount of each resource obtained
+    pub resources: BTreeMap<Resource, u32>,
+    /// the amount of each enemy defeated (by name)
+    pub enemies: BTreeMap<String, u32>,
+    /// whether the area was reached or the NPC talked to (for quests without requirements)
+    pub reached: bool,
+}
+
+impl QuestProgress {
+    /// Checks whether the progress meets every requirement of `quest`, with `total_kills` being the amount of enemies the player has defeated in total.
+    ///
+    /// Quests without a type are met once their area is reached (or their NPC talked to; see `Quest::target`).
+    pub fn meets(&self, quest: &Quest, total_kills: u32) -> bool {
+        let Some(quest_type) = &quest.quest_type else {
+            return self.reached;
+        };
+        let count = |counts: &BTreeMap<String, u32>, name: &str| counts.get(name).copied().unwrap_or_default();
+
+        quest_type.items_required.iter().flatten().all(|required| count(&self.items, &required.item) >= required.amount)
+            && quest_type.resources_required
+                .iter()
+                .flatten()
+                .all(|required| self.resources.get(&required.resource).copied().unwrap_or_default() >= required.amount)
+            && quest_type.enemies_required.iter().flatten().all(|required| count(&self.enemies, &required.enemy) >= required.amount)
+            && quest_type.kills_required.is_none_or(|kills_required| total_kills >= kills_required)
+    }
+}
@@ -0,0 +1,267 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Serialize, Deserialize};
+
+use crate::models::{Cost, Currency, Item, ItemType, KillRewards, Ledger, LedgerEntry, LedgerReason, Quest};
+
+/// Represents the currencies and resources the player owns.
+///
+/// Balances can only change through `deposit`, `withdraw` and `spend` (or the helpers built on them),
+/// so that every change is recorded in the wallet's `Ledger`.
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
+pub struct Wallet {
+    /// the amount owned of each currency (currencies that were never obtained are left out)
+    balances: BTreeMap<Currency, u32>,
+    /// every change made to `balances`
+    #[serde(default)]
+    ledger: Ledger,
+}
+
+impl Wallet {
+    /// Gets the amount of `currency` owned.
+    pub fn balance(&self, currency: Currency) -> u32 {
+        self.balances.get(&currency).copied().unwrap_or_default()
+    }
+
+    /// Gets the record of every change made to the wallet.
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    /// Adds `amount` of `currency` to the wallet because of `reason`.
+    ///
+    /// Balances are capped at `u32::MAX`, so only the amount that fits is added (and recorded).
+    pub fn deposit(&mut self, currency: Currency, amount: u32, reason: LedgerReason) {
+        let balance = self.balances.entry(currency).or_default();
+        let added = amount.min(u32::MAX - *balance);
+
+        if added == 0 {
+            return;
+        }
+
+        *balance += added;
+
+        let balance = *balance;
+        self.ledger.record(LedgerEntry { currency, amount: i64::from(added), balance, reason });
+    }
+
+    /// Takes `amount` of `currency` out of the wallet because of `reason`.
+    pub fn withdraw(&mut self, currency: Currency, amount: u32, reason: LedgerReason) -> Result<(), InsufficientFunds> {
+        let available = self.balance(currency);
+
+        if available < amount {
+            return Err(InsufficientFunds { shortfalls: vec![Shortfall { currency, required: amount, available }] });
+        }
+
+        if amount > 0 {
+            self.balances.insert(currency, available - amount);
+            self.ledger.record(LedgerEntry { currency, amount: -i64::from(amount), balance: available - amount, reason });
+        }
+
+        Ok(())
+    }
+
+    /// Gets every currency the wallet doesn't have enough of to pay `cost` (empty if it can be paid).
+    pub fn shortfalls(&self, cost: &impl Cost) -> Vec<Shortfall> {
+        cost.amounts()
+            .into_iter()
+            .filter(|&(currency, required)| self.balance(currency) < required)
+            .map(|(currency, required)| Shortfall { currency, required, available: self.balance(currency) })
+            .collect()
+    }
+
+    /// Checks whether the wallet has enough of every currency to pay `cost`.
+    pub fn can_afford(&self, cost: &impl Cost) -> bool {
+        self.shortfalls(cost).is_empty()
+    }
+
+    /// Pays `cost` out of the wallet because of `reason`.
+    ///
+    /// Nothing is taken out if the wallet can't pay all of it.
+    pub fn spend(&mut self, cost: &impl Cost, reason: LedgerReason) -> Result<(), InsufficientFunds> {
+        let shortfalls = self.shortfalls(cost);
+
+        if !shortfalls.is_empty() {
+            return Err(InsufficientFunds { shortfalls });
+        }
+
+        // `amounts` lists each currency once with its total, so every withdrawal was covered by the check above
+        for (currency, amount) in cost.amounts() {
+            let balance = self.balance(currency) - amount;
+
+            self.balances.insert(currency, balance);
+            self.ledger.record(LedgerEntry { currency, amount: -i64::from(amount), balance, reason: reason.clone() });
+        }
+
+        Ok(())
+    }
+
+    /// Adds the coins of `rewards` for defeating `enemy` at level `level`.
+    pub fn claim_kill_rewards(&mut self, rewards: &KillRewards, enemy: &str, level: u8) {
+        self.deposit(Currency::Coins, u32::from(rewards.coins), LedgerReason::Kill { enemy: enemy.to_string(), level });
+    }
+
+    /// Adds the coins, energy cores and resources of `quest`'s completion rewards (skills and weapons are left to the quest system).
+    pub fn claim_quest_rewards(&mut self, quest: &Quest) {
+        let reason = LedgerReason::Quest { chapter: quest.chapter, id: quest.id };
+        let rewards = &quest.completion_rewards;
+
+        self.deposit(Currency::Coins, rewards.coins, reason.clone());
+
+        for misc in rewards.misc.iter().flatten() {
+            if let Some(currency) = misc.reward_type.currency() {
+                self.deposit(currency, misc.amount, reason.clone());
+            }
+        }
+    }
+
+    /// Adds the currency `count` of `item` are worth if it's a `Misc Currency` item (e.g. `Blueberry` or `Energy Core`).
+    ///
+    /// Returns whether anything was added.
+    pub fn collect_item(&mut self, item: &Item, count: u32) -> bool {
+        let Some(currency) = Currency::from_item_name(&item.name) else {
+            return false;
+        };
+        let worth: f64 = item.attributes
+            .iter()
+            .filter(|attribute| attribute.item_type == ItemType::MiscCurrency)
+            .filter_map(|attribute| attribute.modifier.values.first())
+            .sum();
+        let amount = (worth * f64::from(count)).round() as u32;
+
+        self.deposit(currency, amount, LedgerReason::Item { name: item.name.clone() });
+
+        amount > 0
+    }
+}
+
+/// Represents a currency the wallet doesn't have enough of.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Shortfall {
+    /// the currency that is lacking
+    pub currency: Currency,
+    /// the amount required
+    pub required: u32,
+    /// the amount owned
+    pub available: u32,
+}
+
+/// Represents a cost that couldn't be paid because the wallet lacks one or more currencies.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InsufficientFunds {
+    /// every currency that is lacking
+    pub shortfalls: Vec<Shortfall>,
+}
+
+impl fmt::Display for InsufficientFunds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shortfalls: Vec<String> = self.shortfalls
+            .iter()
+            .map(|shortfall| format!("{} {} (has {})", shortfall.required, shortfall.currency, shortfall.available))
+            .collect();
+
+        write!(f, "not enough funds: requires {}", shortfalls.join(", "))
+    }
+}
+
+impl std::error::Error for InsufficientFunds {}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Resource, ResourceCost, UpgradeCost};
+
+    use super::*;
+
+    fn adjustment() -> LedgerReason {
+        LedgerReason::Adjustment { note: "test".to_string() }
+    }
+
+    fn wallet_with(balances: &[(Currency, u32)]) -> Wallet {
+        let mut wallet = Wallet::default();
+
+        for &(currency, amount) in balances {
+            wallet.deposit(currency, amount, adjustment());
+        }
+
+        wallet
+    }
+
+    fn cost(coins: u32, resources: &[(Resource, u32)]) -> UpgradeCost {
+        UpgradeCost {
+            coins,
+            energy_cores: 0,
+            resources: Some(resources.iter().map(|&(resource, amount)| ResourceCost { resource, amount }).collect()),
+        }
+    }
+
+    #[test]
+    fn deposit_records_only_what_fits() {
+        let mut wallet = wallet_with(&[(Currency::Coins, u32::MAX - 5)]);
+
+        wallet.deposit(Currency::Coins, 10, adjustment());
+        wallet.deposit(Currency::Coins, 10, adjustment());
+        wallet.deposit(Currency::Wood, 0, adjustment());
+
+        assert_eq!(wallet.balance(Currency::Coins), u32::MAX);
+        assert_eq!(wallet.balance(Currency::Wood), 0);
+
+        let amounts: Vec<i64> = wallet.ledger().entries().iter().map(|entry| entry.amount).collect();
+        assert_eq!(amounts, vec![i64::from(u32::MAX - 5), 5]);
+        assert_eq!(wallet.ledger().summary()[&Currency::Coins].earned, u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn withdraw_fails_without_enough_funds() {
+        let mut wallet = wallet_with(&[(Currency::Coins, 5)]);
+
+        assert_eq!(
+            wallet.withdraw(Currency::Coins, 6, adjustment()),
+            Err(InsufficientFunds { shortfalls: vec![Shortfall { currency: Currency::Coins, required: 6, available: 5 }] })
+        );
+        assert_eq!(wallet.withdraw(Currency::Coins, 5, adjustment()), Ok(()));
+        assert_eq!(wallet.balance(Currency::Coins), 0);
+        assert_eq!(wallet.ledger().entries().len(), 2);
+    }
+
+    #[test]
+    fn spend_takes_every_currency_and_records_it() {
+        let mut wallet = wallet_with(&[(Currency::Coins, 50), (Currency::Wood, 10), (Currency::Coal, 3)]);
+
+        wallet.spend(&cost(20, &[(Resource::Wood, 4), (Resource::Coal, 3)]), LedgerReason::Upgrade { name: "Fireball".to_string(), level: 2 }).unwrap();
+
+        assert_eq!((wallet.balance(Currency::Coins), wallet.balance(Currency::Wood), wallet.balance(Currency::Coal)), (30, 6, 0));
+
+        let spent: Vec<(Currency, i64, u32)> = wallet.ledger().entries()[3..].iter().map(|entry| (entry.currency, entry.amount, entry.balance)).collect();
+        assert_eq!(spent, vec![(Currency::Coins, -20, 30), (Currency::Wood, -4, 6), (Currency::Coal, -3, 0)]);
+    }
+
+    #[test]
+    fn spend_takes_nothing_if_any_currency_is_short() {
+        let mut wallet = wallet_with(&[(Currency::Coins, 50), (Currency::Wood, 2)]);
+
+        let error = wallet.spend(&cost(20, &[(Resource::Wood, 4), (Resource::Stone, 1)]), adjustment()).unwrap_err();
+
+        assert_eq!(
+            error.shortfalls,
+            vec![Shortfall { currency: Currency::Wood, required: 4, available: 2 }, Shortfall { currency: Currency::Stone, required: 1, available: 0 }]
+        );
+        assert_eq!((wallet.balance(Currency::Coins), wallet.balance(Currency::Wood)), (50, 2));
+        assert_eq!(wallet.ledger().entries().len(), 2);
+    }
+
+    #[test]
+    fn spend_adds_up_resources_listed_more_than_once() {
+        let mut wallet = wallet_with(&[(Currency::Wood, 5)]);
+        let duplicated = cost(0, &[(Resource::Wood, 3), (Resource::Wood, 3)]);
+
+        assert!(!wallet.can_afford(&duplicated));
+        assert_eq!(wallet.spend(&duplicated, adjustment()).unwrap_err().shortfalls, vec![Shortfall { currency: Currency::Wood, required: 6, available: 5 }]);
+        assert_eq!(wallet.balance(Currency::Wood), 5);
+
+        wallet.deposit(Currency::Wood, 1, adjustment());
+        wallet.spend(&duplicated, adjustment()).unwrap();
+
+        assert_eq!(wallet.balance(Currency::Wood), 0);
+        assert_eq!(wallet.ledger().entries().last().map(|entry| entry.amount), Some(-6));
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Serialize, Deserialize};
+
+use crate::models::PurchaseCost;
+
+/// Represents how breathable areas are unlocked and what happens outside of them (as stored in `mechanics/breathableAreas.json`).
+///
+/// The areas themselves are objects of the map's `... Breathable Area` layers (e.g. `Beacon 1 Breathable Area 2`);
+/// this only describes their progression.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BreathableAreaParameters {
+    /// what happens to the player outside of every unlocked breathable area
+    #[serde(rename = "outsidePenalty")]
+    pub outside_penalty: SuffocationPenalty,
+    /// how each breathable area is unlocked
+    pub areas: Vec<BreathableAreaUnlock>,
+}
+
+/// Represents the damage the player takes over time outside of every unlocked breathable area.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SuffocationPenalty {
+    /// how long (in seconds) the player can stay outside before taking damage
+    #[serde(rename = "graceTime")]
+    pub grace_time: f64,
+    /// the ratio (from 0 to 1) of the player's base HP lost per second once the grace time is over
+    #[serde(rename = "hpRatioPerSecond")]
+    pub hp_ratio_per_second: f64,
+}
+
+impl SuffocationPenalty {
+    /// Gets the damage a player with `base_hp` HP takes while staying outside for `seconds` seconds,
+    /// having already been outside for `outside_for` seconds beforehand.
+    pub fn damage(&self, base_hp: f64, outside_for: f64, seconds: f64) -> f64 {
+        let suffocating_for = (outside_for + seconds) - outside_for.max(self.grace_time);
+
+        base_hp * self.hp_ratio_per_second * suffocating_for.max(0.0)
+    }
+}
+
+/// Represents what unlocks a breathable area.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BreathableAreaUnlock {
+    /// the name of the breathable area's object within the map
+    pub area: String,
+    /// what unlocks the area (`None` if it is unlocked from the start)
+    #[serde(rename = "unlockedBy")]
+    pub unlocked_by: Option<UnlockRequirement>,
+}
+
+/// A list of all ways a breathable area can be unlocked.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum UnlockRequirement {
+    /// completing the quest with this description (as shown in `quests.json`)
+    Quest { description: String },
+    /// paying `cost` out of the player's wallet
+    Cost { cost: PurchaseCost },
+}
@@ -0,0 +1,160 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Serialize, Deserialize};
+
+use crate::models::{Resource, ResourceCost};
+
+/// A list of every currency the player can own (including resources).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub enum Currency {
+    #[serde(rename = "Coins")]
+    Coins,
+    #[serde(rename = "Energy Cores")]
+    EnergyCores,
+    /// obtained by collecting the `Blueberry` item
+    #[serde(rename = "Blueberries")]
+    Blueberries,
+    #[serde(rename = "Wood")]
+    Wood,
+    #[serde(rename = "Stone")]
+    Stone,
+    #[serde(rename = "Coal")]
+    Coal,
+}
+
+impl Currency {
+    /// Gets the currency a `Misc Currency` item with the name `name` is collected as (if any).
+    pub fn from_item_name(name: &str) -> Option<Self> {
+        match name {
+            "Blueberry" => Some(Currency::Blueberries),
+            "Energy Core" => Some(Currency::EnergyCores),
+            _ => None,
+        }
+    }
+}
+
+impl From<Resource> for Currency {
+    fn from(resource: Resource) -> Self {
+        match resource {
+            Resource::Wood => Currency::Wood,
+            Resource::Stone => Currency::Stone,
+            Resource::Coal => Currency::Coal,
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Currency::Coins => write!(f, "coins"),
+            Currency::EnergyCores => write!(f, "energy cores"),
+            Currency::Blueberries => write!(f, "blueberries"),
+            Currency::Wood => write!(f, "wood"),
+            Currency::Stone => write!(f, "stone"),
+            Currency::Coal => write!(f, "coal"),
+        }
+    }
+}
+
+/// Implemented by every kind of cost (purchase or upgrade) so that they can be paid the same way.
+pub trait Cost {
+    /// the amount of coins required
+    fn coins(&self) -> u32;
+    /// the amount of energy cores required
+    fn energy_cores(&self) -> u32;
+    /// the resources required (empty if none)
+    fn resources(&self) -> &[ResourceCost];
+
+    /// Gets the total amount required of each currency, ordered by currency (a resource listed more than once is added up,
+    /// and currencies that aren't required are left out).
+    fn amounts(&self) -> Vec<(Currency, u32)> {
+        let mut amounts = BTreeMap::new();
+        let resources = self.resources().iter().map(|cost| (Currency::from(cost.resource), cost.amount));
+
+        for (currency, amount) in [(Currency::Coins, self.coins()), (Currency::EnergyCores, self.energy_cores())].into_iter().chain(resources) {
+            let total: &mut u32 = amounts.entry(currency).or_default();
+            *total = total.saturating_add(amount);
+        }
+
+        amounts.into_iter().filter(|&(_, amount)| amount > 0).collect()
+    }
+}
+
+/// Represents the cost required to purchase either a skill or a weapon.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PurchaseCost {
+    /// the amount of coins required
+    pub coins: u32,
+    /// the amount of energy cores required
+    #[serde(rename = "energyCores")]
+    pub energy_cores: u32,
+    /// the resources required (if any)
+    pub resources: Option<Vec<ResourceCost>>,
+}
+
+/// Represents the cost required to upgrade either a skill or a weapon to a certain level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UpgradeCost {
+    /// the amount of coins required
+    pub coins: u32,
+    /// the amount of energy cores required
+    #[serde(rename = "energyCores")]
+    pub energy_cores: u32,
+    /// the resources required (if any)
+    pub resources: Option<Vec<ResourceCost>>,
+}
+
+impl Cost for PurchaseCost {
+    fn coins(&self) -> u32 {
+        self.coins
+    }
+
+    fn energy_cores(&self) -> u32 {
+        self.energy_cores
+    }
+
+    fn resources(&self) -> &[ResourceCost] {
+        self.resources.as_deref().unwrap_or_default()
+    }
+}
+
+impl Cost for UpgradeCost {
+    fn coins(&self) -> u32 {
+        self.coins
+    }
+
+    fn energy_cores(&self) -> u32 {
+        self.energy_cores
+    }
+
+    fn resources(&self) -> &[ResourceCost] {
+        self.resources.as_deref().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(resource: Resource, amount: u32) -> ResourceCost {
+        ResourceCost { resource, amount }
+    }
+
+    #[test]
+    fn amounts_add_up_resources_listed_more_than_once() {
+        let cost = UpgradeCost {
+            coins: 10,
+            energy_cores: 0,
+            resources: Some(vec![resource(Resource::Coal, 2), resource(Resource::Wood, 5), resource(Resource::Coal, 3), resource(Resource::Stone, 0)]),
+        };
+
+        assert_eq!(cost.amounts(), vec![(Currency::Coins, 10), (Currency::Wood, 5), (Currency::Coal, 5)]);
+    }
+
+    #[test]
+    fn amounts_saturate_instead_of_overflowing() {
+        let cost = PurchaseCost { coins: 0, energy_cores: 0, resources: Some(vec![resource(Resource::Wood, u32::MAX), resource(Resource::Wood, 1)]) };
+
+        assert_eq!(cost.amounts(), vec![(Currency::Wood, u32::MAX)]);
+    }
+}
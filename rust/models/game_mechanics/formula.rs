@@ -0,0 +1,772 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Serialize, Deserialize};
+
+/// Represents a balance curve written as an expression (e.g. `floor(level / 1.5 + 5)`), which can be stored in content files
+/// either as a string or as a plain number.
+///
+/// The expression language supports:
+/// 1. numbers and variables (e.g. `level`; which ones are available depends on where the formula is used).
+/// 2. arithmetic (`+`, `-`, `*`, `/`, `%` and `^` for powers) and parentheses.
+/// 3. comparisons (`<`, `<=`, `>`, `>=`, `==`, `!=`) and logic (`&&`, `||`, `!`), where true is 1 and false is 0.
+/// 4. the functions `floor(x)`, `ceil(x)`, `round(x)`, `round(x, digits)`, `abs(x)`, `sqrt(x)`, `pow(x, y)`, `min(x, ...)`, `max(x, ...)`,
+///    `clamp(x, min, max)`, `if(condition, then, otherwise)` and `piecewise(condition, value, ..., otherwise)`, where `if` and `piecewise`
+///    only evaluate the branch they pick.
+///
+/// Formulas are parsed when they are deserialized, so syntax errors are reported (with the offending column) while loading the file.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(try_from = "RawFormula", into = "RawFormula")]
+pub struct Formula {
+    /// the formula as written
+    source: String,
+    /// the parsed formula
+    expression: Expression,
+}
+
+impl Formula {
+    /// Parses `source` into a formula.
+    pub fn parse(source: &str) -> Result<Self, FormulaError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { source, tokens, position: 0 };
+        let expression = parser.parse_expression()?;
+
+        match parser.tokens.get(parser.position) {
+            Some(token) => Err(parser.error_at(token.column, FormulaErrorKind::UnexpectedToken(token.kind.to_string()))),
+            None => Ok(Formula { source: source.to_string(), expression }),
+        }
+    }
+
+    /// Creates a formula that always evaluates to `value`.
+    pub fn constant(value: f64) -> Self {
+        Formula { source: value.to_string(), expression: Expression::Number(value) }
+    }
+
+    /// Gets the formula as written.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Gets the name and column of every variable the formula uses (in the order they are written).
+    pub fn variables(&self) -> Vec<(&str, usize)> {
+        let mut variables = Vec::new();
+        self.expression.collect_variables(&mut variables);
+
+        variables
+    }
+
+    /// Checks that the formula only uses variables within `allowed`.
+    pub fn check_variables(&self, allowed: &[&str]) -> Result<(), FormulaError> {
+        match self.variables().into_iter().find(|(name, _)| !allowed.contains(name)) {
+            Some((name, column)) => Err(self.error_at(column, FormulaErrorKind::UnknownVariable(name.to_string()))),
+            None => Ok(()),
+        }
+    }
+
+    /// Evaluates the formula with `variables` (pairs of names and values).
+    ///
+    /// Returns an error if the formula uses a variable that isn't within `variables` or divides by zero.
+    pub fn evaluate(&self, variables: &[(&str, f64)]) -> Result<f64, FormulaError> {
+        self.expression.evaluate(variables).map_err(|(column, kind)| self.error_at(column, kind))
+    }
+
+    fn error_at(&self, column: usize, kind: FormulaErrorKind) -> FormulaError {
+        FormulaError { source: self.source.clone(), column, kind }
+    }
+}
+
+impl FromStr for Formula {
+    type Err = FormulaError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Formula::parse(source)
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// how a formula is stored in content files
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawFormula {
+    Number(f64),
+    Expression(String),
+}
+
+impl TryFrom<RawFormula> for Formula {
+    type Error = FormulaError;
+
+    fn try_from(raw: RawFormula) -> Result<Self, Self::Error> {
+        match raw {
+            RawFormula::Number(value) => Ok(Formula::constant(value)),
+            RawFormula::Expression(source) => Formula::parse(&source),
+        }
+    }
+}
+
+impl From<Formula> for RawFormula {
+    fn from(formula: Formula) -> Self {
+        match formula.expression {
+            Expression::Number(value) if formula.source == value.to_string() => RawFormula::Number(value),
+            _ => RawFormula::Expression(formula.source),
+        }
+    }
+}
+
+/// Represents an error found while parsing or evaluating a formula.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FormulaError {
+    /// the formula as written
+    pub source: String,
+    /// the column (starting from 1) of the offending part of the formula
+    pub column: usize,
+    /// what went wrong
+    pub kind: FormulaErrorKind,
+}
+
+impl fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {} of formula `{}`", self.kind, self.column, self.source)
+    }
+}
+
+impl std::error::Error for FormulaError {}
+
+/// A list of all errors a formula can have.
+#[derive(Clone, PartialEq, Debug)]
+pub enum FormulaErrorKind {
+    /// a character that isn't part of the language
+    UnexpectedCharacter(char),
+    /// a token that doesn't fit where it is
+    UnexpectedToken(String),
+    /// the formula ended while a value or closing parenthesis was expected
+    UnexpectedEnd,
+    /// a number that couldn't be read
+    InvalidNumber(String),
+    /// a function that doesn't exist
+    UnknownFunction(String),
+    /// a function called with an invalid amount of arguments
+    WrongArgumentCount { function: String, expected: &'static str, got: usize },
+    /// a variable that isn't available
+    UnknownVariable(String),
+    /// a division (or remainder) by zero
+    DivisionByZero,
+}
+
+impl fmt::Display for FormulaErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormulaErrorKind::UnexpectedCharacter(character) => write!(f, "unexpected character `{}`", character),
+            FormulaErrorKind::UnexpectedToken(token) => write!(f, "unexpected `{}`", token),
+            FormulaErrorKind::UnexpectedEnd => write!(f, "unexpected end of formula"),
+            FormulaErrorKind::InvalidNumber(number) => write!(f, "invalid number `{}`", number),
+            FormulaErrorKind::UnknownFunction(name) => write!(f, "unknown function `{}`", name),
+            FormulaErrorKind::WrongArgumentCount { function, expected, got } => {
+                write!(f, "`{}` takes {} argument(s), got {}", function, expected, got)
+            }
+            FormulaErrorKind::UnknownVariable(name) => write!(f, "unknown variable `{}`", name),
+            FormulaErrorKind::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+/// a parsed formula
+#[derive(Clone, PartialEq, Debug)]
+enum Expression {
+    Number(f64),
+    /// a variable's name and column
+    Variable(String, usize),
+    Negate(Box<Expression>),
+    Not(Box<Expression>),
+    /// an operator, its column and its operands
+    Binary(BinaryOperator, usize, Box<Expression>, Box<Expression>),
+    Call(Function, Vec<Expression>),
+}
+
+impl Expression {
+    fn collect_variables<'a>(&'a self, variables: &mut Vec<(&'a str, usize)>) {
+        match self {
+            Expression::Number(_) => {}
+            Expression::Variable(name, column) => variables.push((name, *column)),
+            Expression::Negate(operand) | Expression::Not(operand) => operand.collect_variables(variables),
+            Expression::Binary(_, _, left, right) => {
+                left.collect_variables(variables);
+                right.collect_variables(variables);
+            }
+            Expression::Call(_, arguments) => arguments.iter().for_each(|argument| argument.collect_variables(variables)),
+        }
+    }
+
+    /// evaluates the expression, failing with the column and kind of the first error
+    fn evaluate(&self, variables: &[(&str, f64)]) -> Result<f64, (usize, FormulaErrorKind)> {
+        let value = match self {
+            Expression::Number(value) => *value,
+            Expression::Variable(name, column) => variables
+                .iter()
+                .find(|(variable, _)| variable == name)
+                .map(|&(_, value)| value)
+                .ok_or_else(|| (*column, FormulaErrorKind::UnknownVariable(name.clone())))?,
+            Expression::Negate(operand) => -operand.evaluate(variables)?,
+            Expression::Not(operand) => from_bool(!is_true(operand.evaluate(variables)?)),
+            Expression::Binary(operator, column, left, right) => {
+                let (left, right) = (left.evaluate(variables)?, right.evaluate(variables)?);
+
+                if matches!(operator, BinaryOperator::Divide | BinaryOperator::Remainder) && right == 0.0 {
+                    return Err((*column, FormulaErrorKind::DivisionByZero));
+                }
+
+                operator.apply(left, right)
+            }
+            // only the picked branch is evaluated, so e.g. `if(level > 1, 100 / (level - 1), 0)` is fine at level 1
+            Expression::Call(Function::If, arguments) => {
+                let branch = if is_true(arguments[0].evaluate(variables)?) { &arguments[1] } else { &arguments[2] };
+                branch.evaluate(variables)?
+            }
+            Expression::Call(Function::Piecewise, arguments) => {
+                for pair in arguments.chunks(2) {
+                    match pair {
+                        [condition, value] if is_true(condition.evaluate(variables)?) => return value.evaluate(variables),
+                        [otherwise] => return otherwise.evaluate(variables),
+                        _ => {}
+                    }
+                }
+
+                f64::NAN
+            }
+            Expression::Call(function, arguments) => {
+                let values = arguments.iter().map(|argument| argument.evaluate(variables)).collect::<Result<Vec<_>, _>>()?;
+                function.apply(&values)
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Power,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+}
+
+impl BinaryOperator {
+    fn apply(self, left: f64, right: f64) -> f64 {
+        match self {
+            BinaryOperator::Add => left + right,
+            BinaryOperator::Subtract => left - right,
+            BinaryOperator::Multiply => left * right,
+            BinaryOperator::Divide => left / right,
+            BinaryOperator::Remainder => left % right,
+            BinaryOperator::Power => left.powf(right),
+            BinaryOperator::Less => from_bool(left < right),
+            BinaryOperator::LessOrEqual => from_bool(left <= right),
+            BinaryOperator::Greater => from_bool(left > right),
+            BinaryOperator::GreaterOrEqual => from_bool(left >= right),
+            BinaryOperator::Equal => from_bool(left == right),
+            BinaryOperator::NotEqual => from_bool(left != right),
+            BinaryOperator::And => from_bool(is_true(left) && is_true(right)),
+            BinaryOperator::Or => from_bool(is_true(left) || is_true(right)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Function {
+    Floor,
+    Ceil,
+    Round,
+    Abs,
+    Sqrt,
+    Pow,
+    Min,
+    Max,
+    Clamp,
+    If,
+    Piecewise,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        let function = match name {
+            "floor" => Function::Floor,
+            "ceil" => Function::Ceil,
+            "round" => Function::Round,
+            "abs" => Function::Abs,
+            "sqrt" => Function::Sqrt,
+            "pow" => Function::Pow,
+            "min" => Function::Min,
+            "max" => Function::Max,
+            "clamp" => Function::Clamp,
+            "if" => Function::If,
+            "piecewise" => Function::Piecewise,
+            _ => return None,
+        };
+
+        Some(function)
+    }
+
+    /// describes the amount of arguments the function takes if `count` isn't valid
+    fn check_argument_count(self, count: usize) -> Result<(), &'static str> {
+        let (valid, expected) = match self {
+            Function::Floor | Function::Ceil | Function::Abs | Function::Sqrt => (count == 1, "1"),
+            Function::Round => (count == 1 || count == 2, "1 or 2"),
+            Function::Pow => (count == 2, "2"),
+            Function::Min | Function::Max => (count >= 1, "at least 1"),
+            Function::Clamp | Function::If => (count == 3, "3"),
+            Function::Piecewise => (count >= 3 && count % 2 == 1, "an odd number (at least 3) of"),
+        };
+
+        if valid { Ok(()) } else { Err(expected) }
+    }
+
+    fn apply(self, arguments: &[f64]) -> f64 {
+        match self {
+            Function::Floor => arguments[0].floor(),
+            Function::Ceil => arguments[0].ceil(),
+            // like `Math.round`, halves are rounded up (rather than away from zero)
+            Function::Round => {
+                let scale = 10_f64.powf(arguments.get(1).copied().unwrap_or(0.0));
+                (arguments[0] * scale + 0.5).floor() / scale
+            }
+            Function::Abs => arguments[0].abs(),
+            Function::Sqrt => arguments[0].sqrt(),
+            Function::Pow => arguments[0].powf(arguments[1]),
+            Function::Min => arguments.iter().copied().fold(f64::INFINITY, f64::min),
+            Function::Max => arguments.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Function::Clamp => arguments[0].max(arguments[1]).min(arguments[2]),
+            Function::If | Function::Piecewise => unreachable!("`if` and `piecewise` are evaluated lazily"),
+        }
+    }
+}
+
+fn from_bool(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
+
+fn is_true(value: f64) -> bool {
+    value != 0.0
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum TokenKind {
+    Number(f64),
+    Identifier(String),
+    Operator(&'static str),
+    OpenParenthesis,
+    CloseParenthesis,
+    Comma,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Number(value) => write!(f, "{}", value),
+            TokenKind::Identifier(name) => write!(f, "{}", name),
+            TokenKind::Operator(operator) => write!(f, "{}", operator),
+            TokenKind::OpenParenthesis => write!(f, "("),
+            TokenKind::CloseParenthesis => write!(f, ")"),
+            TokenKind::Comma => write!(f, ","),
+        }
+    }
+}
+
+struct Token {
+    kind: TokenKind,
+    /// the column (starting from 1) the token starts at
+    column: usize,
+}
+
+// operators are matched in order, so longer ones must come before their prefixes
+const OPERATORS: [&str; 15] = ["<=", ">=", "==", "!=", "&&", "||", "+", "-", "*", "/", "%", "^", "<", ">", "!"];
+
+/// splits `source` into tokens
+fn tokenize(source: &str) -> Result<Vec<Token>, FormulaError> {
+    let characters: Vec<char> = source.chars().collect();
+    let error = |column: usize, kind: FormulaErrorKind| FormulaError { source: source.to_string(), column, kind };
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        let character = characters[index];
+        let column = index + 1;
+
+        if character.is_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        let kind = if character.is_ascii_digit() || character == '.' {
+            let start = index;
+
+            while index < characters.len() && (characters[index].is_ascii_digit() || characters[index] == '.') {
+                index += 1;
+            }
+
+            let number: String = characters[start..index].iter().collect();
+            let value = number.parse().map_err(|_| error(column, FormulaErrorKind::InvalidNumber(number.clone())))?;
+
+            tokens.push(Token { kind: TokenKind::Number(value), column });
+            continue;
+        } else if character.is_alphabetic() || character == '_' {
+            let start = index;
+
+            while index < characters.len() && (characters[index].is_alphanumeric() || characters[index] == '_') {
+                index += 1;
+            }
+
+            tokens.push(Token { kind: TokenKind::Identifier(characters[start..index].iter().collect()), column });
+            continue;
+        } else if character == '(' {
+            TokenKind::OpenParenthesis
+        } else if character == ')' {
+            TokenKind::CloseParenthesis
+        } else if character == ',' {
+            TokenKind::Comma
+        } else {
+            let rest: String = characters[index..characters.len().min(index + 2)].iter().collect();
+            let operator = OPERATORS
+                .iter()
+                .find(|operator| rest.starts_with(**operator))
+                .ok_or_else(|| error(column, FormulaErrorKind::UnexpectedCharacter(character)))?;
+
+            index += operator.len() - 1;
+            TokenKind::Operator(operator)
+        };
+
+        tokens.push(Token { kind, column });
+        index += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// a recursive descent parser, from the lowest precedence (`||`) to the highest (function calls and parentheses)
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error_at(&self, column: usize, kind: FormulaErrorKind) -> FormulaError {
+        FormulaError { source: self.source.to_string(), column, kind }
+    }
+
+    /// the error for the current token not fitting (or the formula ending too early)
+    fn unexpected(&self) -> FormulaError {
+        match self.tokens.get(self.position) {
+            Some(token) => self.error_at(token.column, FormulaErrorKind::UnexpectedToken(token.kind.to_string())),
+            None => self.error_at(self.source.chars().count() + 1, FormulaErrorKind::UnexpectedEnd),
+        }
+    }
+
+    /// consumes the current token if it's `kind`
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        let matches = self.tokens.get(self.position).is_some_and(|token| &token.kind == kind);
+
+        if matches {
+            self.position += 1;
+        }
+
+        matches
+    }
+
+    /// consumes the current token if it's one of `operators`, returning the operator and its column
+    fn eat_operator(&mut self, operators: &[(&str, BinaryOperator)]) -> Option<(BinaryOperator, usize)> {
+        let Some(Token { kind: TokenKind::Operator(operator), column }) = self.tokens.get(self.position) else {
+            return None;
+        };
+        let (_, binary_operator) = operators.iter().find(|(symbol, _)| symbol == operator)?;
+        let column = *column;
+
+        self.position += 1;
+        Some((*binary_operator, column))
+    }
+
+    /// parses left-associative binary operators within `operators`, with operands parsed by `operand`
+    fn parse_binary(
+        &mut self,
+        operators: &[(&str, BinaryOperator)],
+        operand: fn(&mut Self) -> Result<Expression, FormulaError>,
+    ) -> Result<Expression, FormulaError> {
+        let mut expression = operand(self)?;
+
+        while let Some((operator, column)) = self.eat_operator(operators) {
+            expression = Expression::Binary(operator, column, Box::new(expression), Box::new(operand(self)?));
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, FormulaError> {
+        self.parse_binary(&[("||", BinaryOperator::Or)], Self::parse_and)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression, FormulaError> {
+        self.parse_binary(&[("&&", BinaryOperator::And)], Self::parse_comparison)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expression, FormulaError> {
+        let operators = [
+            ("<", BinaryOperator::Less),
+            ("<=", BinaryOperator::LessOrEqual),
+            (">", BinaryOperator::Greater),
+            (">=", BinaryOperator::GreaterOrEqual),
+            ("==", BinaryOperator::Equal),
+            ("!=", BinaryOperator::NotEqual),
+        ];
+
+        self.parse_binary(&operators, Self::parse_sum)
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression, FormulaError> {
+        self.parse_binary(&[("+", BinaryOperator::Add), ("-", BinaryOperator::Subtract)], Self::parse_product)
+    }
+
+    fn parse_product(&mut self) -> Result<Expression, FormulaError> {
+        let operators = [("*", BinaryOperator::Multiply), ("/", BinaryOperator::Divide), ("%", BinaryOperator::Remainder)];
+
+        self.parse_binary(&operators, Self::parse_unary)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, FormulaError> {
+        if self.eat(&TokenKind::Operator("-")) {
+            return Ok(Expression::Negate(Box::new(self.parse_unary()?)));
+        }
+
+        if self.eat(&TokenKind::Operator("!")) {
+            return Ok(Expression::Not(Box::new(self.parse_unary()?)));
+        }
+
+        self.parse_power()
+    }
+
+    /// powers are right-associative and bind tighter than negation (`-2^2` is `-4`)
+    fn parse_power(&mut self) -> Result<Expression, FormulaError> {
+        let base = self.parse_primary()?;
+
+        if let Some((operator, column)) = self.eat_operator(&[("^", BinaryOperator::Power)]) {
+            return Ok(Expression::Binary(operator, column, Box::new(base), Box::new(self.parse_unary()?)));
+        }
+
+        Ok(base)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, FormulaError> {
+        let Some(token) = self.tokens.get(self.position) else {
+            return Err(self.unexpected());
+        };
+        let column = token.column;
+
+        match token.kind.clone() {
+            TokenKind::Number(value) => {
+                self.position += 1;
+                Ok(Expression::Number(value))
+            }
+            TokenKind::OpenParenthesis => {
+                self.position += 1;
+                let expression = self.parse_expression()?;
+
+                if !self.eat(&TokenKind::CloseParenthesis) {
+                    return Err(self.unexpected());
+                }
+
+                Ok(expression)
+            }
+            TokenKind::Identifier(name) => {
+                self.position += 1;
+
+                if !self.eat(&TokenKind::OpenParenthesis) {
+                    return Ok(Expression::Variable(name, column));
+                }
+
+                let function = Function::from_name(&name).ok_or_else(|| self.error_at(column, FormulaErrorKind::UnknownFunction(name.clone())))?;
+                let arguments = self.parse_arguments()?;
+
+                function
+                    .check_argument_count(arguments.len())
+                    .map_err(|expected| self.error_at(column, FormulaErrorKind::WrongArgumentCount { function: name, expected, got: arguments.len() }))?;
+
+                Ok(Expression::Call(function, arguments))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// parses comma-separated arguments up to (and including) the closing parenthesis
+    fn parse_arguments(&mut self) -> Result<Vec<Expression>, FormulaError> {
+        let mut arguments = Vec::new();
+
+        if self.eat(&TokenKind::CloseParenthesis) {
+            return Ok(arguments);
+        }
+
+        loop {
+            arguments.push(self.parse_expression()?);
+
+            if self.eat(&TokenKind::CloseParenthesis) {
+                return Ok(arguments);
+            }
+
+            if !self.eat(&TokenKind::Comma) {
+                return Err(self.unexpected());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str, level: f64) -> f64 {
+        Formula::parse(source).unwrap().evaluate(&[("level", level)]).unwrap()
+    }
+
+    fn parse_error(source: &str) -> FormulaError {
+        Formula::parse(source).unwrap_err()
+    }
+
+    #[test]
+    fn follows_operator_precedence() {
+        assert_eq!(evaluate("2 + 3 * 4", 0.0), 14.0);
+        assert_eq!(evaluate("(2 + 3) * 4", 0.0), 20.0);
+        assert_eq!(evaluate("10 - 4 - 3", 0.0), 3.0);
+        assert_eq!(evaluate("12 / 3 / 2", 0.0), 2.0);
+        assert_eq!(evaluate("7 % 4 * 2", 0.0), 6.0);
+        assert_eq!(evaluate("1 + 2 < 4 && 3 == 3", 0.0), 1.0);
+        assert_eq!(evaluate("0 || 2 > 1 && 0", 0.0), 0.0);
+        assert_eq!(evaluate("!0 + 1", 0.0), 2.0);
+    }
+
+    #[test]
+    fn powers_are_right_associative_and_bind_tighter_than_negation() {
+        assert_eq!(evaluate("2^3^2", 0.0), 512.0);
+        assert_eq!(evaluate("-2^2", 0.0), -4.0);
+        assert_eq!(evaluate("2^-1", 0.0), 0.5);
+        assert_eq!(evaluate("3 * level^2", 4.0), 48.0);
+        assert_eq!(evaluate("15 * level^2 + 20 * level + 80", 2.0), 180.0);
+    }
+
+    #[test]
+    fn evaluates_functions() {
+        assert_eq!(evaluate("floor(level / 1.5 + 5)", 4.0), 7.0);
+        assert_eq!(evaluate("ceil(2.1)", 0.0), 3.0);
+        assert_eq!(evaluate("round(2.5)", 0.0), 3.0);
+        assert_eq!(evaluate("round(-2.5)", 0.0), -2.0);
+        assert_eq!(evaluate("round(1.005 * 1000, 1)", 0.0), 1005.0);
+        assert_eq!(evaluate("min(3, level, 5)", 1.0), 1.0);
+        assert_eq!(evaluate("max(3, level, 5)", 9.0), 9.0);
+        assert_eq!(evaluate("clamp(level, 1, 10)", 20.0), 10.0);
+        assert_eq!(evaluate("pow(2, 10) + sqrt(16) + abs(-1)", 0.0), 1029.0);
+    }
+
+    #[test]
+    fn piecewise_picks_the_first_true_condition() {
+        let source = "piecewise(level <= 14, 1.5, level <= 29, 1.45, 1.25)";
+
+        assert_eq!(evaluate(source, 14.0), 1.5);
+        assert_eq!(evaluate(source, 15.0), 1.45);
+        assert_eq!(evaluate(source, 50.0), 1.25);
+    }
+
+    #[test]
+    fn only_evaluates_the_picked_branch() {
+        let formula = Formula::parse("if(level == 1, 0, 100 / (level - 1)) + piecewise(level == 1, 1, missing)").unwrap();
+
+        assert_eq!(formula.evaluate(&[("level", 1.0)]), Ok(1.0));
+        assert_eq!(formula.evaluate(&[("level", 2.0)]).unwrap_err().kind, FormulaErrorKind::UnknownVariable("missing".to_string()));
+    }
+
+    #[test]
+    fn reports_the_column_of_parse_errors() {
+        let error = parse_error("level + $");
+        assert_eq!((error.column, error.kind), (9, FormulaErrorKind::UnexpectedCharacter('$')));
+
+        let error = parse_error("level * * 2");
+        assert_eq!((error.column, error.kind), (9, FormulaErrorKind::UnexpectedToken("*".to_string())));
+
+        let error = parse_error("(level + 1");
+        assert_eq!((error.column, error.kind), (11, FormulaErrorKind::UnexpectedEnd));
+
+        let error = parse_error("1.2.3 + level");
+        assert_eq!((error.column, error.kind), (1, FormulaErrorKind::InvalidNumber("1.2.3".to_string())));
+
+        let error = parse_error("2 + lerp(level)");
+        assert_eq!((error.column, error.kind), (5, FormulaErrorKind::UnknownFunction("lerp".to_string())));
+
+        let error = parse_error("level 2");
+        assert_eq!((error.column, error.kind), (7, FormulaErrorKind::UnexpectedToken("2".to_string())));
+    }
+
+    #[test]
+    fn reports_calls_with_the_wrong_amount_of_arguments() {
+        let cases = [
+            ("floor(1, 2)", "floor", "1", 2),
+            ("round()", "round", "1 or 2", 0),
+            ("1 + pow(2)", "pow", "2", 1),
+            ("max()", "max", "at least 1", 0),
+            ("if(level, 1)", "if", "3", 2),
+            ("piecewise(level, 1)", "piecewise", "an odd number (at least 3) of", 2),
+        ];
+
+        for (source, function, expected, got) in cases {
+            let error = parse_error(source);
+            let column = source.find(function).unwrap() + 1;
+
+            assert_eq!(error.column, column, "{}", source);
+            assert_eq!(error.kind, FormulaErrorKind::WrongArgumentCount { function: function.to_string(), expected, got });
+        }
+    }
+
+    #[test]
+    fn reports_unknown_variables() {
+        let formula = Formula::parse("round(hp * 0.005, 2) + level").unwrap();
+
+        let error = formula.evaluate(&[("level", 1.0)]).unwrap_err();
+        assert_eq!((error.column, error.kind), (7, FormulaErrorKind::UnknownVariable("hp".to_string())));
+
+        let error = formula.check_variables(&["level"]).unwrap_err();
+        assert_eq!((error.column, error.kind), (7, FormulaErrorKind::UnknownVariable("hp".to_string())));
+        assert_eq!(formula.check_variables(&["level", "hp"]), Ok(()));
+    }
+
+    #[test]
+    fn reports_division_by_zero_at_the_operator() {
+        let formula = Formula::parse("100 / (level - 1)").unwrap();
+
+        let error = formula.evaluate(&[("level", 1.0)]).unwrap_err();
+        assert_eq!((error.column, error.kind), (5, FormulaErrorKind::DivisionByZero));
+        assert_eq!(formula.evaluate(&[("level", 3.0)]), Ok(50.0));
+
+        let error = Formula::parse("level % 0").unwrap().evaluate(&[("level", 1.0)]).unwrap_err();
+        assert_eq!((error.column, error.kind), (7, FormulaErrorKind::DivisionByZero));
+    }
+
+    #[test]
+    fn deserializes_numbers_and_expressions() {
+        let formulas: Vec<Formula> = serde_json::from_str(r#"[175, "2 * level"]"#).unwrap();
+
+        assert_eq!(formulas[0].evaluate(&[]), Ok(175.0));
+        assert_eq!(formulas[1].evaluate(&[("level", 3.0)]), Ok(6.0));
+        assert_eq!(serde_json::to_string(&formulas).unwrap(), r#"[175.0,"2 * level"]"#);
+
+        let error = serde_json::from_str::<Formula>(r#""2 *""#).unwrap_err();
+        assert!(error.to_string().contains("unexpected end of formula at column 4"), "{}", error);
+    }
+}
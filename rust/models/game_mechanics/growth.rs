@@ -0,0 +1,174 @@
+use serde::{Serialize, Deserialize};
+
+use crate::models::{Formula, RangeType, Resource, SkillModifier};
+
+/// Represents the parameters the player, enemy, weapon and skill level tables are generated from (as stored in `mechanics/growth.json`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GrowthParameters {
+    /// how the player's stats grow per level
+    pub player: PlayerGrowth,
+    /// how each enemy's stats grow per level
+    pub enemies: Vec<EnemyGrowth>,
+    /// how each weapon's damage, attack time and upgrade cost grow per level
+    #[serde(default)]
+    pub weapons: Vec<WeaponGrowth>,
+    /// how each skill's damage and upgrade cost grow per level
+    #[serde(default)]
+    pub skills: Vec<SkillGrowth>,
+}
+
+/// Represents how the player's stats grow per level.
+/// 
+/// Every stat is a `Formula` of `level`; `hp_regen` can also use `hp` (the player's base HP at that level).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PlayerGrowth {
+    /// the maximum level a player can reach
+    #[serde(rename = "maxLevel")]
+    pub max_level: u8,
+    /// the player's base HP
+    pub hp: Formula,
+    /// how much HP the player regens per second
+    #[serde(rename = "hpRegen")]
+    pub hp_regen: Formula,
+    /// how fast the player moves (units per second)
+    #[serde(rename = "movementSpeed")]
+    pub movement_speed: Formula,
+    /// the xp needed to level up from a level to the next level
+    #[serde(rename = "levelUpXpRequired")]
+    pub level_up_xp_required: Formula,
+}
+
+/// Represents how an enemy and its stats are generated level by level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemyGrowth {
+    /// the enemy's name
+    pub name: String,
+    /// a description explaining the enemy
+    pub description: String,
+    /// the range type of the enemy
+    #[serde(rename = "rangeType")]
+    pub range_type: RangeType,
+    /// the name of the file (within `mechanics/enemies`) the enemy is written to
+    pub file: String,
+    /// the maximum level the enemy can reach
+    #[serde(rename = "maxLevel")]
+    pub max_level: u8,
+    /// how the enemy's stats grow per level
+    pub stats: EnemyStatsGrowth,
+    /// the enemy's skill (which is the same at every level)
+    pub skill: EnemySkillGrowth,
+    /// how the rewards for killing the enemy grow per level
+    #[serde(rename = "killRewards")]
+    pub kill_rewards: KillRewardsGrowth,
+}
+
+/// Represents how an enemy's stats grow per level.
+/// 
+/// Every stat is a `Formula` of `level`; every stat apart from `hp` can also use `hp` (the enemy's base HP at that level).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemyStatsGrowth {
+    /// the enemy's base hp
+    pub hp: Formula,
+    /// the enemy's hp regen per second
+    #[serde(rename = "hpRegen")]
+    pub hp_regen: Formula,
+    /// how fast the enemy moves (units per second)
+    #[serde(rename = "movementSpeed")]
+    pub movement_speed: Formula,
+    /// how much damage the enemy deals per attack
+    pub damage: Formula,
+    /// how fast the enemy shoots its projectile (only if ranged; 0 if melee)
+    #[serde(rename = "projectileVelocity")]
+    pub projectile_velocity: Formula,
+    /// how far the enemy can start attacking
+    #[serde(rename = "attackRange")]
+    pub attack_range: Formula,
+    /// how fast each subsequent attack happens (in seconds)
+    #[serde(rename = "attackTime")]
+    pub attack_time: Formula,
+    /// the probability of dealing a critical hit (in a ratio from 0 to 1)
+    #[serde(rename = "critChance")]
+    pub crit_chance: Formula,
+}
+
+/// Represents an enemy's skill, which is the same at every level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemySkillGrowth {
+    /// the name of the skill's attribute (as found in `mechanics/attributes.json`)
+    pub attribute: String,
+    /// an additional description to explain the skill (on top of the attribute's description if needed)
+    #[serde(rename = "addDescription")]
+    pub add_description: String,
+    /// how long the skill plays out (in seconds)
+    pub duration: f64,
+    /// the skill's modifier
+    pub modifier: SkillModifier,
+    /// the chance for the skill to play out (in a ratio from 0 to 1)
+    pub chance: f64,
+    /// the skill's radius of effect (in units)
+    pub radius: f64,
+}
+
+/// Represents how the rewards for killing an enemy grow per level (as `Formula`s of `level`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct KillRewardsGrowth {
+    /// the amount of coins the player receives
+    pub coins: Formula,
+    /// the amount of experience points the player receives
+    pub xp: Formula,
+}
+
+/// Represents how a weapon's stats and upgrade cost grow per level (as `Formula`s of `level`).
+/// 
+/// Only these fields are generated; everything else about the weapon (e.g. its attributes) stays as written in its file.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WeaponGrowth {
+    /// the weapon's name (as found in `mechanics/weapons`)
+    pub name: String,
+    /// the weapon's base damage
+    pub damage: Formula,
+    /// how long each attack takes (in seconds)
+    #[serde(rename = "attackTime")]
+    pub attack_time: Formula,
+    /// the cost required to upgrade the weapon to a level
+    #[serde(rename = "upgradeCost")]
+    pub upgrade_cost: UpgradeCostGrowth,
+}
+
+/// Represents how a skill's damage and upgrade cost grow per level (as `Formula`s of `level`).
+/// 
+/// Only these fields are generated; everything else about the skill (e.g. its cooldown) stays as written in its file.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SkillGrowth {
+    /// the skill's name (as found in `mechanics/skills`)
+    pub name: String,
+    /// the skill's base damage (only for damaging skills)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub damage: Option<Formula>,
+    /// the cost required to upgrade the skill to a level
+    #[serde(rename = "upgradeCost")]
+    pub upgrade_cost: UpgradeCostGrowth,
+}
+
+/// Represents how an upgrade cost grows per level (as `Formula`s of `level`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UpgradeCostGrowth {
+    /// the amount of coins required
+    pub coins: Formula,
+    /// the amount of energy cores required
+    #[serde(rename = "energyCores")]
+    pub energy_cores: Formula,
+    /// the resources required (a resource is left out of a level's cost wherever its amount is 0)
+    #[serde(default)]
+    pub resources: Vec<ResourceCostGrowth>,
+}
+
+/// Represents how the amount of a resource required grows per level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ResourceCostGrowth {
+    /// the resource type
+    #[serde(rename = "type")]
+    pub resource: Resource,
+    /// the amount of resources required
+    pub amount: Formula,
+}
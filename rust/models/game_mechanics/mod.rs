@@ -2,8 +2,18 @@ pub mod range;
 pub mod targetting;
 pub mod rewards;
 pub mod stats;
+pub mod cost;
+pub mod time;
+pub mod formula;
+pub mod growth;
+pub mod breathable;
 
 pub use range::*;
 pub use targetting::*;
 pub use rewards::*;
-pub use stats::*;
\ No newline at end of file
+pub use stats::*;
+pub use cost::*;
+pub use time::*;
+pub use formula::*;
+pub use growth::*;
+pub use breathable::*;
\ No newline at end of file
@@ -4,7 +4,7 @@ use serde::{Serialize, Deserialize};
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct KillRewards {
     /// The amount of coins the player receives.
-    coins: u16,
+    pub coins: u16,
     /// The amount of experience points the player receives.
-    xp: u16,
+    pub xp: u16,
 }
\ No newline at end of file
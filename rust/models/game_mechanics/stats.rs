@@ -1,7 +1,7 @@
 use serde::{Serialize, Deserialize};
 
 /// A list of stats available within this game (only limited to the player)
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum Stat {
     #[serde(rename = "HP")]
     HP,
@@ -0,0 +1,48 @@
+use std::ops::{Add, Sub};
+
+use serde::{Serialize, Deserialize};
+
+/// Represents a point in game time, i.e. the amount of seconds elapsed since a `GameClock` started.
+/// 
+/// Unlike a wall-clock timestamp, game time can be simulated, which allows gameplay logic (e.g. respawns and attack cooldowns) to run faster than real time.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct GameTime(pub f64);
+
+impl GameTime {
+    /// the moment a `GameClock` starts at
+    pub const ZERO: GameTime = GameTime(0.0);
+
+    /// Creates a `GameTime` instance `seconds` seconds after the clock started.
+    pub fn from_seconds(seconds: f64) -> Self {
+        GameTime(seconds)
+    }
+
+    /// Gets the amount of seconds elapsed since the clock started.
+    pub fn as_seconds(self) -> f64 {
+        self.0
+    }
+
+    /// Gets the amount of seconds elapsed between `earlier` and this time (negative if `earlier` is actually later).
+    pub fn seconds_since(self, earlier: GameTime) -> f64 {
+        self.0 - earlier.0
+    }
+}
+
+impl Add<f64> for GameTime {
+    type Output = GameTime;
+
+    /// Adds `seconds` seconds to this time.
+    fn add(self, seconds: f64) -> GameTime {
+        GameTime(self.0 + seconds)
+    }
+}
+
+impl Sub<f64> for GameTime {
+    type Output = GameTime;
+
+    /// Subtracts `seconds` seconds from this time.
+    fn sub(self, seconds: f64) -> GameTime {
+        GameTime(self.0 - seconds)
+    }
+}
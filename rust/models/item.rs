@@ -1,46 +1,49 @@
 use serde::{Serialize, Deserialize};
 
-use super::{Attribute, StatusEffect, Stat};
+use super::{StatusEffect, Stat};
 
 /// Represents an item instance and its data.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Item {
     /// the name of the item
-    name: String,
+    pub name: String,
     /// the description of the item
-    description: String,
+    pub description: String,
     /// the item's attributes
-    attributes: Vec<ItemAttribute>,
+    pub attributes: Vec<ItemAttribute>,
 }
 
 /// Represents an item's attribute.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct ItemAttribute {
     /// the item's type
-    item_type: ItemType,
+    #[serde(rename = "type")]
+    pub item_type: ItemType,
     /// the chance for the attribute to play out (in a ratio from 0 to 1)
-    chance: f64,
+    pub chance: f64,
     /// the attribute's modifier
-    modifier: ItemModifier,
+    pub modifier: ItemModifier,
     /// the attribute's radius of effect (in units)
-    radius: f64,
+    pub radius: f64,
 }
 
 /// Represents an item's modifier.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct ItemModifier {
     /// status effects inflicted (if any)
-    status_effects: Option<Vec<StatusEffect>>,
+    #[serde(rename = "statusEffects", default, skip_serializing_if = "Option::is_none")]
+    pub status_effects: Option<Vec<StatusEffect>>,
     /// stat boosts (if any)
-    stat_boosts: Option<Vec<Stat>>,
+    #[serde(rename = "statBoosts", default, skip_serializing_if = "Option::is_none")]
+    pub stat_boosts: Option<Vec<Stat>>,
     /// the value of the modifier (0 if `status_effects` is present)
     /// 
     /// note: if stat_boost is present, the length of `values` MUST be equal to the length of `stat_boost`
-    values: Vec<f64>,
+    pub values: Vec<f64>,
 }
 
 /// Lists all available item types.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum ItemType {
     /// items that deal damage
     #[serde(rename = "Damage")]
@@ -49,18 +52,28 @@ pub enum ItemType {
     #[serde(rename = "Healing")]
     Healing,
     /// items that deal status effects
-    #[serde(rename = "StatusEffect")]
+    #[serde(rename = "Status Effect")]
     StatusEffect,
     /// items that grant the player XP
-    #[serde(rename = "GainXP")]
+    #[serde(rename = "Gain XP")]
     GainXP,
     /// items that grant the player coins
-    #[serde(rename = "GainCoins")]
+    #[serde(rename = "Gain Coins")]
     GainCoins,
     /// items that grant the player miscellanous currency
-    #[serde(rename = "MiscCurrency")]
+    #[serde(rename = "Misc Currency")]
     MiscCurrency,
     /// items that grant the player a stat boost
-    #[serde(rename = "StatBoost")]
+    #[serde(rename = "Stat Boost")]
     StatBoost,
-}
\ No newline at end of file
+}
+
+/// Represents an amount of a specific item a quest requires the player to obtain.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ItemsRequired {
+    /// the name of the item (e.g. `Blueberry`)
+    #[serde(rename = "type")]
+    pub item: String,
+    /// the amount of items required
+    pub amount: u32,
+}
@@ -36,7 +36,29 @@ pub struct Object {
     // represents the x coordinate of the object within the map
     pub x: f64,
     // represents the y coordinate of the object within the map
-    pub y: f64
+    pub y: f64,
+    // the custom properties set on the object in Tiled (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Vec<ObjectProperty>>
+}
+
+impl Object {
+    /// Gets the value of the custom property named `name` (if the object has it).
+    pub fn property(&self, name: &str) -> Option<&serde_json::Value> {
+        self.properties.iter().flatten().find(|property| property.name == name).map(|property| &property.value)
+    }
+}
+
+/// Represents a custom property set on an object in Tiled (e.g. an enemy's `level`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ObjectProperty {
+    // the name of the property
+    pub name: String,
+    // the property's type (e.g. `int`, `float`, `string` or `bool`)
+    #[serde(rename = "type")]
+    pub property_type: String,
+    // the property's value
+    pub value: serde_json::Value
 }
 
 /// Represents a point on a polygon or a polyline with x and y coordinates relative to the object
@@ -46,4 +68,11 @@ pub struct Point {
     pub x: f64,
     // the y coordinate of the point
     pub y: f64
-}
\ No newline at end of file
+}
+
+impl Point {
+    /// Gets the (straight line) distance between this point and `other`.
+    pub fn distance_to(&self, other: &Point) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
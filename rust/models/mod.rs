@@ -6,6 +6,8 @@ pub mod attribute;
 pub mod status_effect;
 pub mod weapon;
 pub mod item;
+pub mod resource;
+pub mod quest;
 
 pub use map::*;
 pub use characters::*;
@@ -14,4 +16,6 @@ pub use skill::*;
 pub use attribute::*;
 pub use status_effect::*;
 pub use weapon::*;
-pub use item::*;
\ No newline at end of file
+pub use item::*;
+pub use resource::*;
+pub use quest::*;
\ No newline at end of file
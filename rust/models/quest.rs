@@ -0,0 +1,128 @@
+use serde::{Serialize, Deserialize};
+
+use super::{ItemsRequired, ResourcesRequired, EnemiesRequired, Currency};
+
+/// Represents a quest and its data.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Quest {
+    /// the quest's chapter
+    pub chapter: u8,
+    /// the quest's ID (unique within its chapter)
+    pub id: u8,
+    /// the quest's name
+    pub name: String,
+    /// the quest's description
+    pub description: String,
+    /// the quest's type
+    /// 
+    /// if this is `None`, the quest is usually just to reach an area (or talk to an NPC)
+    #[serde(rename = "type")]
+    pub quest_type: Option<QuestType>,
+    /// the rewards for completing the quest
+    #[serde(rename = "completionRewards")]
+    pub completion_rewards: QuestCompletionRewards,
+}
+
+impl Quest {
+    /// Gets the area or NPC a quest without a type is about, read from its description:
+    /// `Reach <area>`, `Talk to <npc>` or `Find <npc>` (e.g. `Reach Beacon 1` or `Talk to Pfufu`).
+    ///
+    /// Returns `None` for quests with a type or whose description doesn't take one of these forms.
+    pub fn target(&self) -> Option<QuestTarget> {
+        if self.quest_type.is_some() {
+            return None;
+        }
+
+        let description = self.description.trim();
+
+        if let Some(area) = description.strip_prefix("Reach ") {
+            return Some(QuestTarget::Area(area.trim().to_string()));
+        }
+
+        description
+            .strip_prefix("Talk to ")
+            .or_else(|| description.strip_prefix("Find "))
+            .map(|npc| QuestTarget::Npc(npc.trim().to_string()))
+    }
+}
+
+/// Represents where the player has to go (or who they have to talk to) to complete a quest without a type.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum QuestTarget {
+    /// the name of the area to reach (as named within the map)
+    Area(String),
+    /// the name of the NPC to talk to (as named within the map)
+    Npc(String),
+}
+
+/// Represents the requirements of a quest.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct QuestType {
+    /// the items required to be obtained (if any)
+    #[serde(rename = "itemsRequired")]
+    pub items_required: Option<Vec<ItemsRequired>>,
+    /// the resources required to be obtained (if any)
+    #[serde(rename = "resourcesRequired")]
+    pub resources_required: Option<Vec<ResourcesRequired>>,
+    /// the enemies required to be defeated (if any)
+    #[serde(rename = "enemiesRequired")]
+    pub enemies_required: Option<Vec<EnemiesRequired>>,
+    /// the amount of kills the player needs to have (if any)
+    #[serde(rename = "killsRequired", default, skip_serializing_if = "Option::is_none")]
+    pub kills_required: Option<u32>,
+}
+
+/// Represents the rewards for completing a quest.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct QuestCompletionRewards {
+    /// the amount of coins the player receives
+    pub coins: u32,
+    /// the amount of experience points the player receives
+    pub xp: u32,
+    /// any miscellaneous rewards the player receives (if any)
+    pub misc: Option<Vec<QuestCompletionRewardMisc>>,
+}
+
+/// Represents a quest's miscellaneous completion reward.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct QuestCompletionRewardMisc {
+    /// the reward type
+    #[serde(rename = "type")]
+    pub reward_type: MiscReward,
+    /// the reward's name (if the reward isn't quantitative, e.g. a skill or a weapon)
+    pub name: String,
+    /// the reward's level (if the reward is a skill or a weapon)
+    pub level: u8,
+    /// the reward amount (if the reward is quantitative)
+    pub amount: u32,
+}
+
+/// A list of all available miscellaneous quest rewards.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum MiscReward {
+    #[serde(rename = "Skill")]
+    Skill,
+    #[serde(rename = "Weapon")]
+    Weapon,
+    #[serde(rename = "Wood")]
+    Wood,
+    #[serde(rename = "Stone")]
+    Stone,
+    #[serde(rename = "Coal")]
+    Coal,
+    #[serde(rename = "Energy Cores")]
+    EnergyCores,
+}
+
+impl MiscReward {
+    /// Gets the currency this reward is paid in (`None` for skills and weapons).
+    pub fn currency(self) -> Option<Currency> {
+        match self {
+            MiscReward::Skill | MiscReward::Weapon => None,
+            MiscReward::Wood => Some(Currency::Wood),
+            MiscReward::Stone => Some(Currency::Stone),
+            MiscReward::Coal => Some(Currency::Coal),
+            MiscReward::EnergyCores => Some(Currency::EnergyCores),
+        }
+    }
+}
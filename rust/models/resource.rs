@@ -0,0 +1,32 @@
+use serde::{Serialize, Deserialize};
+
+/// A list of all currently available resources.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub enum Resource {
+    #[serde(rename = "Wood")]
+    Wood,
+    #[serde(rename = "Stone")]
+    Stone,
+    #[serde(rename = "Coal")]
+    Coal,
+}
+
+/// Represents an amount of resources required to purchase or upgrade a skill or a weapon.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ResourceCost {
+    /// the resource type
+    #[serde(rename = "type")]
+    pub resource: Resource,
+    /// the amount of resources required
+    pub amount: u32,
+}
+
+/// Represents an amount of resources a quest requires the player to obtain.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ResourcesRequired {
+    /// the resource type
+    #[serde(rename = "type")]
+    pub resource: Resource,
+    /// the amount of resources required
+    pub amount: u32,
+}
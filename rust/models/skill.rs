@@ -1,41 +1,44 @@
+use std::path::PathBuf;
+
 use serde::{Serialize, Deserialize};
 
-use super::{Attribute, TargetMechanics, RangeType};
+use super::{Attribute, TargetMechanics, RangeType, PurchaseCost, UpgradeCost};
+
 /// Represents a skill instance and its data.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Skill {
     /// the skill's name
-    name: String,
+    pub name: String,
     /// the skill's description
-    description: String,
+    pub description: String,
     /// the skill's type (damaging or non-damaging)
-    skill_type: SkillType,
+    pub skill_type: SkillType,
     /// the skill's targetting mechanics
     #[serde(rename = "targetMechanics")]
-    target_mechanics: TargetMechanics,
+    pub target_mechanics: TargetMechanics,
     /// the skill's range type
     #[serde(rename = "rangeType")]
-    range_type: RangeType,
+    pub range_type: RangeType,
     /// the skill's activation range
     #[serde(rename = "activationRange")]
-    activation_range: f64,
+    pub activation_range: f64,
     /// the skill's level
-    level: u8,
+    pub level: u8,
     /// the skill's base damage (if damaging skill)
     #[serde(rename = "baseDamage")]
-    base_damage: Option<f64>,
+    pub base_damage: Option<f64>,
     /// the skill's cast time (in seconds)
-    cast_time: f64,
+    pub cast_time: f64,
     /// the skill's duration in seconds (0 if not status effect)
-    duration: f64,
+    pub duration: f64,
     /// the skill's cooldown (in seconds)
-    cooldown: f64,
+    pub cooldown: f64,
     /// the skill's attributes
-    attributes: Vec<SkillAttribute>
+    pub attributes: Vec<SkillAttribute>
 }
 
 /// Lists all available skill types.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum SkillType {
     #[serde(rename = "Damaging")]
     Damaging,
@@ -47,47 +50,47 @@ pub enum SkillType {
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct SkillAttribute {
     /// the skill's attribute
-    attribute: Attribute,
+    pub attribute: Attribute,
     /// the chance for the attribute to play out (in a ratio from 0 to 1)
-    chance: f64,
+    pub chance: f64,
     /// the attribute's modifier
-    modifier: SkillModifier,
+    pub modifier: SkillModifier,
     /// the attribute's radius of effect (in units)
-    radius: f64,
+    pub radius: f64,
 }
 
 /// Represents an enemy's skill data.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct EnemySkill {
     /// the skill's attribute (i.e. characteristics)
-    attributes: Vec<Attribute>,
+    pub attributes: Vec<Attribute>,
     /// an additional description to explain the skill (on top of the attribute's description if needed)
     #[serde(rename = "addDescription")]
-    add_description: String,
+    pub add_description: String,
     /// the skill's level
-    level: u8,
+    pub level: u8,
     /// how long the skill plays out (in seconds)
-    duration: f64,
+    pub duration: f64,
     /// the skill's modifier
-    modifier: SkillModifier,
+    pub modifier: SkillModifier,
     /// the chance for the skill to play out (in a ratio from 0 to 1)
-    chance: f64,
+    pub chance: f64,
     /// the skill's radius of effect (in units)
-    radius: f64,
+    pub radius: f64,
 }
 
 /// Represents a skill's modifier
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct SkillModifier {
     /// the type of modifier
-    #[serde(rename = "modifierType")]
-    modifier_type: SkillModifierType,
+    #[serde(rename = "type")]
+    pub modifier_type: SkillModifierType,
     /// the value of the modifier
-    value: f64,
+    pub value: f64,
 }
 
 /// A list of available skill modifier types.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum SkillModifierType {
     /// the modifier is a percentage
     #[serde(rename = "Percentage")]
@@ -95,4 +98,158 @@ pub enum SkillModifierType {
     /// the modifier is either an integer, unsigned integer or a float
     #[serde(rename = "Number")]
     Number,
-}
\ No newline at end of file
+}
+
+/// Represents a skill's full data across all of its levels (as stored in `mechanics/skills`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SkillDefinition {
+    /// the skill's name
+    pub name: String,
+    /// the skill's description
+    pub description: String,
+    /// the skill's targetting mechanics
+    #[serde(rename = "targetMechanics")]
+    pub target_mechanics: TargetMechanics,
+    /// the skill's range type
+    #[serde(rename = "rangeType")]
+    pub range_type: RangeType,
+    /// the skill's activation range
+    pub range: f64,
+    /// the cost to purchase this skill (if it is a purchasable skill)
+    #[serde(rename = "purchaseCost")]
+    pub purchase_cost: PurchaseCost,
+    /// the skill's mechanics level by level
+    #[serde(rename = "levelMechanics")]
+    pub level_mechanics: Vec<SkillLevelMechanics>,
+    /// the file this skill was loaded from (set when loading the catalog)
+    #[serde(skip)]
+    pub source: PathBuf,
+}
+
+impl SkillDefinition {
+    /// Gets the skill's mechanics at level `level`.
+    pub fn level_mechanics(&self, level: u8) -> Option<&SkillLevelMechanics> {
+        self.level_mechanics.iter().find(|mechanics| mechanics.level == level)
+    }
+
+    /// Gets the highest level this skill can be upgraded to.
+    pub fn max_level(&self) -> u8 {
+        self.level_mechanics.iter().map(|mechanics| mechanics.level).max().unwrap_or_default()
+    }
+
+    /// Gets the skill's type.
+    /// 
+    /// A skill is considered damaging if any of its levels has a base damage.
+    pub fn skill_type(&self) -> SkillType {
+        if self.level_mechanics.iter().any(|mechanics| mechanics.base_damage.is_some()) {
+            SkillType::Damaging
+        } else {
+            SkillType::NonDamaging
+        }
+    }
+
+    /// Creates a `Skill` instance of this skill at level `level`.
+    /// 
+    /// Returns `None` if the skill has no mechanics for that level.
+    pub fn at_level(&self, level: u8) -> Option<Skill> {
+        let mechanics = self.level_mechanics(level)?;
+
+        Some(Skill {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            skill_type: self.skill_type(),
+            target_mechanics: self.target_mechanics.clone(),
+            range_type: self.range_type,
+            activation_range: self.range,
+            level,
+            base_damage: mechanics.base_damage,
+            cast_time: mechanics.base_skill_cast_time,
+            duration: mechanics.duration,
+            cooldown: mechanics.cooldown,
+            attributes: mechanics.attributes.clone(),
+        })
+    }
+}
+
+/// Represents a skill's data at a specific level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SkillLevelMechanics {
+    /// the skill's level
+    pub level: u8,
+    /// the skill's base damage at this level (only present for damaging skills)
+    #[serde(rename = "baseDamage", default, skip_serializing_if = "Option::is_none")]
+    pub base_damage: Option<f64>,
+    /// the skill's cast time (in seconds) at this level
+    #[serde(rename = "baseSkillCastTime")]
+    pub base_skill_cast_time: f64,
+    /// the skill's duration in seconds (0 if not status effect)
+    pub duration: f64,
+    /// the skill's cooldown (in seconds) at this level
+    pub cooldown: f64,
+    /// the skill's attributes at this level
+    pub attributes: Vec<SkillAttribute>,
+    /// the cost required to upgrade the skill to this level
+    #[serde(rename = "upgradeCost")]
+    pub upgrade_cost: UpgradeCost,
+}
+
+/// Shows the minimum player level required to level up a skill to a certain level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SkillLevelRequirement {
+    /// the skill level
+    pub level: u8,
+    /// the minimum player level required to level up the skill to this level
+    #[serde(rename = "minPlayerLevelRequired")]
+    pub min_player_level_required: u8,
+}
+
+/// Represents an enemy's skill data across all of its levels.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemySkillDefinition {
+    /// the skill's attribute (i.e. characteristics)
+    pub attributes: Vec<Attribute>,
+    /// an additional description to explain the skill (on top of the attribute's description if needed)
+    #[serde(rename = "addDescription")]
+    pub add_description: String,
+    /// the skill's mechanics level by level
+    #[serde(rename = "levelMechanics")]
+    pub level_mechanics: Vec<EnemySkillLevelMechanics>,
+}
+
+impl EnemySkillDefinition {
+    /// Creates an `EnemySkill` instance of this skill at level `level`.
+    /// 
+    /// Enemy skills normally only store the mechanics of the enemy's current level, so if there is no exact match,
+    /// the closest level below `level` is used instead. Returns `None` if there is no such level.
+    pub fn at_level(&self, level: u8) -> Option<EnemySkill> {
+        let mechanics = self.level_mechanics
+            .iter()
+            .filter(|mechanics| mechanics.level <= level)
+            .max_by_key(|mechanics| mechanics.level)?;
+
+        Some(EnemySkill {
+            attributes: self.attributes.clone(),
+            add_description: self.add_description.clone(),
+            level,
+            duration: mechanics.duration,
+            modifier: mechanics.modifier.clone(),
+            chance: mechanics.chance,
+            radius: mechanics.radius,
+        })
+    }
+}
+
+/// Represents an enemy's skill data at a specific level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemySkillLevelMechanics {
+    /// the skill's level
+    pub level: u8,
+    /// how long the skill plays out (in seconds)
+    pub duration: f64,
+    /// the skill's modifier
+    pub modifier: SkillModifier,
+    /// the chance for the skill to play out (in a ratio from 0 to 1)
+    pub chance: f64,
+    /// the skill's radius of effect (in units)
+    pub radius: f64,
+}
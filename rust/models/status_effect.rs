@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+
 use serde::{Serialize, Deserialize};
 
+use super::GameTime;
+
 /// A list of all available status effects.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
 pub enum StatusEffect {
     /// poisons the enemy and deals damage over time
     #[serde(rename = "Toxic")]
@@ -12,7 +16,7 @@ pub enum StatusEffect {
     /// bleeds the enemy and deals damage over time
     #[serde(rename = "Bleed")]
     Bleed,
-    /// stuns the enemy, preventing them from moving
+    /// stuns the enemy, preventing them from moving (and attacking)
     #[serde(rename = "Stun")]
     Stun,
     /// blinds the enemy, reducing their accuracy
@@ -24,4 +28,367 @@ pub enum StatusEffect {
     /// no status effect
     #[serde(rename = "None")]
     None
-}
\ No newline at end of file
+}
+
+impl StatusEffect {
+    /// Checks whether the status effect deals damage over time.
+    pub fn is_damage_over_time(self) -> bool {
+        matches!(self, StatusEffect::Toxic | StatusEffect::Burn | StatusEffect::Bleed)
+    }
+}
+
+/// Represents an instance of a status effect currently inflicted on a character.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ActiveStatusEffect {
+    /// the status effect inflicted
+    pub effect: StatusEffect,
+    /// the name of the attribute or skill that inflicted the status effect (if known)
+    pub source: Option<String>,
+    /// the game time at which the status effect was (last) applied
+    #[serde(rename = "appliedAt")]
+    pub applied_at: GameTime,
+    /// how long the status effect lasts (in seconds) from `applied_at`
+    pub duration: f64,
+    /// how strong the status effect is (per stack), which depends on the status effect:
+    /// 1. Toxic, Burn and Bleed: the damage dealt per tick
+    /// 2. Blind: the accuracy reduction (in a ratio from 0 to 1)
+    /// 3. Resilience: the damage reduction (in a ratio from 0 to 1)
+    /// 4. Stun: unused
+    pub magnitude: f64,
+    /// how many times the status effect is stacked
+    pub stacks: u32,
+    /// the game time of the status effect's last damage tick (only used for damage over time effects)
+    #[serde(rename = "lastTick")]
+    pub last_tick: GameTime,
+}
+
+impl ActiveStatusEffect {
+    /// Gets the game time at which the status effect expires.
+    pub fn expires_at(&self) -> GameTime {
+        self.applied_at + self.duration
+    }
+
+    /// Checks whether the status effect is still active at `now`.
+    pub fn is_active(&self, now: GameTime) -> bool {
+        now < self.expires_at()
+    }
+}
+
+/// A list of ways a status effect behaves when it's applied to a character that already has it.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum StackingRule {
+    /// the existing instance's duration is reset and the higher magnitude is kept
+    Refresh,
+    /// the existing instance gains a stack (up to `max_stacks`) and its duration is reset
+    Stack { max_stacks: u32 },
+    /// each application is tracked as a separate instance with its own duration
+    Independent,
+    /// the new application is ignored while the existing instance is active
+    Ignore,
+}
+
+/// Represents how a specific status effect behaves at runtime.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct StatusEffectRule {
+    /// what happens when the status effect is applied to a character that already has it
+    pub stacking: StackingRule,
+    /// how often (in seconds) damage over time is dealt (only used for damage over time effects)
+    #[serde(rename = "tickInterval")]
+    pub tick_interval: f64,
+    /// the duration (in seconds) used when the source of the status effect doesn't specify one (e.g. weapon attributes)
+    #[serde(rename = "defaultDuration")]
+    pub default_duration: f64,
+}
+
+/// Represents how every status effect behaves at runtime.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StatusEffectRules {
+    /// the rules for each status effect
+    pub rules: HashMap<StatusEffect, StatusEffectRule>,
+}
+
+impl StatusEffectRules {
+    // the rule used for any status effect that doesn't have one
+    const FALLBACK_RULE: StatusEffectRule = StatusEffectRule { stacking: StackingRule::Refresh, tick_interval: 1.0, default_duration: 3.0 };
+
+    /// Gets the rule of `effect`.
+    pub fn rule(&self, effect: StatusEffect) -> StatusEffectRule {
+        self.rules.get(&effect).copied().unwrap_or(Self::FALLBACK_RULE)
+    }
+}
+
+impl Default for StatusEffectRules {
+    /// Damage over time effects tick every second. Bleed stacks up to 3 times, while every other status effect refreshes.
+    fn default() -> Self {
+        let rules = [
+            (StatusEffect::Toxic, Self::FALLBACK_RULE),
+            (StatusEffect::Burn, Self::FALLBACK_RULE),
+            (StatusEffect::Bleed, StatusEffectRule { stacking: StackingRule::Stack { max_stacks: 3 }, ..Self::FALLBACK_RULE }),
+            (StatusEffect::Stun, Self::FALLBACK_RULE),
+            (StatusEffect::Blind, Self::FALLBACK_RULE),
+            (StatusEffect::Resilience, Self::FALLBACK_RULE),
+        ];
+
+        StatusEffectRules { rules: rules.into_iter().collect() }
+    }
+}
+
+/// Represents something that happened to a character's status effects as time passed.
+#[derive(Clone, Serialize, Debug)]
+pub enum StatusEffectUpdate {
+    /// a damage over time effect dealt `damage` damage
+    Tick { effect: StatusEffect, source: Option<String>, damage: f64 },
+    /// a status effect wore off
+    Expired { effect: StatusEffect, source: Option<String> },
+}
+
+/// Represents all status effects currently inflicted on a character.
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct ActiveStatusEffects {
+    /// the status effects currently inflicted
+    pub effects: Vec<ActiveStatusEffect>,
+}
+
+impl ActiveStatusEffects {
+    /// Inflicts `effect` with `magnitude` (see `ActiveStatusEffect.magnitude`) for `duration` seconds at `now`, following the effect's stacking rule.
+    /// 
+    /// If `duration` is `None`, the effect's default duration is used. Applying `StatusEffect::None` does nothing.
+    pub fn apply(
+        &mut self,
+        effect: StatusEffect,
+        source: Option<String>,
+        magnitude: f64,
+        duration: Option<f64>,
+        now: GameTime,
+        rules: &StatusEffectRules,
+    ) {
+        if effect == StatusEffect::None {
+            return;
+        }
+
+        let rule = rules.rule(effect);
+        let duration = duration.filter(|duration| *duration > 0.0).unwrap_or(rule.default_duration);
+        let existing = self.effects.iter_mut().find(|active| active.effect == effect && active.is_active(now));
+
+        match (rule.stacking, existing) {
+            (StackingRule::Ignore, Some(_)) => {}
+            (StackingRule::Refresh, Some(active)) => {
+                active.applied_at = now;
+                active.duration = duration;
+                active.magnitude = active.magnitude.max(magnitude);
+                active.source = source;
+            }
+            (StackingRule::Stack { max_stacks }, Some(active)) => {
+                active.applied_at = now;
+                active.duration = duration;
+                active.stacks = (active.stacks + 1).min(max_stacks.max(1));
+            }
+            _ => self.effects.push(ActiveStatusEffect {
+                effect,
+                source,
+                applied_at: now,
+                duration,
+                magnitude,
+                stacks: 1,
+                last_tick: now,
+            }),
+        }
+    }
+
+    /// Moves the status effects forward to `now`: damage over time effects tick for every interval that passed, and expired effects are removed.
+    /// 
+    /// Returns every tick and expiry that happened (the damage isn't dealt to the character; that's up to the caller).
+    pub fn update(&mut self, now: GameTime, rules: &StatusEffectRules) -> Vec<StatusEffectUpdate> {
+        let mut updates = Vec::new();
+
+        for active in &mut self.effects {
+            if !active.effect.is_damage_over_time() {
+                continue;
+            }
+
+            let tick_interval = rules.rule(active.effect).tick_interval;
+            // an effect can't tick after it expired
+            let last_possible_tick = if now < active.expires_at() { now } else { active.expires_at() };
+
+            while tick_interval > 0.0 && last_possible_tick.seconds_since(active.last_tick) >= tick_interval {
+                active.last_tick = active.last_tick + tick_interval;
+                updates.push(StatusEffectUpdate::Tick {
+                    effect: active.effect,
+                    source: active.source.clone(),
+                    damage: active.magnitude * f64::from(active.stacks),
+                });
+            }
+        }
+
+        self.effects.retain(|active| {
+            let is_active = active.is_active(now);
+
+            if !is_active {
+                updates.push(StatusEffectUpdate::Expired { effect: active.effect, source: active.source.clone() });
+            }
+
+            is_active
+        });
+
+        updates
+    }
+
+    /// Checks whether `effect` is active at `now`.
+    pub fn has(&self, effect: StatusEffect, now: GameTime) -> bool {
+        self.effects.iter().any(|active| active.effect == effect && active.is_active(now))
+    }
+
+    /// Checks whether the character is stunned at `now`, which prevents them from moving and attacking.
+    pub fn is_stunned(&self, now: GameTime) -> bool {
+        self.has(StatusEffect::Stun, now)
+    }
+
+    /// Gets the ratio of the character's attacks that hit at `now`, after Blind's accuracy reduction (1 if not blinded).
+    pub fn accuracy_multiplier(&self, now: GameTime) -> f64 {
+        self.multiplier(StatusEffect::Blind, now)
+    }
+
+    /// Gets the ratio of incoming damage the character takes at `now`, after Resilience's damage reduction (1 if not resilient).
+    pub fn damage_taken_multiplier(&self, now: GameTime) -> f64 {
+        self.multiplier(StatusEffect::Resilience, now)
+    }
+
+    /// Removes every status effect.
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+
+    /// multiplies `1 - magnitude * stacks` of every active instance of `effect` (clamped between 0 and 1)
+    fn multiplier(&self, effect: StatusEffect, now: GameTime) -> f64 {
+        self.effects
+            .iter()
+            .filter(|active| active.effect == effect && active.is_active(now))
+            .map(|active| (1.0 - active.magnitude * f64::from(active.stacks)).clamp(0.0, 1.0))
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: f64) -> GameTime {
+        GameTime::from_seconds(seconds)
+    }
+
+    fn rules_with(effect: StatusEffect, stacking: StackingRule) -> StatusEffectRules {
+        let mut rules = StatusEffectRules::default();
+        rules.rules.insert(effect, StatusEffectRule { stacking, ..rules.rule(effect) });
+
+        rules
+    }
+
+    fn ticks(updates: &[StatusEffectUpdate]) -> Vec<f64> {
+        updates
+            .iter()
+            .filter_map(|update| match update {
+                StatusEffectUpdate::Tick { damage, .. } => Some(*damage),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn refresh_resets_the_duration_and_keeps_the_higher_magnitude() {
+        let rules = StatusEffectRules::default();
+        let mut effects = ActiveStatusEffects::default();
+
+        effects.apply(StatusEffect::Burn, None, 5.0, Some(3.0), at(0.0), &rules);
+        effects.apply(StatusEffect::Burn, Some("Fire".to_string()), 2.0, Some(3.0), at(2.0), &rules);
+
+        assert_eq!(effects.effects.len(), 1);
+        assert_eq!(effects.effects[0].magnitude, 5.0);
+        assert_eq!(effects.effects[0].stacks, 1);
+        assert_eq!(effects.effects[0].expires_at(), at(5.0));
+        assert_eq!(effects.effects[0].source.as_deref(), Some("Fire"));
+    }
+
+    #[test]
+    fn stacks_are_capped_and_multiply_tick_damage() {
+        let rules = StatusEffectRules::default();
+        let mut effects = ActiveStatusEffects::default();
+
+        for _ in 0..5 {
+            effects.apply(StatusEffect::Bleed, None, 2.0, Some(3.0), at(0.0), &rules);
+        }
+
+        assert_eq!(effects.effects.len(), 1);
+        assert_eq!(effects.effects[0].stacks, 3);
+        assert_eq!(ticks(&effects.update(at(1.0), &rules)), vec![6.0]);
+    }
+
+    #[test]
+    fn independent_applications_are_tracked_separately() {
+        let rules = rules_with(StatusEffect::Toxic, StackingRule::Independent);
+        let mut effects = ActiveStatusEffects::default();
+
+        effects.apply(StatusEffect::Toxic, None, 1.0, Some(2.0), at(0.0), &rules);
+        effects.apply(StatusEffect::Toxic, None, 4.0, Some(2.0), at(1.0), &rules);
+
+        assert_eq!(effects.effects.len(), 2);
+        assert_eq!(ticks(&effects.update(at(2.0), &rules)), vec![1.0, 1.0, 4.0]);
+        assert_eq!(effects.effects.len(), 1);
+    }
+
+    #[test]
+    fn ignore_keeps_the_existing_application() {
+        let rules = rules_with(StatusEffect::Stun, StackingRule::Ignore);
+        let mut effects = ActiveStatusEffects::default();
+
+        effects.apply(StatusEffect::Stun, None, 0.0, Some(1.0), at(0.0), &rules);
+        effects.apply(StatusEffect::Stun, None, 0.0, Some(5.0), at(0.5), &rules);
+
+        assert_eq!(effects.effects.len(), 1);
+        assert!(effects.is_stunned(at(0.9)));
+        assert!(!effects.is_stunned(at(1.0)));
+    }
+
+    #[test]
+    fn missing_durations_fall_back_to_the_default_and_none_is_never_applied() {
+        let rules = StatusEffectRules::default();
+        let mut effects = ActiveStatusEffects::default();
+
+        effects.apply(StatusEffect::None, None, 1.0, Some(3.0), at(0.0), &rules);
+        effects.apply(StatusEffect::Blind, None, 0.5, None, at(0.0), &rules);
+
+        assert_eq!(effects.effects.len(), 1);
+        assert_eq!(effects.effects[0].duration, rules.rule(StatusEffect::Blind).default_duration);
+    }
+
+    #[test]
+    fn damage_over_time_ticks_once_per_interval_until_it_expires() {
+        let rules = StatusEffectRules::default();
+        let mut effects = ActiveStatusEffects::default();
+
+        effects.apply(StatusEffect::Burn, None, 2.0, Some(3.0), at(0.0), &rules);
+
+        assert!(ticks(&effects.update(at(0.5), &rules)).is_empty());
+        assert_eq!(ticks(&effects.update(at(2.5), &rules)), vec![2.0, 2.0]);
+
+        // the last tick happens at expiry, and nothing ticks past it
+        let updates = effects.update(at(10.0), &rules);
+
+        assert_eq!(ticks(&updates), vec![2.0]);
+        assert!(matches!(updates.last(), Some(StatusEffectUpdate::Expired { effect: StatusEffect::Burn, .. })));
+        assert!(effects.effects.is_empty());
+    }
+
+    #[test]
+    fn blind_and_resilience_reduce_accuracy_and_damage_taken() {
+        let rules = StatusEffectRules::default();
+        let mut effects = ActiveStatusEffects::default();
+
+        effects.apply(StatusEffect::Blind, None, 0.25, Some(2.0), at(0.0), &rules);
+        effects.apply(StatusEffect::Resilience, None, 1.5, Some(2.0), at(0.0), &rules);
+
+        assert_eq!(effects.accuracy_multiplier(at(1.0)), 0.75);
+        assert_eq!(effects.damage_taken_multiplier(at(1.0)), 0.0);
+        assert_eq!(effects.accuracy_multiplier(at(2.0)), 1.0);
+    }
+}
@@ -1,52 +1,54 @@
+use std::path::PathBuf;
+
 use serde::{Serialize, Deserialize};
 
-use super::{RangeType, Attribute};
+use super::{RangeType, Attribute, PurchaseCost, UpgradeCost};
 
 /// Represents a weapon and its data.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Weapon {
     /// the name of the weapon
-    name: String,
+    pub name: String,
     /// the weapon's description
-    description: String,
+    pub description: String,
     /// the weapon's level
-    level: u8,
+    pub level: u8,
     /// the weapon's rarity
-    rarity: WeaponRarity,
+    pub rarity: WeaponRarity,
     /// the weapon's range type
     #[serde(rename = "rangeType")]
-    range_type: RangeType,
+    pub range_type: RangeType,
     /// represents the weapon's attributes
-    attributes: Vec<WeaponAttribute>,
+    pub attributes: Vec<WeaponAttribute>,
     /// the weapon's base damage
     #[serde(rename = "baseDamage")]
-    base_damage: f64,
+    pub base_damage: f64,
     /// the weapon's base attack range
     #[serde(rename = "baseAttackRange")]
-    base_attack_range: f64,
-    /// the weapon's base attack speed
-    #[serde(rename = "baseAttackSpeed")]
-    base_attack_speed: f64,
+    pub base_attack_range: f64,
+    /// how long each attack takes (in seconds)
+    #[serde(rename = "baseAttackTime")]
+    pub base_attack_time: f64,
     /// the weapon's crit chance
     #[serde(rename = "critChance")]
-    crit_chance: f64,
+    pub crit_chance: f64,
 }
 
 /// Represents a weapon's attributes.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct WeaponAttribute {
     /// the weapon's attribute
-    attribute: Attribute,
+    pub attribute: Attribute,
     /// the chance for the attribute to play out (in a ratio from 0 to 1)
-    chance: f64,
+    pub chance: f64,
     /// the attribute's modifier
-    modifier: WeaponAttributeModifier,
+    pub modifier: WeaponAttributeModifier,
     /// the attribute's radius of effect (in units)
-    radius: f64,
+    pub radius: f64,
 }
 
 /// A list of all available weapon rarities.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum WeaponRarity {
     #[serde(rename = "Common")]
     Common,
@@ -64,14 +66,14 @@ pub enum WeaponRarity {
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct WeaponAttributeModifier {
     /// the type of modifier
-    #[serde(rename = "modifierType")]
-    modifier_type: WeaponAttributeModifierType,
+    #[serde(rename = "type")]
+    pub modifier_type: WeaponAttributeModifierType,
     /// the value of the modifier
-    value: f64,
+    pub value: f64,
 }
 
 /// A list of available weapon attribute modifier types.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum WeaponAttributeModifierType {
     /// the modifier is a percentage
     #[serde(rename = "Percentage")]
@@ -80,3 +82,92 @@ pub enum WeaponAttributeModifierType {
     #[serde(rename = "Number")]
     Number,
 }
+
+/// Represents a weapon's full data across all of its levels (as stored in `mechanics/weapons`).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WeaponDefinition {
+    /// the name of the weapon
+    pub name: String,
+    /// the weapon's rarity
+    pub rarity: WeaponRarity,
+    /// the weapon's range type
+    #[serde(rename = "rangeType")]
+    pub range_type: RangeType,
+    /// the weapon's description
+    pub description: String,
+    /// the cost to purchase this weapon (if it is a purchasable weapon)
+    #[serde(rename = "purchaseCost")]
+    pub purchase_cost: PurchaseCost,
+    /// the weapon's mechanics level by level
+    #[serde(rename = "levelMechanics")]
+    pub level_mechanics: Vec<WeaponLevelMechanics>,
+    /// the file this weapon was loaded from (set when loading the catalog)
+    #[serde(skip)]
+    pub source: PathBuf,
+}
+
+impl WeaponDefinition {
+    /// Gets the weapon's mechanics at level `level`.
+    pub fn level_mechanics(&self, level: u8) -> Option<&WeaponLevelMechanics> {
+        self.level_mechanics.iter().find(|mechanics| mechanics.level == level)
+    }
+
+    /// Gets the highest level this weapon can be upgraded to.
+    pub fn max_level(&self) -> u8 {
+        self.level_mechanics.iter().map(|mechanics| mechanics.level).max().unwrap_or_default()
+    }
+
+    /// Creates a `Weapon` instance of this weapon at level `level`.
+    /// 
+    /// Returns `None` if the weapon has no mechanics for that level.
+    pub fn at_level(&self, level: u8) -> Option<Weapon> {
+        let mechanics = self.level_mechanics(level)?;
+
+        Some(Weapon {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            level,
+            rarity: self.rarity,
+            range_type: self.range_type,
+            attributes: mechanics.attributes.clone(),
+            base_damage: mechanics.base_damage,
+            base_attack_range: mechanics.base_attack_range,
+            base_attack_time: mechanics.base_attack_time,
+            crit_chance: mechanics.crit_chance,
+        })
+    }
+}
+
+/// Represents a weapon's data at a specific level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WeaponLevelMechanics {
+    /// the weapon's level
+    pub level: u8,
+    /// the weapon's base damage at this level
+    #[serde(rename = "baseDamage")]
+    pub base_damage: f64,
+    /// the weapon's base attack range at this level
+    #[serde(rename = "baseAttackRange")]
+    pub base_attack_range: f64,
+    /// how long each attack takes (in seconds) at this level
+    #[serde(rename = "baseAttackTime")]
+    pub base_attack_time: f64,
+    /// the weapon's crit chance at this level
+    #[serde(rename = "critChance")]
+    pub crit_chance: f64,
+    /// the weapon's attributes at this level
+    pub attributes: Vec<WeaponAttribute>,
+    /// the cost required to upgrade the weapon to this level
+    #[serde(rename = "upgradeCost")]
+    pub upgrade_cost: UpgradeCost,
+}
+
+/// Shows the minimum player level required to level up a weapon to a certain level.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WeaponLevelRequirement {
+    /// the weapon level
+    pub level: u8,
+    /// the minimum player level required to level up the weapon to this level
+    #[serde(rename = "minPlayerLevelRequired")]
+    pub min_player_level_required: u8,
+}
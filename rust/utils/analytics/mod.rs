@@ -0,0 +1,5 @@
+pub mod time_to_kill;
+pub mod monte_carlo;
+
+pub use time_to_kill::*;
+pub use monte_carlo::*;
@@ -0,0 +1,322 @@
+use std::thread;
+
+use serde::Serialize;
+
+use crate::{
+    models::{Enemy, Player},
+    utils::{simulate_combat, Combatant, CombatEventKind, CombatOutcome, CombatReport, GameRng, SimulationConfig, SimulationError},
+};
+
+// the z-score of a 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// Represents the settings of a batch of simulations of the same matchup.
+#[derive(Clone, Debug)]
+pub struct MonteCarloConfig {
+    /// how many fights are simulated
+    pub runs: u32,
+    /// the seed every fight's seed is derived from, so the same batch always results in the same report
+    pub seed: u64,
+    /// how many threads the fights are split across (0 uses every available core)
+    pub threads: usize,
+    /// the settings of each fight
+    pub simulation: SimulationConfig,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        MonteCarloConfig {
+            runs: 1000,
+            seed: 0,
+            threads: 0,
+            simulation: SimulationConfig::default(),
+        }
+    }
+}
+
+/// Represents the spread of a value across a batch of fights.
+#[derive(Clone, Serialize, Debug)]
+pub struct Distribution {
+    /// how many fights the value was sampled from
+    pub samples: usize,
+    /// the average value
+    pub mean: f64,
+    /// the 95% confidence interval of `mean`
+    pub mean_ci95: (f64, f64),
+    /// the middle value
+    pub median: f64,
+    /// the 5th percentile
+    pub p5: f64,
+    /// the 95th percentile
+    pub p95: f64,
+    /// the lowest value
+    pub min: f64,
+    /// the highest value
+    pub max: f64,
+}
+
+impl Distribution {
+    /// Summarizes `samples` (`None` if there are none). Percentiles are linearly interpolated between the closest samples.
+    pub fn from_samples(mut samples: Vec<f64>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort_by(f64::total_cmp);
+
+        let count = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / count;
+        let variance = if samples.len() > 1 {
+            samples.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / (count - 1.0)
+        } else {
+            0.0
+        };
+        let margin = Z_95 * (variance / count).sqrt();
+
+        Some(Distribution {
+            samples: samples.len(),
+            mean,
+            mean_ci95: (mean - margin, mean + margin),
+            median: percentile(&samples, 0.5),
+            p5: percentile(&samples, 0.05),
+            p95: percentile(&samples, 0.95),
+            min: samples[0],
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+/// Represents the aggregated results of a batch of simulations of the same matchup.
+#[derive(Clone, Serialize, Debug)]
+pub struct MonteCarloReport {
+    /// how many fights were simulated
+    pub runs: u32,
+    /// how many fights the player won
+    pub wins: u32,
+    /// how many fights the player was defeated in
+    pub losses: u32,
+    /// how many fights timed out
+    pub timeouts: u32,
+    /// the ratio of fights the player won
+    pub win_rate: f64,
+    /// the 95% confidence interval of `win_rate` (Wilson score interval)
+    pub win_rate_ci95: (f64, f64),
+    /// how long (in seconds) the player needed to defeat every enemy, across the fights the player won
+    pub time_to_kill: Option<Distribution>,
+    /// how long (in seconds) the enemies needed to defeat the player, across the fights the player lost
+    pub time_to_die: Option<Distribution>,
+    /// the ratio of the player's max HP left at the end of each fight (0 if the player was defeated)
+    pub player_hp_remaining: Option<Distribution>,
+    /// how many times the player was healed (by their skills or attributes, not regen) in each fight
+    pub heals: Option<Distribution>,
+    /// how much HP the player was healed for (by their skills or attributes, not regen) in each fight
+    pub hp_healed: Option<Distribution>,
+}
+
+/// Simulates `config.runs` fights between `player` (with their loadout) and a fresh copy of `enemies` across threads and aggregates their results.
+///
+/// Each fight gets its own seed derived from `config.seed` up front, so the report doesn't depend on how many threads are used.
+///
+/// Returns an error (without simulating anything) if `config.simulation` is invalid (see `SimulationConfig::validate`) or the player has no weapon equipped.
+pub fn run_monte_carlo(player: &Player, enemies: &[Enemy], config: &MonteCarloConfig) -> Result<MonteCarloReport, SimulationError> {
+    config.simulation.validate()?;
+
+    if player.equipped_weapon().is_none() {
+        return Err(SimulationError::NoWeaponEquipped);
+    }
+
+    let mut seeder = GameRng::new(config.seed);
+    let seeds: Vec<u64> = (0..config.runs).map(|_| seeder.next_u64()).collect();
+    let threads = match config.threads {
+        0 => thread::available_parallelism().map(usize::from).unwrap_or(1),
+        threads => threads,
+    };
+    let chunk_size = seeds.len().div_ceil(threads).max(1);
+
+    let reports: Vec<CombatReport> = thread::scope(|scope| {
+        let handles: Vec<_> = seeds
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|&seed| simulate_combat(player, &mut enemies.to_vec(), &config.simulation, &mut GameRng::new(seed)))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("A simulation thread panicked"))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|chunks| chunks.into_iter().flatten().collect())
+    })?;
+
+    Ok(summarize(player, &reports, config.runs))
+}
+
+/// aggregates the reports of a batch of fights
+fn summarize(player: &Player, reports: &[CombatReport], runs: u32) -> MonteCarloReport {
+    let count_outcome = |outcome: CombatOutcome| reports.iter().filter(|report| report.outcome == outcome).count() as u32;
+    let durations = |outcome: CombatOutcome| reports.iter().filter(|report| report.outcome == outcome).map(|report| report.duration).collect();
+    let heals: Vec<Vec<f64>> = reports.iter().map(player_heals).collect();
+
+    let wins = count_outcome(CombatOutcome::PlayerWon);
+
+    MonteCarloReport {
+        runs,
+        wins,
+        losses: count_outcome(CombatOutcome::PlayerDefeated),
+        timeouts: count_outcome(CombatOutcome::TimedOut),
+        win_rate: if runs > 0 { f64::from(wins) / f64::from(runs) } else { 0.0 },
+        win_rate_ci95: wilson_interval(wins, runs),
+        time_to_kill: Distribution::from_samples(durations(CombatOutcome::PlayerWon)),
+        time_to_die: Distribution::from_samples(durations(CombatOutcome::PlayerDefeated)),
+        player_hp_remaining: Distribution::from_samples(reports.iter().map(|report| report.player_hp / player.base_hp).collect()),
+        heals: Distribution::from_samples(heals.iter().map(|amounts| amounts.len() as f64).collect()),
+        hp_healed: Distribution::from_samples(heals.iter().map(|amounts| amounts.iter().sum()).collect()),
+    }
+}
+
+/// gets the amount of every heal the player received (from skills and attributes) in a fight
+fn player_heals(report: &CombatReport) -> Vec<f64> {
+    report.timeline
+        .iter()
+        .filter_map(|event| match event.kind {
+            CombatEventKind::Heal { target: Combatant::Player, amount, .. } => Some(amount),
+            _ => None,
+        })
+        .collect()
+}
+
+/// gets the value at `ratio` (from 0 to 1) of the sorted `samples`, interpolating between the two closest samples
+fn percentile(samples: &[f64], ratio: f64) -> f64 {
+    let position = ratio * (samples.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+
+    samples[lower] + (samples[upper] - samples[lower]) * (position - lower as f64)
+}
+
+/// gets the 95% Wilson score interval of `successes` out of `trials`, which (unlike the normal approximation) stays within 0 and 1
+fn wilson_interval(successes: u32, trials: u32) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+
+    let trials = f64::from(trials);
+    let ratio = f64::from(successes) / trials;
+    let z_squared = Z_95 * Z_95;
+    let center = (ratio + z_squared / (2.0 * trials)) / (1.0 + z_squared / trials);
+    let margin = Z_95 * (ratio * (1.0 - ratio) / trials + z_squared / (4.0 * trials * trials)).sqrt() / (1.0 + z_squared / trials);
+
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::Point,
+        utils::{load_catalog, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-4, "expected {}, got {}", expected, actual);
+    }
+
+    fn matchup(skills: &[&str]) -> (Player, Vec<Enemy>) {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = Player::new(catalog.player_level(1).unwrap(), &catalog.player_levels);
+        let enemy = catalog.enemy("Golem").unwrap().at_level(1, Point { x: 0.0, y: 0.0 }, 0).unwrap();
+
+        player.equip_weapon_unchecked(catalog.weapon("Common Rusty Sword").unwrap().at_level(1).unwrap());
+
+        for (slot, skill) in skills.iter().enumerate() {
+            player.equip_skill_unchecked(catalog.skill(skill).unwrap().at_level(1).unwrap(), slot).unwrap();
+        }
+
+        (player, vec![enemy])
+    }
+
+    #[test]
+    fn percentiles_interpolate_between_the_closest_samples() {
+        let samples = [1.0, 2.0, 3.0, 4.0];
+
+        assert_close(percentile(&samples, 0.0), 1.0);
+        assert_close(percentile(&samples, 0.5), 2.5);
+        // 5% of the way through 3 gaps is 0.15 of the way from 1 to 2
+        assert_close(percentile(&samples, 0.05), 1.15);
+        assert_close(percentile(&samples, 0.95), 3.85);
+        assert_close(percentile(&samples, 1.0), 4.0);
+        assert_close(percentile(&[7.0], 0.95), 7.0);
+    }
+
+    #[test]
+    fn distribution_summarizes_unsorted_samples() {
+        let distribution = Distribution::from_samples(vec![9.0, 2.0, 4.0, 5.0, 4.0, 7.0, 4.0, 5.0]).unwrap();
+
+        assert_eq!(distribution.samples, 8);
+        assert_close(distribution.mean, 5.0);
+        // the sample variance is 32 / 7, so the margin is 1.96 * sqrt(32 / 7 / 8)
+        assert_close(distribution.mean_ci95.0, 5.0 - 1.481_594);
+        assert_close(distribution.mean_ci95.1, 5.0 + 1.481_594);
+        assert_close(distribution.median, 4.5);
+        assert_close(distribution.min, 2.0);
+        assert_close(distribution.max, 9.0);
+        assert!(Distribution::from_samples(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn wilson_interval_matches_hand_computed_bounds() {
+        let (lower, upper) = wilson_interval(5, 10);
+        assert_close(lower, 0.236_593);
+        assert_close(upper, 0.763_407);
+
+        // unlike the normal approximation, the interval of 0 successes isn't collapsed to 0
+        let (lower, upper) = wilson_interval(0, 10);
+        assert_close(lower, 0.0);
+        assert_close(upper, 0.277_530);
+
+        let (lower, upper) = wilson_interval(10, 10);
+        assert_close(lower, 0.722_470);
+        assert_close(upper, 1.0);
+
+        assert_eq!(wilson_interval(0, 0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn report_doesnt_depend_on_the_thread_count() {
+        let (player, enemies) = matchup(&["Fireball"]);
+        let report = |threads| {
+            let config = MonteCarloConfig { runs: 40, seed: 9, threads, ..MonteCarloConfig::default() };
+
+            serde_json::to_string(&run_monte_carlo(&player, &enemies, &config).unwrap()).unwrap()
+        };
+
+        assert_eq!(report(1), report(3));
+        assert_eq!(report(1), report(8));
+    }
+
+    #[test]
+    fn counts_the_heals_of_slotted_skills() {
+        let config = MonteCarloConfig { runs: 20, ..MonteCarloConfig::default() };
+        let (player, enemies) = matchup(&[]);
+        let without_heals = run_monte_carlo(&player, &enemies, &config).unwrap();
+        let (player, enemies) = matchup(&["Restorative Touch"]);
+        let with_heals = run_monte_carlo(&player, &enemies, &config).unwrap();
+
+        assert_eq!(without_heals.heals.unwrap().max, 0.0);
+        assert!(with_heals.heals.unwrap().min >= 1.0);
+        assert!(with_heals.hp_healed.unwrap().mean > 0.0);
+    }
+
+    #[test]
+    fn rejects_a_player_without_a_weapon() {
+        let (mut player, enemies) = matchup(&[]);
+        player.unequip_weapon();
+
+        assert!(matches!(run_monte_carlo(&player, &enemies, &MonteCarloConfig::default()), Err(SimulationError::NoWeaponEquipped)));
+    }
+}
@@ -0,0 +1,324 @@
+use std::{fmt, ops::RangeInclusive};
+
+use serde::Serialize;
+
+use crate::{
+    models::{Player, Point},
+    utils::{simulate_combat, Combatant, CombatOutcome, GameCatalog, GameRng, SimulationConfig, SimulationError},
+};
+
+/// Represents a sweep of fights between a player wielding a weapon and an enemy across ranges of levels.
+#[derive(Clone, Debug)]
+pub struct TimeToKillSweep {
+    /// the name of the weapon the player wields
+    pub weapon: String,
+    /// the name of the enemy the player fights
+    pub enemy: String,
+    /// the player levels to sweep (levels that don't exist in the catalog are skipped)
+    pub player_levels: RangeInclusive<u8>,
+    /// the weapon levels to sweep (levels that don't exist in the catalog are skipped)
+    pub weapon_levels: RangeInclusive<u8>,
+    /// the enemy levels to sweep (levels that don't exist in the catalog are skipped)
+    pub enemy_levels: RangeInclusive<u8>,
+    /// how many fights are simulated for each combination of levels (their results are averaged)
+    pub runs: u32,
+    /// the seed of the fights' random numbers, so the same sweep always results in the same table
+    pub seed: u64,
+    /// the settings of each fight
+    pub config: SimulationConfig,
+}
+
+impl TimeToKillSweep {
+    /// Creates a sweep of `weapon` against `enemy` across every level that exists in `catalog` (100 runs each, with a seed of 0).
+    ///
+    /// Returns an error if the weapon or enemy doesn't exist.
+    pub fn all_levels(catalog: &GameCatalog, weapon: &str, enemy: &str) -> Result<Self, AnalyticsError> {
+        let weapon = catalog.weapon(weapon).ok_or_else(|| AnalyticsError::UnknownWeapon(weapon.to_string()))?;
+        let enemy = catalog.enemy(enemy).ok_or_else(|| AnalyticsError::UnknownEnemy(enemy.to_string()))?;
+        let max_player_level = catalog.player_levels.iter().map(|stats| stats.level).max().unwrap_or_default();
+
+        Ok(TimeToKillSweep {
+            weapon: weapon.name.clone(),
+            enemy: enemy.name.clone(),
+            player_levels: 1..=max_player_level,
+            weapon_levels: 1..=weapon.max_level(),
+            enemy_levels: 1..=enemy.max_level(),
+            runs: 100,
+            seed: 0,
+            config: SimulationConfig::default(),
+        })
+    }
+}
+
+/// Represents the averaged results of the fights of a single combination of levels.
+#[derive(Clone, Serialize, Debug)]
+pub struct TimeToKillRow {
+    /// the player's level
+    pub player_level: u8,
+    /// the weapon's level
+    pub weapon_level: u8,
+    /// the enemy's level
+    pub enemy_level: u8,
+    /// the damage the player deals to the enemy per second (including crits, attribute procs and damage over time)
+    pub player_dps: f64,
+    /// the damage the enemy deals to the player per second (including crits, skill procs and damage over time)
+    pub enemy_dps: f64,
+    /// how long (in seconds) the player needs to kill the enemy at `player_dps`, offset by the enemy's HP regen
+    /// (`None` if the player can't out-damage the enemy's regen)
+    pub time_to_kill: Option<f64>,
+    /// how long (in seconds) the enemy needs to kill the player at `enemy_dps`, offset by the player's HP regen
+    /// (`None` if the enemy can't out-damage the player's regen)
+    pub time_to_die: Option<f64>,
+    /// the ratio of the player's HP left once the enemy is killed at these paces (negative if the player is killed first; `None` if the enemy can't be killed)
+    pub survival_margin: Option<f64>,
+    /// the ratio of simulated fights the player won
+    pub win_rate: f64,
+}
+
+/// Represents an error that occurred while running an analysis over the catalog.
+#[derive(Debug)]
+pub enum AnalyticsError {
+    /// no weapon with this name exists in the catalog
+    UnknownWeapon(String),
+    /// no enemy with this name exists in the catalog
+    UnknownEnemy(String),
+    /// the fights can't be simulated with the sweep's settings
+    Simulation(SimulationError),
+}
+
+impl fmt::Display for AnalyticsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalyticsError::UnknownWeapon(name) => write!(f, "weapon `{}` doesn't exist", name),
+            AnalyticsError::UnknownEnemy(name) => write!(f, "enemy `{}` doesn't exist", name),
+            AnalyticsError::Simulation(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for AnalyticsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnalyticsError::Simulation(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Simulates `sweep.runs` one-on-one fights for every combination of player, weapon and enemy levels of `sweep` and averages them into a table.
+///
+/// The DPS of each side is measured over the whole duration of the fights, so it accounts for crits, misses, procs and damage over time.
+/// Since a fight ends as soon as one side is defeated, the time to kill and time to die are derived from the measured DPS instead of the fights' durations.
+///
+/// Rows are ordered by player level, then weapon level, then enemy level.
+///
+/// Returns an error if the weapon or enemy doesn't exist, or if `sweep.config` is invalid (see `SimulationConfig::validate`).
+pub fn sweep_time_to_kill(catalog: &GameCatalog, sweep: &TimeToKillSweep) -> Result<Vec<TimeToKillRow>, AnalyticsError> {
+    let weapon = catalog.weapon(&sweep.weapon).ok_or_else(|| AnalyticsError::UnknownWeapon(sweep.weapon.clone()))?;
+    let enemy = catalog.enemy(&sweep.enemy).ok_or_else(|| AnalyticsError::UnknownEnemy(sweep.enemy.clone()))?;
+    sweep.config.validate().map_err(AnalyticsError::Simulation)?;
+    let mut rng = GameRng::new(sweep.seed);
+    let mut rows = Vec::new();
+
+    for player_level in sweep.player_levels.clone() {
+        let Some(player) = catalog.player_level(player_level).map(|stats| Player::new(stats, &catalog.player_levels)) else {
+            continue;
+        };
+
+        for weapon_level in sweep.weapon_levels.clone() {
+            let Some(weapon) = weapon.at_level(weapon_level) else {
+                continue;
+            };
+            let mut player = player.clone();
+            player.equip_weapon_unchecked(weapon);
+
+            for enemy_level in sweep.enemy_levels.clone() {
+                let Some(enemy) = enemy.at_level(enemy_level, Point { x: 0.0, y: 0.0 }, 0) else {
+                    continue;
+                };
+
+                let mut duration = 0.0;
+                let mut player_damage = 0.0;
+                let mut enemy_damage = 0.0;
+                let mut wins = 0;
+
+                for _ in 0..sweep.runs {
+                    let mut enemies = vec![enemy.clone()];
+                    let report = simulate_combat(&player, &mut enemies, &sweep.config, &mut rng.fork()).map_err(AnalyticsError::Simulation)?;
+
+                    duration += report.duration;
+                    player_damage += report.damage_taken_by(Combatant::Enemy(0));
+                    enemy_damage += report.damage_taken_by(Combatant::Player);
+
+                    if report.outcome == CombatOutcome::PlayerWon {
+                        wins += 1;
+                    }
+                }
+
+                let player_dps = if duration > 0.0 { player_damage / duration } else { 0.0 };
+                let enemy_dps = if duration > 0.0 { enemy_damage / duration } else { 0.0 };
+                let time_to_kill = time_to_deplete(enemy.base_hp, player_dps - enemy.base_hp_regen);
+                let time_to_die = time_to_deplete(player.base_hp, enemy_dps - player.base_hp_regen);
+
+                rows.push(TimeToKillRow {
+                    player_level,
+                    weapon_level,
+                    enemy_level,
+                    player_dps,
+                    enemy_dps,
+                    time_to_kill,
+                    time_to_die,
+                    survival_margin: time_to_kill.map(|time_to_kill| time_to_die.map_or(1.0, |time_to_die| 1.0 - time_to_kill / time_to_die)),
+                    win_rate: if sweep.runs > 0 { f64::from(wins) / f64::from(sweep.runs) } else { 0.0 },
+                });
+            }
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Converts `rows` into a CSV table (with a header row). Missing values are left empty.
+pub fn time_to_kill_csv(rows: &[TimeToKillRow]) -> String {
+    let optional = |value: Option<f64>| value.map(|value| value.to_string()).unwrap_or_default();
+    let mut csv = String::from("player_level,weapon_level,enemy_level,player_dps,enemy_dps,time_to_kill,time_to_die,survival_margin,win_rate\n");
+
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            row.player_level,
+            row.weapon_level,
+            row.enemy_level,
+            row.player_dps,
+            row.enemy_dps,
+            optional(row.time_to_kill),
+            optional(row.time_to_die),
+            optional(row.survival_margin),
+            row.win_rate,
+        ));
+    }
+
+    csv
+}
+
+/// gets how long (in seconds) it takes to deplete `hp` at `net_dps` (`None` if it never depletes)
+fn time_to_deplete(hp: f64, net_dps: f64) -> Option<f64> {
+    (net_dps > 0.0).then(|| hp / net_dps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{load_catalog, CATALOG_ROOT};
+
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "expected {}, got {}", expected, actual);
+    }
+
+    /// the catalog with every chance of a level 3 rusty sword against a level 1 Golem removed, except for the sword always critting
+    fn deterministic_catalog() -> GameCatalog {
+        let mut catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let sword = catalog.weapons.iter_mut().find(|weapon| weapon.name == "Common Rusty Sword").unwrap();
+        sword.level_mechanics[2].crit_chance = 1.0;
+        sword.level_mechanics[2].attributes.clear();
+        let golem = catalog.enemies.iter_mut().find(|enemy| enemy.name == "Golem").unwrap();
+        golem.level_mechanics[0].crit_chance = 0.0;
+        golem.level_mechanics[0].skill.level_mechanics.iter_mut().for_each(|mechanics| mechanics.chance = 0.0);
+
+        catalog
+    }
+
+    fn sweep(catalog: &GameCatalog) -> TimeToKillSweep {
+        TimeToKillSweep {
+            player_levels: 5..=5,
+            weapon_levels: 3..=3,
+            enemy_levels: 1..=1,
+            runs: 3,
+            ..TimeToKillSweep::all_levels(catalog, "Common Rusty Sword", "Golem").unwrap()
+        }
+    }
+
+    #[test]
+    fn level_5_player_with_a_level_3_sword_against_a_level_1_golem() {
+        let catalog = deterministic_catalog();
+        let rows = sweep_time_to_kill(&catalog, &sweep(&catalog)).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        let row = &rows[0];
+
+        assert_eq!((row.player_level, row.weapon_level, row.enemy_level), (5, 3, 1));
+        // the sword crits for 30 * 2 = 60 at 0.75s, 2.25s, ..., 8.25s (6 hits), while the Golem (350 HP) regenerates 1.75 HP every second,
+        // so it's left with 4 + 1.75 HP by the 7th hit at 9.75s: 6 * 60 + 5.75 = 365.75 damage over 9.75s
+        assert_close(row.player_dps, 365.75 / 9.75);
+        // the Golem hits for 30 at 1.25s, 3.75s, 6.25s and 8.75s
+        assert_close(row.enemy_dps, 120.0 / 9.75);
+        // net of regen: 350 HP at (37.51 - 1.75) DPS and 650 HP at (12.31 - 5.2) DPS
+        let time_to_kill = 350.0 / (365.75 / 9.75 - 1.75);
+        let time_to_die = 650.0 / (120.0 / 9.75 - 5.2);
+
+        assert_close(row.time_to_kill.unwrap(), time_to_kill);
+        assert_close(row.time_to_die.unwrap(), time_to_die);
+        assert_close(row.survival_margin.unwrap(), 1.0 - time_to_kill / time_to_die);
+        assert_close(row.win_rate, 1.0);
+    }
+
+    #[test]
+    fn enemies_that_cant_out_damage_regen_never_kill() {
+        assert_eq!(time_to_deplete(100.0, 0.0), None);
+        assert_eq!(time_to_deplete(100.0, -2.0), None);
+        assert_eq!(time_to_deplete(100.0, 4.0), Some(25.0));
+    }
+
+    #[test]
+    fn csv_and_json_share_the_same_columns() {
+        let catalog = deterministic_catalog();
+        let rows = sweep_time_to_kill(&catalog, &sweep(&catalog)).unwrap();
+        let csv = time_to_kill_csv(&rows);
+        let mut lines = csv.lines();
+        let header: Vec<&str> = lines.next().unwrap().split(',').collect();
+        let json = serde_json::to_value(&rows).unwrap();
+        let keys: Vec<&str> = json[0].as_object().unwrap().keys().map(String::as_str).collect();
+
+        assert_eq!(header, [
+            "player_level",
+            "weapon_level",
+            "enemy_level",
+            "player_dps",
+            "enemy_dps",
+            "time_to_kill",
+            "time_to_die",
+            "survival_margin",
+            "win_rate",
+        ]);
+        assert_eq!(keys.len(), header.len());
+        assert!(header.iter().all(|column| keys.contains(column)));
+        assert_eq!(lines.next().unwrap().split(',').count(), header.len());
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn missing_values_are_left_empty_in_csv() {
+        let row = TimeToKillRow {
+            player_level: 1,
+            weapon_level: 1,
+            enemy_level: 1,
+            player_dps: 1.0,
+            enemy_dps: 0.0,
+            time_to_kill: None,
+            time_to_die: None,
+            survival_margin: None,
+            win_rate: 0.0,
+        };
+
+        assert_eq!(time_to_kill_csv(&[row]).lines().nth(1), Some("1,1,1,1,0,,,,0"));
+    }
+
+    #[test]
+    fn unknown_weapons_and_enemies_are_rejected() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+
+        assert!(matches!(TimeToKillSweep::all_levels(&catalog, "Excalibur", "Golem"), Err(AnalyticsError::UnknownWeapon(_))));
+        assert!(matches!(TimeToKillSweep::all_levels(&catalog, "Common Rusty Sword", "Dragon"), Err(AnalyticsError::UnknownEnemy(_))));
+    }
+}
@@ -0,0 +1,36 @@
+use std::{fmt, io, path::PathBuf};
+
+use crate::utils::JsonFileError;
+
+/// Represents an error that occurred while loading the `mechanics/` catalog.
+#[derive(Debug)]
+pub enum CatalogError {
+    /// a directory within the catalog couldn't be read
+    ReadDir { path: PathBuf, source: io::Error },
+    /// a file within the catalog couldn't be read or deserialized
+    File(JsonFileError),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::ReadDir { path, source } => write!(f, "failed to read directory {}: {}", path.display(), source),
+            CatalogError::File(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CatalogError::ReadDir { source, .. } => Some(source),
+            CatalogError::File(err) => Some(err),
+        }
+    }
+}
+
+impl From<JsonFileError> for CatalogError {
+    fn from(err: JsonFileError) -> Self {
+        CatalogError::File(err)
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use crate::models::{
+    Attribute, BreathableAreaParameters, EnemyDefinition, GrowthParameters, Item, PlayerLevelStats, Quest, SkillDefinition, SkillLevelRequirement,
+    WeaponDefinition, WeaponLevelRequirement,
+};
+
+/// Represents every piece of game content stored under `mechanics/`, loaded into memory.
+/// 
+/// This is intended to be the single source of truth for all balancing tools, so that each of them doesn't have to re-read the JSON files.
+/// 
+/// Use `load_catalog` to create an instance.
+#[derive(Clone, Debug, Default)]
+pub struct GameCatalog {
+    /// the directory the catalog was loaded from
+    pub root: PathBuf,
+    /// all attributes (from `attributes.json`)
+    pub attributes: Vec<Attribute>,
+    /// all weapons (from `weapons/`)
+    pub weapons: Vec<WeaponDefinition>,
+    /// all skills (from `skills/`)
+    pub skills: Vec<SkillDefinition>,
+    /// all enemies (from `enemies/`)
+    pub enemies: Vec<EnemyDefinition>,
+    /// all items (from `items.json`)
+    pub items: Vec<Item>,
+    /// all quests in the order they are defined (from `quests.json`)
+    pub quests: Vec<Quest>,
+    /// the player's stats level by level (from `player/playerLevelMechanics.json`)
+    pub player_levels: Vec<PlayerLevelStats>,
+    /// the minimum player level required for each weapon level (from `weapons/weaponLevelRequirement.json`)
+    pub weapon_level_requirements: Vec<WeaponLevelRequirement>,
+    /// the minimum player level required for each skill level (from `skills/skillLevelRequirement.json`)
+    pub skill_level_requirements: Vec<SkillLevelRequirement>,
+    /// the parameters the player and enemy level tables are generated from (from `growth.json`, if it exists)
+    pub growth: Option<GrowthParameters>,
+    /// how breathable areas are unlocked and what happens outside of them (from `breathableAreas.json`, if it exists)
+    pub breathable_areas: Option<BreathableAreaParameters>,
+}
+
+impl GameCatalog {
+    /// Gets the attribute with the name `name`.
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|attribute| attribute.name == name)
+    }
+
+    /// Gets the weapon with the name `name`.
+    pub fn weapon(&self, name: &str) -> Option<&WeaponDefinition> {
+        self.weapons.iter().find(|weapon| weapon.name == name)
+    }
+
+    /// Gets the skill with the name `name`.
+    pub fn skill(&self, name: &str) -> Option<&SkillDefinition> {
+        self.skills.iter().find(|skill| skill.name == name)
+    }
+
+    /// Gets the enemy with the name `name`.
+    pub fn enemy(&self, name: &str) -> Option<&EnemyDefinition> {
+        self.enemies.iter().find(|enemy| enemy.name == name)
+    }
+
+    /// Gets the item with the name `name`.
+    pub fn item(&self, name: &str) -> Option<&Item> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    /// Gets the quest with the ID `id` within chapter `chapter`.
+    pub fn quest(&self, chapter: u8, id: u8) -> Option<&Quest> {
+        self.quests.iter().find(|quest| quest.chapter == chapter && quest.id == id)
+    }
+
+    /// Gets all quests whose name is `name` (quests within the same chapter share a name).
+    pub fn quests_by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Quest> + 'a {
+        self.quests.iter().filter(move |quest| quest.name == name)
+    }
+
+    /// Gets the player's stats at level `level`.
+    pub fn player_level(&self, level: u8) -> Option<&PlayerLevelStats> {
+        self.player_levels.iter().find(|stats| stats.level == level)
+    }
+
+    /// Gets the minimum player level required to level up a weapon to level `level`.
+    pub fn weapon_level_requirement(&self, level: u8) -> Option<&WeaponLevelRequirement> {
+        self.weapon_level_requirements.iter().find(|requirement| requirement.level == level)
+    }
+
+    /// Gets the minimum player level required to level up a skill to level `level`.
+    pub fn skill_level_requirement(&self, level: u8) -> Option<&SkillLevelRequirement> {
+        self.skill_level_requirements.iter().find(|requirement| requirement.level == level)
+    }
+}
@@ -0,0 +1,91 @@
+use std::{fs::read_dir, path::{Path, PathBuf}};
+
+use crate::{models::{EnemyDefinition, SkillDefinition, WeaponDefinition}, utils::read_json_file};
+
+use super::{CatalogError, GameCatalog};
+
+/// The default directory (relative to the crate root) that contains all game content.
+pub const CATALOG_ROOT: &str = "mechanics";
+
+// the names of the files with a fixed location within the catalog
+pub const ATTRIBUTES_FILE: &str = "attributes.json";
+pub const ITEMS_FILE: &str = "items.json";
+pub const QUESTS_FILE: &str = "quests.json";
+pub const GROWTH_FILE: &str = "growth.json";
+pub const BREATHABLE_AREAS_FILE: &str = "breathableAreas.json";
+pub const PLAYER_LEVELS_FILE: &str = "playerLevelMechanics.json";
+pub const WEAPON_LEVEL_REQUIREMENT_FILE: &str = "weaponLevelRequirement.json";
+pub const SKILL_LEVEL_REQUIREMENT_FILE: &str = "skillLevelRequirement.json";
+
+/// Loads every JSON file under `root` into a `GameCatalog` instance.
+/// 
+/// Files are discovered recursively and deserialized based on where they live:
+/// 1. `attributes.json`, `items.json`, `quests.json`, `growth.json` and `breathableAreas.json` at the top level.
+/// 2. `player/playerLevelMechanics.json`.
+/// 3. `weapons/**` (one weapon per file, apart from `weaponLevelRequirement.json`).
+/// 4. `skills/**` (one skill per file, apart from `skillLevelRequirement.json`).
+/// 5. `enemies/**` (one enemy per file).
+/// 
+/// Any other JSON file is skipped with a warning.
+pub fn load_catalog(root: impl AsRef<Path>) -> Result<GameCatalog, CatalogError> {
+    let root = root.as_ref();
+    let mut catalog = GameCatalog { root: root.to_path_buf(), ..Default::default() };
+
+    // sort the files so that the catalog's order doesn't depend on the file system
+    let mut files = Vec::new();
+    collect_json_files(root, &mut files)?;
+    files.sort();
+
+    for path in files {
+        let relative_path = path.strip_prefix(root).unwrap_or(&path);
+        let file_name = relative_path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let top_level = relative_path.components().count() == 1;
+        let directory = relative_path.components().next().and_then(|component| component.as_os_str().to_str()).unwrap_or_default();
+
+        match (top_level, directory, file_name) {
+            (true, _, ATTRIBUTES_FILE) => catalog.attributes = read_json_file(&path)?,
+            (true, _, ITEMS_FILE) => catalog.items = read_json_file(&path)?,
+            (true, _, QUESTS_FILE) => catalog.quests = read_json_file(&path)?,
+            (true, _, GROWTH_FILE) => catalog.growth = Some(read_json_file(&path)?),
+            (true, _, BREATHABLE_AREAS_FILE) => catalog.breathable_areas = Some(read_json_file(&path)?),
+            (false, "player", PLAYER_LEVELS_FILE) => catalog.player_levels = read_json_file(&path)?,
+            (false, "weapons", WEAPON_LEVEL_REQUIREMENT_FILE) => catalog.weapon_level_requirements = read_json_file(&path)?,
+            (false, "skills", SKILL_LEVEL_REQUIREMENT_FILE) => catalog.skill_level_requirements = read_json_file(&path)?,
+            (false, "weapons", _) => {
+                let mut weapon: WeaponDefinition = read_json_file(&path)?;
+                weapon.source = path.clone();
+                catalog.weapons.push(weapon);
+            }
+            (false, "skills", _) => {
+                let mut skill: SkillDefinition = read_json_file(&path)?;
+                skill.source = path.clone();
+                catalog.skills.push(skill);
+            }
+            (false, "enemies", _) => {
+                let mut enemy: EnemyDefinition = read_json_file(&path)?;
+                enemy.source = path.clone();
+                catalog.enemies.push(enemy);
+            }
+            _ => tracing::warn!("skipping unrecognized catalog file: {}", path.display()),
+        }
+    }
+
+    Ok(catalog)
+}
+
+/// Recursively collects the paths of all `.json` files within `dir` into `files`.
+fn collect_json_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), CatalogError> {
+    let entries = read_dir(dir).map_err(|source| CatalogError::ReadDir { path: dir.to_path_buf(), source })?;
+
+    for entry in entries {
+        let path = entry.map_err(|source| CatalogError::ReadDir { path: dir.to_path_buf(), source })?.path();
+
+        if path.is_dir() {
+            collect_json_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "json") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod error;
+pub mod game_catalog;
+pub mod loader;
+pub mod validation;
+
+pub use error::*;
+pub use game_catalog::*;
+pub use loader::*;
+pub use validation::*;
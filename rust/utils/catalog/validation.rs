@@ -0,0 +1,697 @@
+use std::{collections::HashSet, fmt, path::{Path, PathBuf}};
+
+use serde::Serialize;
+
+use crate::models::{Attribute, AttributeType, Formula, ItemModifier, MiscReward, StatusEffect, TargetMechanics, UnlockRequirement, UpgradeCostGrowth};
+
+use super::{
+    GameCatalog, ATTRIBUTES_FILE, BREATHABLE_AREAS_FILE, GROWTH_FILE, ITEMS_FILE, PLAYER_LEVELS_FILE, QUESTS_FILE, SKILL_LEVEL_REQUIREMENT_FILE,
+    WEAPON_LEVEL_REQUIREMENT_FILE,
+};
+
+/// How severe a catalog diagnostic is.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Debug)]
+pub enum Severity {
+    /// the content is suspicious but still usable
+    #[serde(rename = "warning")]
+    Warning,
+    /// the content breaks an invariant and must be fixed
+    #[serde(rename = "error")]
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Represents a single problem found while validating the catalog.
+#[derive(Clone, Serialize, Debug)]
+pub struct Diagnostic {
+    /// how severe the problem is
+    pub severity: Severity,
+    /// the file that contains the problem
+    pub path: PathBuf,
+    /// a JSON pointer (RFC 6901) to the offending value within `path`
+    pub pointer: String,
+    /// a message explaining the problem
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}#{}: {}", self.severity, self.path.display(), self.pointer, self.message)
+    }
+}
+
+/// Checks whether any of `diagnostics` is an error.
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error)
+}
+
+/// Validates the invariants of all content within `catalog` and returns every violation found.
+///
+/// The invariants checked are mostly the ones documented on the models themselves, e.g.:
+/// 1. chances (crit chance, attribute chance, skill chance) must be a ratio from 0 to 1.
+/// 2. `ItemModifier.values` must be as long as `stat_boosts` (if present).
+/// 3. a skill's target mechanics must agree with the target mechanics of its attributes.
+/// 4. an attribute of type `Status Effect` must inflict a status effect.
+/// 5. quests can only reference items, enemies, skills and weapons that exist in the catalog, and quests without a type must name their area or NPC.
+/// 6. level tables must go from level 1 upwards without gaps.
+/// 7. growth formulas can only use the variables available to them, and weapon/skill growth can only refer to weapons/skills that exist.
+/// 8. breathable areas can only be unlocked by quests that exist in the catalog, and each area is only listed once.
+pub fn validate_catalog(catalog: &GameCatalog) -> Vec<Diagnostic> {
+    let mut validator = Validator { catalog, diagnostics: Vec::new() };
+
+    validator.validate_attributes();
+    validator.validate_weapons();
+    validator.validate_skills();
+    validator.validate_enemies();
+    validator.validate_items();
+    validator.validate_quests();
+    validator.validate_level_tables();
+    validator.validate_growth();
+    validator.validate_breathable_areas();
+
+    validator.diagnostics
+}
+
+/// Walks through the catalog and collects diagnostics.
+struct Validator<'a> {
+    catalog: &'a GameCatalog,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, severity: Severity, path: &Path, pointer: String, message: String) {
+        self.diagnostics.push(Diagnostic { severity, path: path.to_path_buf(), pointer, message });
+    }
+
+    fn error(&mut self, path: &Path, pointer: String, message: String) {
+        self.report(Severity::Error, path, pointer, message);
+    }
+
+    fn warning(&mut self, path: &Path, pointer: String, message: String) {
+        self.report(Severity::Warning, path, pointer, message);
+    }
+
+    /// the path of a file with a fixed location within the catalog
+    fn file(&self, relative_path: &str) -> PathBuf {
+        self.catalog.root.join(relative_path)
+    }
+
+    /// checks that `chance` is a ratio from 0 to 1
+    fn check_chance(&mut self, path: &Path, pointer: String, chance: f64) {
+        if !(0.0..=1.0).contains(&chance) {
+            self.error(path, pointer, format!("chance must be a ratio from 0 to 1, got {}", chance));
+        }
+    }
+
+    /// checks that `levels` go from 1 upwards without gaps or duplicates
+    fn check_levels(&mut self, path: &Path, pointer_prefix: &str, levels: impl Iterator<Item = u8>) {
+        for (index, level) in levels.enumerate() {
+            let expected_level = index + 1;
+
+            if usize::from(level) != expected_level {
+                self.error(
+                    path,
+                    format!("{}/{}/level", pointer_prefix, index),
+                    format!("expected level {}, got {}", expected_level, level),
+                );
+            }
+        }
+    }
+
+    /// checks that `formula` only uses variables within `allowed`
+    fn check_formula(&mut self, path: &Path, pointer: String, formula: &Formula, allowed: &[&str]) {
+        if let Err(err) = formula.check_variables(allowed) {
+            self.error(path, pointer, err.to_string());
+        }
+    }
+
+    /// checks that each name within `names` is only used once
+    fn check_unique_names<'n>(&mut self, kind: &str, names: impl Iterator<Item = (&'n Path, String, &'n str)>) {
+        let mut seen = HashSet::new();
+
+        for (path, pointer, name) in names {
+            if !seen.insert(name) {
+                self.error(path, pointer, format!("duplicate {} name `{}`", kind, name));
+            }
+        }
+    }
+
+    /// checks that an attribute's types agree with the status effects it inflicts
+    fn check_attribute_status_effects(&mut self, path: &Path, pointer: &str, attribute: &Attribute) {
+        let inflicts_status_effect = attribute.status_effects
+            .iter()
+            .flatten()
+            .any(|status_effect| *status_effect != StatusEffect::None);
+        let is_status_effect_type = attribute.types.contains(&AttributeType::StatusEffect);
+
+        if is_status_effect_type && !inflicts_status_effect {
+            self.error(
+                path,
+                format!("{}/statusEffects", pointer),
+                format!("attribute `{}` is of type `Status Effect` but doesn't inflict any status effect", attribute.name),
+            );
+        } else if inflicts_status_effect && !is_status_effect_type {
+            self.warning(
+                path,
+                format!("{}/types", pointer),
+                format!("attribute `{}` inflicts a status effect but isn't of type `Status Effect`", attribute.name),
+            );
+        }
+    }
+
+    /// checks that an attribute embedded within a weapon, skill or enemy matches its definition in `attributes.json`
+    fn check_embedded_attribute(&mut self, path: &Path, pointer: &str, attribute: &Attribute) {
+        match self.catalog.attribute(&attribute.name) {
+            None => self.error(
+                path,
+                format!("{}/name", pointer),
+                format!("attribute `{}` doesn't exist in {}", attribute.name, ATTRIBUTES_FILE),
+            ),
+            Some(definition) if definition != attribute => self.warning(
+                path,
+                pointer.to_string(),
+                format!("attribute `{}` differs from its definition in {}", attribute.name, ATTRIBUTES_FILE),
+            ),
+            _ => {}
+        }
+
+        self.check_attribute_status_effects(path, pointer, attribute);
+    }
+
+    fn validate_attributes(&mut self) {
+        let catalog = self.catalog;
+        let path = self.file(ATTRIBUTES_FILE);
+
+        self.check_unique_names(
+            "attribute",
+            catalog.attributes.iter().enumerate().map(|(index, attribute)| (path.as_path(), format!("/{}/name", index), attribute.name.as_str())),
+        );
+
+        for (index, attribute) in catalog.attributes.iter().enumerate() {
+            self.check_attribute_status_effects(&path, &format!("/{}", index), attribute);
+        }
+    }
+
+    fn validate_weapons(&mut self) {
+        let catalog = self.catalog;
+
+        self.check_unique_names(
+            "weapon",
+            catalog.weapons.iter().map(|weapon| (weapon.source.as_path(), "/name".to_string(), weapon.name.as_str())),
+        );
+
+        for weapon in &catalog.weapons {
+            let path = weapon.source.as_path();
+
+            self.check_levels(path, "/levelMechanics", weapon.level_mechanics.iter().map(|mechanics| mechanics.level));
+
+            for (level_index, mechanics) in weapon.level_mechanics.iter().enumerate() {
+                let level_pointer = format!("/levelMechanics/{}", level_index);
+
+                self.check_chance(path, format!("{}/critChance", level_pointer), mechanics.crit_chance);
+
+                for (attribute_index, weapon_attribute) in mechanics.attributes.iter().enumerate() {
+                    let attribute_pointer = format!("{}/attributes/{}", level_pointer, attribute_index);
+
+                    self.check_chance(path, format!("{}/chance", attribute_pointer), weapon_attribute.chance);
+                    self.check_embedded_attribute(path, &format!("{}/attribute", attribute_pointer), &weapon_attribute.attribute);
+                }
+
+                if self.catalog.weapon_level_requirement(mechanics.level).is_none() {
+                    self.warning(
+                        path,
+                        format!("{}/level", level_pointer),
+                        format!("weapon level {} has no entry in {}", mechanics.level, WEAPON_LEVEL_REQUIREMENT_FILE),
+                    );
+                }
+            }
+        }
+    }
+
+    fn validate_skills(&mut self) {
+        let catalog = self.catalog;
+
+        self.check_unique_names(
+            "skill",
+            catalog.skills.iter().map(|skill| (skill.source.as_path(), "/name".to_string(), skill.name.as_str())),
+        );
+
+        for skill in &catalog.skills {
+            let path = skill.source.as_path();
+            let is_damaging = skill.level_mechanics.first().is_some_and(|mechanics| mechanics.base_damage.is_some());
+
+            self.check_levels(path, "/levelMechanics", skill.level_mechanics.iter().map(|mechanics| mechanics.level));
+
+            for (level_index, mechanics) in skill.level_mechanics.iter().enumerate() {
+                let level_pointer = format!("/levelMechanics/{}", level_index);
+
+                if mechanics.base_damage.is_some() != is_damaging {
+                    self.error(
+                        path,
+                        format!("{}/baseDamage", level_pointer),
+                        "`baseDamage` must either be present in every level or in none of them".to_string(),
+                    );
+                }
+
+                let has_status_effect_attribute = mechanics.attributes
+                    .iter()
+                    .any(|skill_attribute| skill_attribute.attribute.types.contains(&AttributeType::StatusEffect));
+
+                if mechanics.duration > 0.0 && !has_status_effect_attribute {
+                    self.warning(
+                        path,
+                        format!("{}/duration", level_pointer),
+                        format!("skill has a duration of {}s but none of its attributes is a status effect", mechanics.duration),
+                    );
+                }
+
+                for (attribute_index, skill_attribute) in mechanics.attributes.iter().enumerate() {
+                    let attribute_pointer = format!("{}/attributes/{}", level_pointer, attribute_index);
+                    let attribute = &skill_attribute.attribute;
+
+                    self.check_chance(path, format!("{}/chance", attribute_pointer), skill_attribute.chance);
+                    self.check_embedded_attribute(path, &format!("{}/attribute", attribute_pointer), attribute);
+
+                    if attribute.target_mechanics != skill.target_mechanics {
+                        self.error(
+                            path,
+                            format!("{}/attribute/targetMechanics", attribute_pointer),
+                            format!(
+                                "attribute `{}` targets {} but the skill targets {}",
+                                attribute.name,
+                                describe_target_mechanics(&attribute.target_mechanics),
+                                describe_target_mechanics(&skill.target_mechanics),
+                            ),
+                        );
+                    }
+                }
+
+                if self.catalog.skill_level_requirement(mechanics.level).is_none() {
+                    self.warning(
+                        path,
+                        format!("{}/level", level_pointer),
+                        format!("skill level {} has no entry in {}", mechanics.level, SKILL_LEVEL_REQUIREMENT_FILE),
+                    );
+                }
+            }
+        }
+    }
+
+    fn validate_enemies(&mut self) {
+        let catalog = self.catalog;
+
+        self.check_unique_names(
+            "enemy",
+            catalog.enemies.iter().map(|enemy| (enemy.source.as_path(), "/name".to_string(), enemy.name.as_str())),
+        );
+
+        for enemy in &catalog.enemies {
+            let path = enemy.source.as_path();
+
+            self.check_levels(path, "/levelMechanics", enemy.level_mechanics.iter().map(|mechanics| mechanics.level));
+
+            for (level_index, mechanics) in enemy.level_mechanics.iter().enumerate() {
+                let level_pointer = format!("/levelMechanics/{}", level_index);
+
+                self.check_chance(path, format!("{}/critChance", level_pointer), mechanics.crit_chance);
+
+                if mechanics.skill.at_level(mechanics.level).is_none() {
+                    self.error(
+                        path,
+                        format!("{}/skill/levelMechanics", level_pointer),
+                        format!("enemy skill has no mechanics for level {}", mechanics.level),
+                    );
+                }
+
+                for (attribute_index, attribute) in mechanics.skill.attributes.iter().enumerate() {
+                    self.check_embedded_attribute(path, &format!("{}/skill/attributes/{}", level_pointer, attribute_index), attribute);
+                }
+
+                for (skill_level_index, skill_mechanics) in mechanics.skill.level_mechanics.iter().enumerate() {
+                    self.check_chance(
+                        path,
+                        format!("{}/skill/levelMechanics/{}/chance", level_pointer, skill_level_index),
+                        skill_mechanics.chance,
+                    );
+                }
+            }
+        }
+    }
+
+    fn validate_items(&mut self) {
+        let catalog = self.catalog;
+        let path = self.file(ITEMS_FILE);
+
+        self.check_unique_names(
+            "item",
+            catalog.items.iter().enumerate().map(|(index, item)| (path.as_path(), format!("/{}/name", index), item.name.as_str())),
+        );
+
+        for (item_index, item) in catalog.items.iter().enumerate() {
+            for (attribute_index, item_attribute) in item.attributes.iter().enumerate() {
+                let attribute_pointer = format!("/{}/attributes/{}", item_index, attribute_index);
+
+                self.check_chance(&path, format!("{}/chance", attribute_pointer), item_attribute.chance);
+                self.check_item_modifier(&path, &format!("{}/modifier", attribute_pointer), &item_attribute.modifier);
+            }
+        }
+    }
+
+    fn check_item_modifier(&mut self, path: &Path, pointer: &str, modifier: &ItemModifier) {
+        if let Some(stat_boosts) = &modifier.stat_boosts {
+            if stat_boosts.len() != modifier.values.len() {
+                self.error(
+                    path,
+                    format!("{}/values", pointer),
+                    format!("expected {} values (one per stat boost), got {}", stat_boosts.len(), modifier.values.len()),
+                );
+            }
+        }
+
+        if modifier.status_effects.is_some() && modifier.values.iter().any(|value| *value != 0.0) {
+            self.warning(
+                path,
+                format!("{}/values", pointer),
+                "values should be 0 when the modifier inflicts status effects".to_string(),
+            );
+        }
+    }
+
+    fn validate_quests(&mut self) {
+        let catalog = self.catalog;
+        let path = self.file(QUESTS_FILE);
+        let mut seen_quests = HashSet::new();
+
+        for (quest_index, quest) in catalog.quests.iter().enumerate() {
+            let quest_pointer = format!("/{}", quest_index);
+
+            if !seen_quests.insert((quest.chapter, quest.id)) {
+                self.error(
+                    &path,
+                    format!("{}/id", quest_pointer),
+                    format!("duplicate quest ID {} in chapter {} (`{}`)", quest.id, quest.chapter, quest.description),
+                );
+            }
+
+            if quest.quest_type.is_none() && quest.target().is_none() {
+                self.warning(
+                    &path,
+                    format!("{}/description", quest_pointer),
+                    format!("quest `{}` has no type and its description doesn't name an area to reach or an NPC to talk to", quest.description),
+                );
+            }
+
+            if let Some(quest_type) = &quest.quest_type {
+                for (index, required) in quest_type.items_required.iter().flatten().enumerate() {
+                    if catalog.item(&required.item).is_none() {
+                        self.error(
+                            &path,
+                            format!("{}/type/itemsRequired/{}/type", quest_pointer, index),
+                            format!("item `{}` doesn't exist", required.item),
+                        );
+                    }
+                }
+
+                for (index, required) in quest_type.enemies_required.iter().flatten().enumerate() {
+                    if catalog.enemy(&required.enemy).is_none() {
+                        self.error(
+                            &path,
+                            format!("{}/type/enemiesRequired/{}/type", quest_pointer, index),
+                            format!("enemy `{}` doesn't exist", required.enemy),
+                        );
+                    }
+                }
+            }
+
+            for (index, reward) in quest.completion_rewards.misc.iter().flatten().enumerate() {
+                let reward_pointer = format!("{}/completionRewards/misc/{}", quest_pointer, index);
+                let max_level = match reward.reward_type {
+                    MiscReward::Skill => catalog.skill(&reward.name).map(|skill| skill.max_level()),
+                    MiscReward::Weapon => catalog.weapon(&reward.name).map(|weapon| weapon.max_level()),
+                    _ => continue,
+                };
+
+                match max_level {
+                    None => self.error(
+                        &path,
+                        format!("{}/name", reward_pointer),
+                        format!("{:?} `{}` doesn't exist", reward.reward_type, reward.name),
+                    ),
+                    Some(max_level) if reward.level == 0 || reward.level > max_level => self.error(
+                        &path,
+                        format!("{}/level", reward_pointer),
+                        format!("{:?} `{}` has no level {} (max level is {})", reward.reward_type, reward.name, reward.level, max_level),
+                    ),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn validate_level_tables(&mut self) {
+        let catalog = self.catalog;
+        let player_levels_path = self.file(&format!("player/{}", PLAYER_LEVELS_FILE));
+        let weapon_requirements_path = self.file(&format!("weapons/{}", WEAPON_LEVEL_REQUIREMENT_FILE));
+        let skill_requirements_path = self.file(&format!("skills/{}", SKILL_LEVEL_REQUIREMENT_FILE));
+
+        self.check_levels(&player_levels_path, "", catalog.player_levels.iter().map(|stats| stats.level));
+        self.check_levels(&weapon_requirements_path, "", catalog.weapon_level_requirements.iter().map(|requirement| requirement.level));
+        self.check_levels(&skill_requirements_path, "", catalog.skill_level_requirements.iter().map(|requirement| requirement.level));
+
+        for (index, stats) in catalog.player_levels.iter().enumerate() {
+            if catalog.weapon_level_requirement(stats.max_weapon_level).is_none() {
+                self.error(
+                    &player_levels_path,
+                    format!("/{}/maxWeaponLevel", index),
+                    format!("weapon level {} has no entry in {}", stats.max_weapon_level, WEAPON_LEVEL_REQUIREMENT_FILE),
+                );
+            }
+
+            if catalog.skill_level_requirement(stats.max_skill_level).is_none() {
+                self.error(
+                    &player_levels_path,
+                    format!("/{}/maxSkillLevel", index),
+                    format!("skill level {} has no entry in {}", stats.max_skill_level, SKILL_LEVEL_REQUIREMENT_FILE),
+                );
+            }
+        }
+    }
+
+    fn validate_growth(&mut self) {
+        let catalog = self.catalog;
+        let Some(growth) = &catalog.growth else {
+            return;
+        };
+        let path = self.file(GROWTH_FILE);
+        let (level, level_and_hp) = (["level"], ["level", "hp"]);
+        let player = &growth.player;
+
+        self.check_formula(&path, "/player/hp".to_string(), &player.hp, &level);
+        self.check_formula(&path, "/player/hpRegen".to_string(), &player.hp_regen, &level_and_hp);
+        self.check_formula(&path, "/player/movementSpeed".to_string(), &player.movement_speed, &level_and_hp);
+        self.check_formula(&path, "/player/levelUpXpRequired".to_string(), &player.level_up_xp_required, &level_and_hp);
+
+        for (index, enemy) in growth.enemies.iter().enumerate() {
+            let stats = &enemy.stats;
+            let stat_formulas = [
+                ("hpRegen", &stats.hp_regen),
+                ("movementSpeed", &stats.movement_speed),
+                ("damage", &stats.damage),
+                ("projectileVelocity", &stats.projectile_velocity),
+                ("attackRange", &stats.attack_range),
+                ("attackTime", &stats.attack_time),
+                ("critChance", &stats.crit_chance),
+            ];
+
+            self.check_formula(&path, format!("/enemies/{}/stats/hp", index), &stats.hp, &level);
+
+            for (field, formula) in stat_formulas {
+                self.check_formula(&path, format!("/enemies/{}/stats/{}", index, field), formula, &level_and_hp);
+            }
+
+            self.check_formula(&path, format!("/enemies/{}/killRewards/coins", index), &enemy.kill_rewards.coins, &level);
+            self.check_formula(&path, format!("/enemies/{}/killRewards/xp", index), &enemy.kill_rewards.xp, &level);
+
+            if catalog.attribute(&enemy.skill.attribute).is_none() {
+                self.error(&path, format!("/enemies/{}/skill/attribute", index), format!("attribute `{}` doesn't exist", enemy.skill.attribute));
+            }
+
+            self.check_chance(&path, format!("/enemies/{}/skill/chance", index), enemy.skill.chance);
+        }
+
+        for (index, weapon) in growth.weapons.iter().enumerate() {
+            self.check_formula(&path, format!("/weapons/{}/damage", index), &weapon.damage, &level);
+            self.check_formula(&path, format!("/weapons/{}/attackTime", index), &weapon.attack_time, &level);
+            self.check_upgrade_cost_growth(&path, &format!("/weapons/{}/upgradeCost", index), &weapon.upgrade_cost);
+
+            if catalog.weapon(&weapon.name).is_none() {
+                self.error(&path, format!("/weapons/{}/name", index), format!("weapon `{}` doesn't exist", weapon.name));
+            }
+        }
+
+        for (index, skill) in growth.skills.iter().enumerate() {
+            if let Some(damage) = &skill.damage {
+                self.check_formula(&path, format!("/skills/{}/damage", index), damage, &level);
+            }
+
+            self.check_upgrade_cost_growth(&path, &format!("/skills/{}/upgradeCost", index), &skill.upgrade_cost);
+
+            if catalog.skill(&skill.name).is_none() {
+                self.error(&path, format!("/skills/{}/name", index), format!("skill `{}` doesn't exist", skill.name));
+            }
+        }
+    }
+
+    /// checks that every formula of an upgrade cost's growth only uses `level`
+    fn check_upgrade_cost_growth(&mut self, path: &Path, pointer_prefix: &str, growth: &UpgradeCostGrowth) {
+        self.check_formula(path, format!("{}/coins", pointer_prefix), &growth.coins, &["level"]);
+        self.check_formula(path, format!("{}/energyCores", pointer_prefix), &growth.energy_cores, &["level"]);
+
+        for (index, resource) in growth.resources.iter().enumerate() {
+            self.check_formula(path, format!("{}/resources/{}/amount", pointer_prefix, index), &resource.amount, &["level"]);
+        }
+    }
+
+    fn validate_breathable_areas(&mut self) {
+        let catalog = self.catalog;
+        let Some(breathable_areas) = &catalog.breathable_areas else {
+            return;
+        };
+        let path = self.file(BREATHABLE_AREAS_FILE);
+        let penalty = &breathable_areas.outside_penalty;
+
+        if penalty.grace_time < 0.0 {
+            self.error(&path, "/outsidePenalty/graceTime".to_string(), format!("grace time can't be negative, got {}", penalty.grace_time));
+        }
+
+        if !(0.0..=1.0).contains(&penalty.hp_ratio_per_second) {
+            self.error(
+                &path,
+                "/outsidePenalty/hpRatioPerSecond".to_string(),
+                format!("HP ratio must be from 0 to 1, got {}", penalty.hp_ratio_per_second),
+            );
+        }
+
+        self.check_unique_names(
+            "breathable area",
+            breathable_areas.areas.iter().enumerate().map(|(index, unlock)| (path.as_path(), format!("/areas/{}/area", index), unlock.area.as_str())),
+        );
+
+        for (index, unlock) in breathable_areas.areas.iter().enumerate() {
+            if let Some(UnlockRequirement::Quest { description }) = &unlock.unlocked_by {
+                if !catalog.quests.iter().any(|quest| &quest.description == description) {
+                    self.error(
+                        &path,
+                        format!("/areas/{}/unlockedBy/description", index),
+                        format!("no quest has the description `{}`", description),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// describes target mechanics in a human-readable way, e.g. `Enemy (count 3)`
+fn describe_target_mechanics(target_mechanics: &TargetMechanics) -> String {
+    format!("{:?} (count {})", target_mechanics.target_type, target_mechanics.count)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::{Stat, TargetType},
+        utils::{load_catalog, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    fn catalog() -> GameCatalog {
+        load_catalog(CATALOG_ROOT).unwrap()
+    }
+
+    /// gets the messages of the errors reported at `pointer`
+    fn errors_at(diagnostics: &[Diagnostic], pointer: &str) -> Vec<String> {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error && diagnostic.pointer == pointer)
+            .map(|diagnostic| diagnostic.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn shipped_catalog_has_no_errors() {
+        let diagnostics = validate_catalog(&catalog());
+
+        assert!(!has_errors(&diagnostics), "{:#?}", diagnostics);
+    }
+
+    #[test]
+    fn item_modifier_needs_one_value_per_stat_boost() {
+        let mut catalog = catalog();
+        let modifier = &mut catalog.items[0].attributes[0].modifier;
+        modifier.stat_boosts = Some(vec![Stat::HP, Stat::HPRegen]);
+        modifier.values = vec![10.0];
+
+        let errors = errors_at(&validate_catalog(&catalog), "/0/attributes/0/modifier/values");
+
+        assert_eq!(errors, ["expected 2 values (one per stat boost), got 1"]);
+    }
+
+    #[test]
+    fn chances_must_be_ratios() {
+        let mut catalog = catalog();
+        catalog.weapons[0].level_mechanics[0].crit_chance = 1.5;
+        catalog.items[0].attributes[0].chance = -0.1;
+        let diagnostics = validate_catalog(&catalog);
+
+        assert_eq!(errors_at(&diagnostics, "/levelMechanics/0/critChance"), ["chance must be a ratio from 0 to 1, got 1.5"]);
+        assert_eq!(errors_at(&diagnostics, "/0/attributes/0/chance"), ["chance must be a ratio from 0 to 1, got -0.1"]);
+    }
+
+    #[test]
+    fn skill_attributes_must_target_like_their_skill() {
+        let mut catalog = catalog();
+        let skill = catalog.skills.iter_mut().find(|skill| skill.name == "Fireball").unwrap();
+        skill.target_mechanics.target_type = TargetType::Radius;
+        let diagnostics = validate_catalog(&catalog);
+        let errors = errors_at(&diagnostics, "/levelMechanics/0/attributes/0/attribute/targetMechanics");
+
+        assert_eq!(errors, ["attribute `Ignite` targets Enemy (count 1) but the skill targets Radius (count 1)"]);
+    }
+
+    #[test]
+    fn quests_must_reference_existing_content() {
+        let mut catalog = catalog();
+        let quest_type = catalog.quests[2].quest_type.as_mut().unwrap();
+        quest_type.enemies_required.as_mut().unwrap()[0].enemy = "Dragon".to_string();
+        catalog.quests[1].quest_type.as_mut().unwrap().items_required.as_mut().unwrap()[0].item = "Mango".to_string();
+        catalog.quests[5].completion_rewards.misc.as_mut().unwrap()[0].name = "Meteor".to_string();
+        catalog.quests[4].description = "Reach Beacon One".to_string();
+        let diagnostics = validate_catalog(&catalog);
+
+        assert_eq!(errors_at(&diagnostics, "/2/type/enemiesRequired/0/type"), ["enemy `Dragon` doesn't exist"]);
+        assert_eq!(errors_at(&diagnostics, "/1/type/itemsRequired/0/type"), ["item `Mango` doesn't exist"]);
+        assert_eq!(errors_at(&diagnostics, "/5/completionRewards/misc/0/name"), ["Skill `Meteor` doesn't exist"]);
+        // the breathable area unlocked by "Reach Beacon 1" no longer has a quest
+        assert!(diagnostics.iter().any(|diagnostic| diagnostic.message == "no quest has the description `Reach Beacon 1`"));
+    }
+
+    #[test]
+    fn quest_ids_must_be_unique_within_a_chapter() {
+        let mut catalog = catalog();
+        let duplicate = catalog.quests[10].clone();
+        catalog.quests.insert(11, duplicate);
+
+        let errors = errors_at(&validate_catalog(&catalog), "/11/id");
+
+        assert_eq!(errors, ["duplicate quest ID 2 in chapter 3 (`Help Razer defeat the enemies around the storage (2 Golems)`)"]);
+    }
+}
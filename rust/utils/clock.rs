@@ -0,0 +1,86 @@
+use crate::models::GameTime;
+
+/// A source of game time.
+/// 
+/// Simulations and tests use `SimulatedClock` so that time can pass as fast as needed; live gameplay would implement it over wall-clock time.
+pub trait GameClock {
+    /// Gets the current game time.
+    fn now(&self) -> GameTime;
+}
+
+/// A clock whose time only moves when it's told to.
+/// 
+/// This is used to run gameplay logic thousands of times faster than real time, and to make it reproducible.
+#[derive(Clone, Debug, Default)]
+pub struct SimulatedClock {
+    /// the clock's current time
+    now: GameTime,
+}
+
+impl SimulatedClock {
+    /// Creates a clock that starts at `start`.
+    pub fn new(start: GameTime) -> Self {
+        SimulatedClock { now: start }
+    }
+
+    /// Moves the clock forward by `seconds` seconds.
+    pub fn advance(&mut self, seconds: f64) {
+        self.now = self.now + seconds;
+    }
+
+    /// Sets the clock's time to `time`.
+    pub fn set(&mut self, time: GameTime) {
+        self.now = time;
+    }
+}
+
+impl GameClock for SimulatedClock {
+    fn now(&self) -> GameTime {
+        self.now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::Point,
+        utils::{load_catalog, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    #[test]
+    fn time_only_moves_when_told_to() {
+        let mut clock = SimulatedClock::default();
+
+        assert_eq!(clock.now().as_seconds(), 0.0);
+
+        clock.advance(1.5);
+        clock.advance(2.0);
+        assert_eq!(clock.now().as_seconds(), 3.5);
+        assert_eq!(clock.now().seconds_since(GameTime::from_seconds(1.0)), 2.5);
+
+        clock.set(GameTime::from_seconds(100.0));
+        assert_eq!(clock.now().as_seconds(), 100.0);
+        assert_eq!(SimulatedClock::new(GameTime::from_seconds(7.0)).now().as_seconds(), 7.0);
+    }
+
+    #[test]
+    fn enemies_respawn_once_the_clock_passes_their_respawn_time() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut enemy = catalog.enemy("Golem").unwrap().at_level(1, Point { x: 0.0, y: 0.0 }, 30).unwrap();
+        let mut clock = SimulatedClock::new(GameTime::from_seconds(5.0));
+
+        enemy.die(clock.now());
+
+        clock.advance(29.5);
+        assert!(!enemy.can_respawn(clock.now()));
+
+        clock.advance(0.5);
+        assert!(enemy.can_respawn(clock.now()));
+
+        enemy.respawn();
+        assert!(!enemy.live_state.is_dead);
+        assert!(!enemy.can_respawn(clock.now()));
+    }
+}
@@ -0,0 +1,323 @@
+use crate::models::{
+    Attribute,
+    AttributeType,
+    EnemySkill,
+    SkillAttribute,
+    SkillModifier,
+    SkillModifierType,
+    StatusEffect,
+    WeaponAttribute,
+    WeaponAttributeModifier,
+    WeaponAttributeModifierType,
+};
+
+/// Represents a skill or weapon attribute's modifier, regardless of where it comes from.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EffectModifier {
+    /// a percentage (from 0 to 100) of a value that depends on the attribute type (e.g. the target's max HP for `EnemyHPDamage`)
+    Percentage(f64),
+    /// a flat amount
+    Number(f64),
+}
+
+impl EffectModifier {
+    /// Applies the modifier to `base`, i.e. gets the percentage of `base` for percentages or the flat amount for numbers.
+    pub fn apply_to(self, base: f64) -> f64 {
+        match self {
+            EffectModifier::Percentage(percentage) => base * percentage / 100.0,
+            EffectModifier::Number(amount) => amount,
+        }
+    }
+
+    /// Gets the modifier as a ratio from 0 to 1 (used by status effects that reduce something, e.g. Blind and Resilience).
+    pub fn as_ratio(self) -> f64 {
+        match self {
+            EffectModifier::Percentage(percentage) => percentage / 100.0,
+            EffectModifier::Number(ratio) => ratio,
+        }
+        .clamp(0.0, 1.0)
+    }
+}
+
+impl From<&SkillModifier> for EffectModifier {
+    fn from(modifier: &SkillModifier) -> Self {
+        match modifier.modifier_type {
+            SkillModifierType::Percentage => EffectModifier::Percentage(modifier.value),
+            SkillModifierType::Number => EffectModifier::Number(modifier.value),
+        }
+    }
+}
+
+impl From<&WeaponAttributeModifier> for EffectModifier {
+    fn from(modifier: &WeaponAttributeModifier) -> Self {
+        match modifier.modifier_type {
+            WeaponAttributeModifierType::Percentage => EffectModifier::Percentage(modifier.value),
+            WeaponAttributeModifierType::Number => EffectModifier::Number(modifier.value),
+        }
+    }
+}
+
+/// Represents the stats of a character that attribute effects are based on.
+#[derive(Clone, Copy, Debug)]
+pub struct EffectStats {
+    /// the character's max HP
+    pub max_hp: f64,
+    /// the damage of the character's attacks
+    pub damage: f64,
+}
+
+/// A list of concrete effects an attribute can produce on one of its targets.
+///
+/// `target` is the index of the affected target in the targets the attribute was resolved against.
+#[derive(Clone, PartialEq, Debug)]
+pub enum AttributeEffect {
+    /// the target takes `amount` damage
+    Damage { target: usize, amount: f64 },
+    /// the target heals `amount` HP
+    Healing { target: usize, amount: f64 },
+    /// the target is inflicted with `effect` (see `ActiveStatusEffects::apply` for `magnitude` and `duration`)
+    Status { target: usize, effect: StatusEffect, magnitude: f64, duration: Option<f64> },
+}
+
+/// Represents an attribute along with the level mechanics needed to resolve it into concrete effects.
+#[derive(Clone, Debug)]
+pub struct AttributeEffectSource {
+    /// the attribute
+    pub attribute: Attribute,
+    /// the attribute's modifier
+    pub modifier: EffectModifier,
+    /// the chance for the attribute to play out (in a ratio from 0 to 1)
+    pub chance: f64,
+    /// how long the status effects inflicted by the attribute last (in seconds), if its source specifies it
+    pub duration: Option<f64>,
+    /// the attribute's radius of effect (in units)
+    pub radius: f64,
+}
+
+impl AttributeEffectSource {
+    /// Creates the effect source of a weapon's attribute (weapons don't specify a duration).
+    pub fn from_weapon_attribute(weapon_attribute: &WeaponAttribute) -> Self {
+        AttributeEffectSource {
+            attribute: weapon_attribute.attribute.clone(),
+            modifier: EffectModifier::from(&weapon_attribute.modifier),
+            chance: weapon_attribute.chance,
+            duration: None,
+            radius: weapon_attribute.radius,
+        }
+    }
+
+    /// Creates the effect source of a skill's attribute, where `skill_duration` is the skill's `duration`.
+    pub fn from_skill_attribute(skill_attribute: &SkillAttribute, skill_duration: f64) -> Self {
+        AttributeEffectSource {
+            attribute: skill_attribute.attribute.clone(),
+            modifier: EffectModifier::from(&skill_attribute.modifier),
+            chance: skill_attribute.chance,
+            duration: Some(skill_duration).filter(|duration| *duration > 0.0),
+            radius: skill_attribute.radius,
+        }
+    }
+
+    /// Creates the effect sources of each of an enemy skill's attributes (which all share the skill's modifier, chance and duration).
+    pub fn from_enemy_skill(skill: &EnemySkill) -> Vec<Self> {
+        skill.attributes
+            .iter()
+            .map(|attribute| AttributeEffectSource {
+                attribute: attribute.clone(),
+                modifier: EffectModifier::from(&skill.modifier),
+                chance: skill.chance,
+                duration: Some(skill.duration).filter(|duration| *duration > 0.0),
+                radius: skill.radius,
+            })
+            .collect()
+    }
+
+    /// Resolves the attribute's effects on each of `targets` when `caster` uses it (regardless of its chance).
+    ///
+    /// Each attribute type applies the modifier to a different base value:
+    /// 1. `SetDamage`: the caster's damage.
+    /// 2. `OwnHPDamage`: the caster's max HP.
+    /// 3. `EnemyHPDamage`: the target's max HP.
+    /// 4. `SetHealing` and `HPHealing`: the target's max HP.
+    /// 5. `StatusEffect`: damage over time effects deal the modifier applied to the target's max HP per tick,
+    ///    Blind and Resilience use the modifier as a ratio, and a Stun without a duration lasts for the modifier's amount of seconds.
+    ///
+    /// Number modifiers are flat amounts, so the base value only matters for percentages.
+    pub fn resolve(&self, caster: &EffectStats, targets: &[EffectStats]) -> Vec<AttributeEffect> {
+        let mut effects = Vec::new();
+
+        for (target, stats) in targets.iter().enumerate() {
+            for attribute_type in &self.attribute.types {
+                match attribute_type {
+                    AttributeType::SetDamage => effects.push(AttributeEffect::Damage { target, amount: self.modifier.apply_to(caster.damage) }),
+                    AttributeType::OwnHPDamage => effects.push(AttributeEffect::Damage { target, amount: self.modifier.apply_to(caster.max_hp) }),
+                    AttributeType::EnemyHPDamage => effects.push(AttributeEffect::Damage { target, amount: self.modifier.apply_to(stats.max_hp) }),
+                    AttributeType::SetHealing | AttributeType::HPHealing => {
+                        effects.push(AttributeEffect::Healing { target, amount: self.modifier.apply_to(stats.max_hp) })
+                    }
+                    AttributeType::StatusEffect => effects.extend(self.status_effects(target, stats)),
+                }
+            }
+        }
+
+        effects
+    }
+
+    /// resolves the status effects inflicted on a single target
+    fn status_effects(&self, target: usize, stats: &EffectStats) -> Vec<AttributeEffect> {
+        self.attribute.status_effects
+            .iter()
+            .flatten()
+            .filter(|effect| **effect != StatusEffect::None)
+            .map(|&effect| {
+                let (magnitude, duration) = match (effect, self.modifier) {
+                    (StatusEffect::Stun, EffectModifier::Number(seconds)) => (0.0, self.duration.or(Some(seconds))),
+                    (StatusEffect::Stun, _) => (0.0, self.duration),
+                    (StatusEffect::Blind | StatusEffect::Resilience, modifier) => (modifier.as_ratio(), self.duration),
+                    (_, modifier) => (modifier.apply_to(stats.max_hp), self.duration),
+                };
+
+                AttributeEffect::Status { target, effect, magnitude, duration }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::{Point, TargetMechanics, TargetType},
+        utils::{load_catalog, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    const CASTER: EffectStats = EffectStats { max_hp: 400.0, damage: 20.0 };
+    const TARGETS: [EffectStats; 2] = [EffectStats { max_hp: 200.0, damage: 10.0 }, EffectStats { max_hp: 1000.0, damage: 0.0 }];
+
+    fn source(attribute_type: AttributeType, status_effects: &[StatusEffect], modifier: EffectModifier, duration: Option<f64>) -> AttributeEffectSource {
+        AttributeEffectSource {
+            attribute: Attribute {
+                name: "Test".to_string(),
+                description: String::new(),
+                types: vec![attribute_type],
+                target_mechanics: TargetMechanics { target_type: TargetType::Enemy, count: 2 },
+                status_effects: Some(status_effects.to_vec()),
+            },
+            modifier,
+            chance: 1.0,
+            duration,
+            radius: 0.0,
+        }
+    }
+
+    /// resolves an attribute of a single type against both targets
+    fn resolve(attribute_type: AttributeType, modifier: EffectModifier) -> Vec<AttributeEffect> {
+        source(attribute_type, &[], modifier, None).resolve(&CASTER, &TARGETS)
+    }
+
+    fn damage(amounts: [f64; 2]) -> Vec<AttributeEffect> {
+        vec![AttributeEffect::Damage { target: 0, amount: amounts[0] }, AttributeEffect::Damage { target: 1, amount: amounts[1] }]
+    }
+
+    fn healing(amounts: [f64; 2]) -> Vec<AttributeEffect> {
+        vec![AttributeEffect::Healing { target: 0, amount: amounts[0] }, AttributeEffect::Healing { target: 1, amount: amounts[1] }]
+    }
+
+    #[test]
+    fn damage_types_apply_percentages_to_their_own_base() {
+        // the caster's damage, the caster's max HP and each target's max HP
+        assert_eq!(resolve(AttributeType::SetDamage, EffectModifier::Percentage(50.0)), damage([10.0, 10.0]));
+        assert_eq!(resolve(AttributeType::OwnHPDamage, EffectModifier::Percentage(10.0)), damage([40.0, 40.0]));
+        assert_eq!(resolve(AttributeType::EnemyHPDamage, EffectModifier::Percentage(7.5)), damage([15.0, 75.0]));
+    }
+
+    #[test]
+    fn damage_types_deal_numbers_as_flat_amounts() {
+        assert_eq!(resolve(AttributeType::SetDamage, EffectModifier::Number(35.0)), damage([35.0, 35.0]));
+        assert_eq!(resolve(AttributeType::OwnHPDamage, EffectModifier::Number(12.0)), damage([12.0, 12.0]));
+        assert_eq!(resolve(AttributeType::EnemyHPDamage, EffectModifier::Number(8.0)), damage([8.0, 8.0]));
+    }
+
+    #[test]
+    fn healing_types_heal_a_share_of_the_target_max_hp_or_a_flat_amount() {
+        for attribute_type in [AttributeType::SetHealing, AttributeType::HPHealing] {
+            assert_eq!(resolve(attribute_type, EffectModifier::Percentage(10.0)), healing([20.0, 100.0]));
+            assert_eq!(resolve(attribute_type, EffectModifier::Number(65.0)), healing([65.0, 65.0]));
+        }
+    }
+
+    #[test]
+    fn damage_over_time_ticks_a_share_of_the_target_max_hp_or_a_flat_amount() {
+        let burn = |modifier| source(AttributeType::StatusEffect, &[StatusEffect::Burn, StatusEffect::None], modifier, Some(3.0)).resolve(&CASTER, &TARGETS);
+        let status = |target, magnitude| AttributeEffect::Status { target, effect: StatusEffect::Burn, magnitude, duration: Some(3.0) };
+
+        // `None` isn't inflicted
+        assert_eq!(burn(EffectModifier::Percentage(1.0)), vec![status(0, 2.0), status(1, 10.0)]);
+        assert_eq!(burn(EffectModifier::Number(1.5)), vec![status(0, 1.5), status(1, 1.5)]);
+    }
+
+    #[test]
+    fn blind_and_resilience_use_the_modifier_as_a_ratio() {
+        for effect in [StatusEffect::Blind, StatusEffect::Resilience] {
+            for (modifier, ratio) in [(EffectModifier::Percentage(30.0), 0.3), (EffectModifier::Number(0.4), 0.4)] {
+                let effects = source(AttributeType::StatusEffect, &[effect], modifier, Some(5.0)).resolve(&CASTER, &TARGETS[..1]);
+
+                assert_eq!(effects, vec![AttributeEffect::Status { target: 0, effect, magnitude: ratio, duration: Some(5.0) }]);
+            }
+        }
+    }
+
+    #[test]
+    fn ratios_are_clamped_between_0_and_1() {
+        assert_eq!(EffectModifier::Percentage(150.0).as_ratio(), 1.0);
+        assert_eq!(EffectModifier::Percentage(-5.0).as_ratio(), 0.0);
+        assert_eq!(EffectModifier::Percentage(25.0).as_ratio(), 0.25);
+        assert_eq!(EffectModifier::Number(1.5).as_ratio(), 1.0);
+        assert_eq!(EffectModifier::Number(-0.5).as_ratio(), 0.0);
+        assert_eq!(EffectModifier::Number(0.25).as_ratio(), 0.25);
+    }
+
+    #[test]
+    fn stun_without_a_duration_lasts_for_its_number_modifier() {
+        let stun = |modifier, duration| source(AttributeType::StatusEffect, &[StatusEffect::Stun], modifier, duration).resolve(&CASTER, &TARGETS[..1]);
+        let status = |duration| vec![AttributeEffect::Status { target: 0, effect: StatusEffect::Stun, magnitude: 0.0, duration }];
+
+        assert_eq!(stun(EffectModifier::Number(3.0), None), status(Some(3.0)));
+        // the source's duration wins over the modifier
+        assert_eq!(stun(EffectModifier::Number(3.0), Some(2.0)), status(Some(2.0)));
+        // a percentage can't be a number of seconds
+        assert_eq!(stun(EffectModifier::Percentage(3.0), None), status(None));
+    }
+
+    #[test]
+    fn sword_slam_stuns_for_its_modifier_since_it_has_no_duration() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let skill = catalog.skill("Sword Slam").unwrap().at_level(1).unwrap();
+        let source = AttributeEffectSource::from_skill_attribute(&skill.attributes[0], skill.duration);
+
+        assert_eq!(source.duration, None);
+        assert_eq!(source.resolve(&CASTER, &TARGETS[..1]), vec![AttributeEffect::Status { target: 0, effect: StatusEffect::Stun, magnitude: 0.0, duration: Some(3.0) }]);
+    }
+
+    #[test]
+    fn golem_ground_smash_deals_hp_based_damage_and_stuns() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let golem = catalog.enemy("Golem").unwrap().at_level(1, Point { x: 0.0, y: 0.0 }, 0).unwrap();
+        let player_hp = catalog.player_level(1).unwrap().base_hp;
+        let sources = AttributeEffectSource::from_enemy_skill(&golem.skill);
+
+        assert_eq!(sources.len(), 1);
+        assert_eq!(Some(&sources[0].attribute), catalog.attribute("Ground Smash"));
+        assert_eq!(sources[0].modifier, EffectModifier::Percentage(7.5));
+        assert_eq!(sources[0].chance, 0.125);
+        assert_eq!(sources[0].radius, 500.0);
+
+        let caster = EffectStats { max_hp: golem.base_hp, damage: golem.base_damage };
+        let target = EffectStats { max_hp: player_hp, damage: 0.0 };
+
+        assert_eq!(sources[0].resolve(&caster, &[target]), vec![
+            AttributeEffect::Damage { target: 0, amount: player_hp * 0.075 },
+            AttributeEffect::Status { target: 0, effect: StatusEffect::Stun, magnitude: 0.0, duration: Some(2.0) },
+        ]);
+    }
+}
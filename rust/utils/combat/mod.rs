@@ -0,0 +1,9 @@
+pub mod timeline;
+pub mod effect;
+pub mod targetting;
+pub mod simulation;
+
+pub use timeline::*;
+pub use effect::*;
+pub use targetting::*;
+pub use simulation::*;
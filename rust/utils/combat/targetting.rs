@@ -0,0 +1,213 @@
+use crate::models::{Point, TargetMechanics, TargetType};
+
+/// A list of relations a target candidate can have with the caster.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TargetSide {
+    /// the caster itself
+    Caster,
+    /// someone on the caster's team
+    Ally,
+    /// someone on the opposing team
+    Enemy,
+}
+
+/// Represents an entity that can be targeted by an attribute.
+///
+/// Only entities that can actually be targeted (e.g. alive ones) should be given as candidates.
+#[derive(Clone, Copy, Debug)]
+pub struct TargetCandidate {
+    /// where the entity currently is
+    pub position: Point,
+    /// the entity's relation with the caster
+    pub side: TargetSide,
+}
+
+/// Represents the settings of a target selection that don't come from the attribute itself.
+#[derive(Clone, Copy, Debug)]
+pub struct TargetingOptions {
+    /// how far (in units) from the caster the first target can be (e.g. a skill's `range`)
+    pub range: f64,
+    /// the center of an area of effect (the "area of choice" of `Radius` and `All` target types; defaults to the caster's position)
+    pub center: Option<Point>,
+    /// the index of the candidate to target first if it's a valid target (e.g. the caster's current attack target)
+    pub focus: Option<usize>,
+    /// how far (in units) a chain can jump from one target to the next
+    pub chain_range: f64,
+}
+
+impl Default for TargetingOptions {
+    fn default() -> Self {
+        TargetingOptions {
+            range: f64::INFINITY,
+            center: None,
+            focus: None,
+            chain_range: 300.0,
+        }
+    }
+}
+
+/// Selects the candidates affected by an attribute with `mechanics` and `radius` that's used by a caster at `caster`.
+///
+/// Returns the indices of the affected candidates, in the order they're hit. A `count` of 0 means there's no limit (or a single target for
+/// single target types). Each target type selects its targets as follows:
+/// 1. `Self`: the caster and its nearest allies (`count`), or everyone on the caster's team within `radius` of the caster.
+/// 2. `Enemy`: the focused (or nearest) enemy within range. Extra targets (`count`) are chained to, each being the nearest enemy
+///    within `options.chain_range` of the previous one, unless the attribute has a radius, in which case the nearest enemies within `radius` of the first target are hit instead.
+/// 3. `Radius`: every enemy within `radius` of the center (nearest first).
+/// 4. `Both`: the focused (or nearest) candidates within range regardless of their side, or everyone within `radius` of the center.
+/// 5. `All`: everyone within `radius` of the center, or everyone within range if the attribute has no radius.
+pub fn select_targets(caster: Point, candidates: &[TargetCandidate], mechanics: &TargetMechanics, radius: f64, options: &TargetingOptions) -> Vec<usize> {
+    let count = usize::from(mechanics.count);
+    let center = options.center.unwrap_or(caster);
+    let is_enemy = |candidate: &TargetCandidate| candidate.side == TargetSide::Enemy;
+    let is_anyone = |_: &TargetCandidate| true;
+
+    let targets = match mechanics.target_type {
+        TargetType::Ally if radius > 0.0 => ranked(caster, candidates, |candidate| !is_enemy(candidate), radius, None),
+        TargetType::Ally => ranked(caster, candidates, |candidate| !is_enemy(candidate), options.range, None).into_iter().take(count.max(1)).collect(),
+        TargetType::Enemy => {
+            let Some(&first) = ranked(caster, candidates, is_enemy, options.range, options.focus).first() else {
+                return Vec::new();
+            };
+
+            if radius > 0.0 {
+                ranked(candidates[first].position, candidates, is_enemy, radius, Some(first))
+            } else {
+                chain(first, candidates, count.max(1), options.chain_range)
+            }
+        }
+        TargetType::Radius => ranked(center, candidates, is_enemy, radius, None),
+        TargetType::Both if radius > 0.0 => ranked(center, candidates, is_anyone, radius, None),
+        TargetType::Both => ranked(caster, candidates, is_anyone, options.range, options.focus).into_iter().take(count.max(1)).collect(),
+        TargetType::All if radius > 0.0 => ranked(center, candidates, is_anyone, radius, None),
+        TargetType::All => ranked(caster, candidates, is_anyone, options.range, None),
+    };
+
+    match count {
+        0 => targets,
+        count => targets.into_iter().take(count).collect(),
+    }
+}
+
+/// gets the indices of every allowed candidate within `max_distance` of `from`, nearest first (and `focus` first if it's one of them)
+fn ranked(from: Point, candidates: &[TargetCandidate], allowed: impl Fn(&TargetCandidate) -> bool, max_distance: f64, focus: Option<usize>) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..candidates.len())
+        .filter(|&index| allowed(&candidates[index]) && from.distance_to(&candidates[index].position) <= max_distance)
+        .collect();
+
+    // the sort is stable, so candidates at the same distance keep their order
+    indices.sort_by(|&a, &b| from.distance_to(&candidates[a].position).total_cmp(&from.distance_to(&candidates[b].position)));
+
+    if let Some(position) = focus.and_then(|focus| indices.iter().position(|&index| index == focus)) {
+        let focus = indices.remove(position);
+        indices.insert(0, focus);
+    }
+
+    indices
+}
+
+/// chains from `first` to the nearest enemy that hasn't been hit yet within `chain_range` of the last target, until `count` enemies are hit
+fn chain(first: usize, candidates: &[TargetCandidate], count: usize, chain_range: f64) -> Vec<usize> {
+    let mut targets = vec![first];
+
+    while targets.len() < count {
+        let last = candidates[targets[targets.len() - 1]].position;
+        let next = ranked(last, candidates, |candidate| candidate.side == TargetSide::Enemy, chain_range, None)
+            .into_iter()
+            .find(|index| !targets.contains(index));
+
+        match next {
+            Some(next) => targets.push(next),
+            None => break,
+        }
+    }
+
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mechanics(target_type: TargetType, count: u8) -> TargetMechanics {
+        TargetMechanics { target_type, count }
+    }
+
+    fn candidate(x: f64, side: TargetSide) -> TargetCandidate {
+        TargetCandidate { position: Point { x, y: 0.0 }, side }
+    }
+
+    /// the caster at 0, an ally at 50 and enemies at 100, 200 and 1000
+    fn battlefield() -> Vec<TargetCandidate> {
+        vec![
+            candidate(0.0, TargetSide::Caster),
+            candidate(50.0, TargetSide::Ally),
+            candidate(1000.0, TargetSide::Enemy),
+            candidate(200.0, TargetSide::Enemy),
+            candidate(100.0, TargetSide::Enemy),
+        ]
+    }
+
+    fn select(mechanics: &TargetMechanics, radius: f64, options: &TargetingOptions) -> Vec<usize> {
+        select_targets(Point { x: 0.0, y: 0.0 }, &battlefield(), mechanics, radius, options)
+    }
+
+    #[test]
+    fn enemy_targets_the_nearest_enemy() {
+        assert_eq!(select(&mechanics(TargetType::Enemy, 1), 0.0, &TargetingOptions::default()), vec![4]);
+    }
+
+    #[test]
+    fn enemy_targets_the_focus_first_even_if_it_is_farther() {
+        let options = TargetingOptions { focus: Some(3), ..TargetingOptions::default() };
+
+        assert_eq!(select(&mechanics(TargetType::Enemy, 1), 0.0, &options), vec![3]);
+    }
+
+    #[test]
+    fn enemy_ignores_a_focus_that_isnt_an_enemy() {
+        let options = TargetingOptions { focus: Some(1), ..TargetingOptions::default() };
+
+        assert_eq!(select(&mechanics(TargetType::Enemy, 1), 0.0, &options), vec![4]);
+    }
+
+    #[test]
+    fn enemy_chains_within_the_chain_range() {
+        // the enemy at 1000 is too far from the enemy at 200 to be chained to
+        assert_eq!(select(&mechanics(TargetType::Enemy, 3), 0.0, &TargetingOptions::default()), vec![4, 3]);
+    }
+
+    #[test]
+    fn enemy_with_a_radius_splashes_around_the_first_target() {
+        assert_eq!(select(&mechanics(TargetType::Enemy, 0), 150.0, &TargetingOptions::default()), vec![4, 3]);
+    }
+
+    #[test]
+    fn nothing_is_targeted_out_of_range() {
+        let options = TargetingOptions { range: 80.0, ..TargetingOptions::default() };
+
+        assert!(select(&mechanics(TargetType::Enemy, 1), 0.0, &options).is_empty());
+    }
+
+    #[test]
+    fn ally_targets_the_caster_and_its_team() {
+        assert_eq!(select(&mechanics(TargetType::Ally, 0), 0.0, &TargetingOptions::default()), vec![0]);
+        assert_eq!(select(&mechanics(TargetType::Ally, 2), 0.0, &TargetingOptions::default()), vec![0, 1]);
+        assert_eq!(select(&mechanics(TargetType::Ally, 0), 60.0, &TargetingOptions::default()), vec![0, 1]);
+    }
+
+    #[test]
+    fn radius_targets_every_enemy_around_the_center() {
+        let options = TargetingOptions { center: Some(Point { x: 140.0, y: 0.0 }), ..TargetingOptions::default() };
+
+        assert_eq!(select(&mechanics(TargetType::Radius, 0), 60.0, &options), vec![4, 3]);
+        assert_eq!(select(&mechanics(TargetType::Radius, 1), 60.0, &options), vec![4]);
+    }
+
+    #[test]
+    fn both_and_all_target_either_side() {
+        assert_eq!(select(&mechanics(TargetType::Both, 2), 0.0, &TargetingOptions::default()), vec![0, 1]);
+        assert_eq!(select(&mechanics(TargetType::All, 0), 120.0, &TargetingOptions::default()), vec![0, 1, 4]);
+        assert_eq!(select(&mechanics(TargetType::All, 0), 0.0, &TargetingOptions::default()), vec![0, 1, 4, 3, 2]);
+    }
+}
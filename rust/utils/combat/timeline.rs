@@ -0,0 +1,157 @@
+use serde::Serialize;
+
+use crate::models::StatusEffect;
+
+/// Identifies a participant of a fight.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Debug)]
+pub enum Combatant {
+    /// the player
+    Player,
+    /// the enemy at this index of the fight's enemies
+    Enemy(usize),
+}
+
+/// Represents something that happened during a fight, `time` seconds after it started.
+#[derive(Clone, Serialize, Debug)]
+pub struct CombatEvent {
+    /// when the event happened (in seconds since the start of the fight)
+    pub time: f64,
+    /// what happened
+    pub kind: CombatEventKind,
+}
+
+/// A list of everything that can happen during a fight.
+#[derive(Clone, Serialize, Debug)]
+#[serde(tag = "type")]
+pub enum CombatEventKind {
+    /// `attacker` hit `target` for `damage`, leaving it with `target_hp` HP
+    Attack {
+        attacker: Combatant,
+        target: Combatant,
+        damage: f64,
+        /// whether the attack was a critical hit
+        crit: bool,
+        target_hp: f64,
+    },
+    /// `caster` cast its skill named `name`
+    SkillCast {
+        caster: Combatant,
+        name: String,
+    },
+    /// an attribute or skill named `name` of `source` procced (i.e. its chance rolled successfully) on `target`
+    Proc {
+        source: Combatant,
+        target: Combatant,
+        name: String,
+    },
+    /// an attribute named `name` of `source` dealt `damage` to `target`, leaving it with `target_hp` HP
+    EffectDamage {
+        source: Combatant,
+        target: Combatant,
+        name: String,
+        damage: f64,
+        target_hp: f64,
+    },
+    /// an attribute named `name` of `source` healed `target` for `amount` HP, leaving it with `target_hp` HP
+    Heal {
+        source: Combatant,
+        target: Combatant,
+        name: String,
+        amount: f64,
+        target_hp: f64,
+    },
+    /// `source` inflicted `effect` on `target`
+    StatusApplied {
+        source: Combatant,
+        target: Combatant,
+        effect: StatusEffect,
+    },
+    /// a damage over time `effect` on `combatant` dealt `damage`, leaving it with `hp` HP
+    StatusTick {
+        combatant: Combatant,
+        effect: StatusEffect,
+        damage: f64,
+        hp: f64,
+    },
+    /// `effect` on `combatant` wore off
+    StatusExpired {
+        combatant: Combatant,
+        effect: StatusEffect,
+    },
+    /// `attacker`'s attack on `target` missed (due to Blind)
+    Miss {
+        attacker: Combatant,
+        target: Combatant,
+    },
+    /// `combatant` regenerated `amount` HP, leaving it with `hp` HP
+    Regen {
+        combatant: Combatant,
+        amount: f64,
+        hp: f64,
+    },
+    /// `combatant` was defeated
+    Death {
+        combatant: Combatant,
+    },
+}
+
+/// How a fight ended.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Debug)]
+pub enum CombatOutcome {
+    /// the player defeated every enemy
+    PlayerWon,
+    /// the player was defeated
+    PlayerDefeated,
+    /// neither side was defeated before the simulation's max duration
+    TimedOut,
+}
+
+/// Represents the result of a simulated fight.
+#[derive(Clone, Serialize, Debug)]
+pub struct CombatReport {
+    /// how the fight ended
+    pub outcome: CombatOutcome,
+    /// how long the fight lasted (in seconds)
+    pub duration: f64,
+    /// the player's HP at the end of the fight
+    pub player_hp: f64,
+    /// each enemy's HP at the end of the fight (in the same order as the fight's enemies)
+    pub enemy_hp: Vec<f64>,
+    /// everything that happened during the fight, in chronological order
+    pub timeline: Vec<CombatEvent>,
+}
+
+impl CombatReport {
+    /// Gets the total damage dealt by `attacker` throughout the fight, through attacks and attribute effects (damage over time isn't included).
+    pub fn damage_dealt_by(&self, attacker: Combatant) -> f64 {
+        self.timeline
+            .iter()
+            .map(|event| match event.kind {
+                CombatEventKind::Attack { attacker: event_attacker, damage, .. } if event_attacker == attacker => damage,
+                CombatEventKind::EffectDamage { source, damage, .. } if source == attacker => damage,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    /// Gets the total damage taken by `target` throughout the fight, through attacks, attribute effects and damage over time.
+    pub fn damage_taken_by(&self, target: Combatant) -> f64 {
+        self.timeline
+            .iter()
+            .map(|event| match event.kind {
+                CombatEventKind::Attack { target: event_target, damage, .. } if event_target == target => damage,
+                CombatEventKind::EffectDamage { target: event_target, damage, .. } if event_target == target => damage,
+                CombatEventKind::StatusTick { combatant, damage, .. } if combatant == target => damage,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    /// Gets the time (in seconds since the start of the fight) at which `combatant` was defeated (if it was).
+    pub fn death_time(&self, combatant: Combatant) -> Option<f64> {
+        self.timeline.iter().find_map(|event| match event.kind {
+            CombatEventKind::Death { combatant: dead } if dead == combatant => Some(event.time),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{
+    models::{Cost, Currency, UpgradeCost},
+    utils::GameCatalog,
+};
+
+use super::UpgradeKind;
+
+/// Represents the sum of one or more costs.
+#[derive(Clone, Default, Serialize, Debug)]
+#[serde(transparent)]
+pub struct CostTotals {
+    /// the amount of each currency (currencies that aren't required are left out)
+    pub amounts: BTreeMap<Currency, u64>,
+}
+
+impl CostTotals {
+    /// Adds `cost` to the totals.
+    pub fn add(&mut self, cost: &impl Cost) {
+        for (currency, amount) in cost.amounts() {
+            *self.amounts.entry(currency).or_default() += u64::from(amount);
+        }
+    }
+
+    /// Gets the amount of `currency` (0 if it isn't required).
+    pub fn amount(&self, currency: Currency) -> u64 {
+        self.amounts.get(&currency).copied().unwrap_or_default()
+    }
+}
+
+/// Represents upgrading a weapon or skill from the previous level to `level`.
+#[derive(Clone, Serialize, Debug)]
+pub struct UpgradeCostStep {
+    /// the level being upgraded to
+    pub level: u8,
+    /// the minimum player level required for this level (`None` if it has no entry in the level requirement table)
+    pub min_player_level_required: Option<u8>,
+    /// what upgrading to this level costs
+    pub cost: CostTotals,
+    /// what upgrading from level 1 up to this level costs in total
+    pub cumulative: CostTotals,
+}
+
+/// Represents what it costs to upgrade a weapon or skill from level 1 to its max level.
+#[derive(Clone, Serialize, Debug)]
+pub struct UpgradeCostReport {
+    /// whether this is a weapon or a skill
+    pub kind: UpgradeKind,
+    /// the name of the weapon or skill
+    pub name: String,
+    /// the highest level the weapon or skill can be upgraded to
+    pub max_level: u8,
+    /// every upgrade from level 1 to `max_level`
+    pub steps: Vec<UpgradeCostStep>,
+    /// what upgrading from level 1 to `max_level` costs in total
+    pub to_max: CostTotals,
+}
+
+impl UpgradeCostReport {
+    /// Gets the minimum player level required to reach `max_level` (`None` if any level has no entry in the level requirement table).
+    pub fn max_player_level_required(&self) -> Option<u8> {
+        let levels: Option<Vec<u8>> = self.steps.iter().map(|step| step.min_player_level_required).collect();
+
+        levels.map(|levels| levels.into_iter().max().unwrap_or(1))
+    }
+}
+
+/// Reports what it costs to upgrade every weapon and skill within `catalog` from level 1 to its max level, level by level.
+pub fn upgrade_cost_reports(catalog: &GameCatalog) -> Vec<UpgradeCostReport> {
+    let weapons = catalog.weapons.iter().map(|weapon| {
+        let levels = weapon.level_mechanics
+            .iter()
+            .map(|mechanics| (mechanics.level, &mechanics.upgrade_cost, catalog.weapon_level_requirement(mechanics.level).map(|requirement| requirement.min_player_level_required)));
+
+        cost_report(UpgradeKind::Weapon, &weapon.name, levels)
+    });
+    let skills = catalog.skills.iter().map(|skill| {
+        let levels = skill.level_mechanics
+            .iter()
+            .map(|mechanics| (mechanics.level, &mechanics.upgrade_cost, catalog.skill_level_requirement(mechanics.level).map(|requirement| requirement.min_player_level_required)));
+
+        cost_report(UpgradeKind::Skill, &skill.name, levels)
+    });
+
+    weapons.chain(skills).collect()
+}
+
+/// Formats `reports` as a CSV table, with one row per weapon or skill and what it costs to upgrade it from level 1 to its max level.
+pub fn upgrade_cost_csv(reports: &[UpgradeCostReport]) -> String {
+    let mut csv = String::from("kind,name,max_level,max_player_level_required,coins,energy_cores,wood,stone,coal\n");
+
+    for report in reports {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            report.kind,
+            report.name,
+            report.max_level,
+            report.max_player_level_required().map(|level| level.to_string()).unwrap_or_default(),
+            report.to_max.amount(Currency::Coins),
+            report.to_max.amount(Currency::EnergyCores),
+            report.to_max.amount(Currency::Wood),
+            report.to_max.amount(Currency::Stone),
+            report.to_max.amount(Currency::Coal),
+        ));
+    }
+
+    csv
+}
+
+/// builds the report of a weapon or skill out of its `(level, upgrade cost, min player level required)` levels, skipping level 1 (which is never upgraded to)
+fn cost_report<'a>(kind: UpgradeKind, name: &str, levels: impl Iterator<Item = (u8, &'a UpgradeCost, Option<u8>)>) -> UpgradeCostReport {
+    let mut cumulative = CostTotals::default();
+    let mut steps = Vec::new();
+
+    for (level, upgrade_cost, min_player_level_required) in levels.filter(|&(level, _, _)| level > 1) {
+        let mut cost = CostTotals::default();
+        cost.add(upgrade_cost);
+        cumulative.add(upgrade_cost);
+
+        steps.push(UpgradeCostStep { level, min_player_level_required, cost, cumulative: cumulative.clone() });
+    }
+
+    UpgradeCostReport {
+        kind,
+        name: name.to_string(),
+        max_level: steps.last().map_or(1, |step| step.level),
+        steps,
+        to_max: cumulative,
+    }
+}
@@ -0,0 +1,7 @@
+pub mod upgrade;
+pub mod cost_report;
+pub mod shop;
+
+pub use upgrade::*;
+pub use cost_report::*;
+pub use shop::*;
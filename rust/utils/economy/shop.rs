@@ -0,0 +1,275 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    models::{InsufficientFunds, LedgerReason, Player, PurchaseCost},
+    utils::{EventBus, GameCatalog, GameEvent},
+};
+
+use super::UpgradeKind;
+
+/// Represents a weapon or skill the shop sells (always at level 1).
+#[derive(Clone, Serialize, Debug)]
+pub struct ShopEntry {
+    /// whether a weapon or a skill is sold
+    pub kind: UpgradeKind,
+    /// the name of the weapon or skill
+    pub name: String,
+    /// what the weapon or skill costs
+    pub cost: PurchaseCost,
+    /// the minimum player level required for level 1 (`None` if it has no entry in the level requirement table)
+    pub min_player_level_required: Option<u8>,
+}
+
+impl ShopEntry {
+    /// Checks whether `player` can purchase the weapon or skill.
+    ///
+    /// The checks are made in the order they are reported: already owned, player level too low, and then insufficient funds.
+    pub fn check(&self, player: &Player) -> Result<(), PurchaseError> {
+        let owned = match self.kind {
+            UpgradeKind::Weapon => player.inventory.weapons.iter().any(|weapon| weapon.name == self.name),
+            UpgradeKind::Skill => player.inventory.skills.iter().any(|skill| skill.name == self.name),
+        };
+
+        if owned {
+            return Err(PurchaseError::AlreadyOwned { kind: self.kind, name: self.name.clone() });
+        }
+
+        let player_level = player.player_level_mechanics.current_level;
+
+        if let Some(required_player_level) = self.min_player_level_required.filter(|&required| player_level < required) {
+            return Err(PurchaseError::LevelRequirementNotMet { kind: self.kind, name: self.name.clone(), required_player_level, player_level });
+        }
+
+        let shortfalls = player.wallet.shortfalls(&self.cost);
+
+        if !shortfalls.is_empty() {
+            return Err(PurchaseError::InsufficientFunds(InsufficientFunds { shortfalls }));
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether `player`'s wallet can pay for the weapon or skill (regardless of whether it could be purchased otherwise).
+    pub fn is_affordable(&self, player: &Player) -> bool {
+        player.wallet.can_afford(&self.cost)
+    }
+}
+
+/// Represents a weapon or skill that was purchased.
+#[derive(Clone, Debug)]
+pub struct Purchase {
+    /// whether a weapon or a skill was purchased
+    pub kind: UpgradeKind,
+    /// the name of the weapon or skill
+    pub name: String,
+    /// what the purchase cost
+    pub cost: PurchaseCost,
+}
+
+/// Represents why a weapon or skill couldn't be purchased.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PurchaseError {
+    /// the shop doesn't sell the weapon or skill (i.e. it doesn't exist in the catalog or has no level 1)
+    NotSold { kind: UpgradeKind, name: String },
+    /// the weapon or skill is already in the player's inventory
+    AlreadyOwned { kind: UpgradeKind, name: String },
+    /// the player's level is below the minimum player level required for level 1
+    LevelRequirementNotMet { kind: UpgradeKind, name: String, required_player_level: u8, player_level: u8 },
+    /// the player can't pay the purchase cost
+    InsufficientFunds(InsufficientFunds),
+}
+
+impl fmt::Display for PurchaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PurchaseError::NotSold { kind, name } => write!(f, "{} `{}` isn't sold", kind, name),
+            PurchaseError::AlreadyOwned { kind, name } => write!(f, "{} `{}` is already in the player's inventory", kind, name),
+            PurchaseError::LevelRequirementNotMet { kind, name, required_player_level, player_level } => write!(
+                f,
+                "{} `{}` requires player level {} (player is level {})",
+                kind, name, required_player_level, player_level
+            ),
+            PurchaseError::InsufficientFunds(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PurchaseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PurchaseError::InsufficientFunds(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Lists every weapon and then every skill of the catalog the shop sells, in the order they were loaded.
+pub fn shop_entries(catalog: &GameCatalog) -> Vec<ShopEntry> {
+    let weapons = catalog.weapons
+        .iter()
+        .filter(|weapon| weapon.level_mechanics(1).is_some())
+        .map(|weapon| ShopEntry {
+            kind: UpgradeKind::Weapon,
+            name: weapon.name.clone(),
+            cost: weapon.purchase_cost.clone(),
+            min_player_level_required: catalog.weapon_level_requirement(1).map(|requirement| requirement.min_player_level_required),
+        });
+    let skills = catalog.skills
+        .iter()
+        .filter(|skill| skill.level_mechanics(1).is_some())
+        .map(|skill| ShopEntry {
+            kind: UpgradeKind::Skill,
+            name: skill.name.clone(),
+            cost: skill.purchase_cost.clone(),
+            min_player_level_required: catalog.skill_level_requirement(1).map(|requirement| requirement.min_player_level_required),
+        });
+
+    weapons.chain(skills).collect()
+}
+
+/// Gets the entry of the weapon or skill with the name `name` the shop sells.
+pub fn shop_entry(catalog: &GameCatalog, kind: UpgradeKind, name: &str) -> Option<ShopEntry> {
+    shop_entries(catalog).into_iter().find(|entry| entry.kind == kind && entry.name == name)
+}
+
+/// Purchases the weapon with the name `name`, paying for it out of the player's wallet and adding a level 1 copy of it to the player's inventory,
+/// and then publishes a `Purchased` event to `bus`.
+///
+/// Nothing changes if the purchase fails (see `ShopEntry::check`).
+pub fn purchase_weapon(player: &mut Player, catalog: &GameCatalog, bus: &mut EventBus, name: &str) -> Result<Purchase, PurchaseError> {
+    let kind = UpgradeKind::Weapon;
+    let (entry, weapon) = shop_entry(catalog, kind, name)
+        .zip(catalog.weapon(name).and_then(|weapon| weapon.at_level(1)))
+        .ok_or_else(|| PurchaseError::NotSold { kind, name: name.to_string() })?;
+
+    pay(player, &entry)?;
+    player.inventory.weapons.push(weapon);
+    bus.publish(GameEvent::Purchased { kind, name: entry.name.clone() }, player, catalog);
+
+    Ok(Purchase { kind, name: entry.name, cost: entry.cost })
+}
+
+/// Purchases the skill with the name `name`, paying for it out of the player's wallet and adding a level 1 copy of it to the player's inventory,
+/// and then publishes a `Purchased` event to `bus`.
+///
+/// Nothing changes if the purchase fails (see `ShopEntry::check`).
+pub fn purchase_skill(player: &mut Player, catalog: &GameCatalog, bus: &mut EventBus, name: &str) -> Result<Purchase, PurchaseError> {
+    let kind = UpgradeKind::Skill;
+    let (entry, skill) = shop_entry(catalog, kind, name)
+        .zip(catalog.skill(name).and_then(|skill| skill.at_level(1)))
+        .ok_or_else(|| PurchaseError::NotSold { kind, name: name.to_string() })?;
+
+    pay(player, &entry)?;
+    player.inventory.skills.push(skill);
+    bus.publish(GameEvent::Purchased { kind, name: entry.name.clone() }, player, catalog);
+
+    Ok(Purchase { kind, name: entry.name, cost: entry.cost })
+}
+
+/// checks whether the player can purchase the entry and pays for it
+fn pay(player: &mut Player, entry: &ShopEntry) -> Result<(), PurchaseError> {
+    entry.check(player)?;
+    player.wallet
+        .spend(&entry.cost, LedgerReason::Purchase { name: entry.name.clone() })
+        .map_err(PurchaseError::InsufficientFunds)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::{Currency, Shortfall},
+        utils::{load_catalog, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    const SWORD: &str = "Common Rusty Sword";
+    const FIREBALL: &str = "Fireball";
+
+    /// a level 1 player with `coins` coins
+    fn player_with(catalog: &GameCatalog, coins: u32) -> Player {
+        let mut player = Player::new(&catalog.player_levels[0], &catalog.player_levels);
+        player.wallet.deposit(Currency::Coins, coins, LedgerReason::Adjustment { note: "test".to_string() });
+
+        player
+    }
+
+    #[test]
+    fn sells_every_weapon_and_then_every_skill() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let entries = shop_entries(&catalog);
+
+        assert_eq!(entries.len(), catalog.weapons.len() + catalog.skills.len());
+        assert_eq!((entries[0].kind, entries[0].name.as_str(), entries[0].cost.coins), (UpgradeKind::Weapon, SWORD, 150));
+        assert!(entries[1..].iter().all(|entry| entry.kind == UpgradeKind::Skill));
+        assert!(shop_entry(&catalog, UpgradeKind::Skill, SWORD).is_none());
+    }
+
+    #[test]
+    fn purchases_a_weapon_and_publishes_it() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_with(&catalog, 200);
+        let mut bus = EventBus::new();
+        let mut events = bus.subscribe_async();
+
+        let purchase = purchase_weapon(&mut player, &catalog, &mut bus, SWORD).unwrap();
+
+        assert_eq!((purchase.kind, purchase.cost.coins), (UpgradeKind::Weapon, 150));
+        assert_eq!(player.inventory.weapons.iter().map(|weapon| (weapon.name.as_str(), weapon.level)).collect::<Vec<_>>(), vec![(SWORD, 1)]);
+        assert_eq!(player.wallet.balance(Currency::Coins), 50);
+        assert_eq!(events.try_recv(), Ok(GameEvent::Purchased { kind: UpgradeKind::Weapon, name: SWORD.to_string() }));
+    }
+
+    #[test]
+    fn rejects_what_isnt_sold_or_is_already_owned() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_with(&catalog, 1000);
+        let mut bus = EventBus::new();
+
+        assert_eq!(
+            purchase_skill(&mut player, &catalog, &mut bus, SWORD).unwrap_err(),
+            PurchaseError::NotSold { kind: UpgradeKind::Skill, name: SWORD.to_string() }
+        );
+
+        purchase_skill(&mut player, &catalog, &mut bus, FIREBALL).unwrap();
+
+        assert_eq!(
+            purchase_skill(&mut player, &catalog, &mut bus, FIREBALL).unwrap_err(),
+            PurchaseError::AlreadyOwned { kind: UpgradeKind::Skill, name: FIREBALL.to_string() }
+        );
+        assert_eq!(player.inventory.skills.len(), 1);
+        assert_eq!(player.wallet.balance(Currency::Coins), 780);
+    }
+
+    #[test]
+    fn rejects_purchases_below_the_required_player_level() {
+        let mut catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_with(&catalog, 1000);
+        catalog.skill_level_requirements[0].min_player_level_required = 3;
+
+        assert_eq!(
+            purchase_skill(&mut player, &catalog, &mut EventBus::new(), FIREBALL).unwrap_err(),
+            PurchaseError::LevelRequirementNotMet { kind: UpgradeKind::Skill, name: FIREBALL.to_string(), required_player_level: 3, player_level: 1 }
+        );
+        assert!(player.inventory.skills.is_empty());
+        assert_eq!(player.wallet.balance(Currency::Coins), 1000);
+    }
+
+    #[test]
+    fn changes_nothing_if_the_player_cant_pay() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_with(&catalog, 100);
+        let mut bus = EventBus::new();
+        let mut events = bus.subscribe_async();
+
+        assert_eq!(
+            purchase_weapon(&mut player, &catalog, &mut bus, SWORD).unwrap_err(),
+            PurchaseError::InsufficientFunds(InsufficientFunds { shortfalls: vec![Shortfall { currency: Currency::Coins, required: 150, available: 100 }] })
+        );
+        assert!(player.inventory.weapons.is_empty());
+        assert_eq!(player.wallet.ledger().entries().len(), 1);
+        assert!(events.try_recv().is_err());
+    }
+}
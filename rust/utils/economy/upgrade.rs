@@ -0,0 +1,331 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    models::{
+        InsufficientFunds, LedgerReason, LevelRequirementError, Player, PlayerLevelStats, Skill, SkillDefinition, UpgradeCost, Weapon, WeaponDefinition,
+    },
+    utils::{EventBus, GameCatalog, GameEvent},
+};
+
+/// A list of everything the player can upgrade.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Debug)]
+pub enum UpgradeKind {
+    Weapon,
+    Skill,
+}
+
+impl fmt::Display for UpgradeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpgradeKind::Weapon => write!(f, "weapon"),
+            UpgradeKind::Skill => write!(f, "skill"),
+        }
+    }
+}
+
+/// Represents a weapon or skill that was upgraded.
+#[derive(Clone, Debug)]
+pub struct Upgrade {
+    /// whether a weapon or a skill was upgraded
+    pub kind: UpgradeKind,
+    /// the name of the weapon or skill
+    pub name: String,
+    /// the level before the upgrade
+    pub from: u8,
+    /// the level after the upgrade
+    pub to: u8,
+    /// what the upgrade cost
+    pub cost: UpgradeCost,
+}
+
+/// Represents why a weapon or skill couldn't be upgraded.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum UpgradeError {
+    /// the weapon or skill isn't in the player's inventory
+    NotInInventory { kind: UpgradeKind, name: String },
+    /// the weapon or skill doesn't exist in the catalog
+    UnknownDefinition { kind: UpgradeKind, name: String },
+    /// the weapon or skill is already at its highest level
+    AtMaxLevel { kind: UpgradeKind, name: String, level: u8 },
+    /// the next level isn't allowed at the player's level
+    LevelRequirement { kind: UpgradeKind, name: String, error: LevelRequirementError },
+    /// the player can't pay the upgrade cost
+    InsufficientFunds(InsufficientFunds),
+}
+
+impl fmt::Display for UpgradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpgradeError::NotInInventory { kind, name } => write!(f, "{} `{}` isn't in the player's inventory", kind, name),
+            UpgradeError::UnknownDefinition { kind, name } => write!(f, "{} `{}` doesn't exist", kind, name),
+            UpgradeError::AtMaxLevel { kind, name, level } => write!(f, "{} `{}` is already at its max level ({})", kind, name, level),
+            UpgradeError::LevelRequirement { kind, name, error } => write!(f, "{} `{}` {}", kind, name, error),
+            UpgradeError::InsufficientFunds(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for UpgradeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            UpgradeError::LevelRequirement { error, .. } => Some(error),
+            UpgradeError::InsufficientFunds(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Upgrades the weapon with the name `name` within the player's inventory by one level, paying for it out of the player's wallet.
+///
+/// The upgrade must be allowed at the player's level (by the weapon level requirement table and the player's max weapon level),
+/// and the weapon's stats are replaced by the ones of the next level before an `Upgraded` event is published to `bus`.
+/// Nothing changes if the upgrade fails.
+pub fn upgrade_weapon(player: &mut Player, catalog: &GameCatalog, bus: &mut EventBus, name: &str) -> Result<Upgrade, UpgradeError> {
+    upgrade::<WeaponDefinition>(player, catalog, bus, name)
+}
+
+/// Upgrades the skill with the name `name` within the player's inventory by one level, paying for it out of the player's wallet.
+///
+/// The upgrade must be allowed at the player's level (by the skill level requirement table and the player's max skill level),
+/// and the skill's stats are replaced by the ones of the next level before an `Upgraded` event is published to `bus`.
+/// Nothing changes if the upgrade fails.
+pub fn upgrade_skill(player: &mut Player, catalog: &GameCatalog, bus: &mut EventBus, name: &str) -> Result<Upgrade, UpgradeError> {
+    upgrade::<SkillDefinition>(player, catalog, bus, name)
+}
+
+/// upgrades the weapon or skill with the name `name` by one level (see `upgrade_weapon` and `upgrade_skill`)
+fn upgrade<D: Upgradable>(player: &mut Player, catalog: &GameCatalog, bus: &mut EventBus, name: &str) -> Result<Upgrade, UpgradeError> {
+    let kind = D::KIND;
+    let index = D::inventory(player)
+        .iter()
+        .position(|instance| D::instance_name(instance) == name)
+        .ok_or_else(|| UpgradeError::NotInInventory { kind, name: name.to_string() })?;
+    let definition = D::find(catalog, name).ok_or_else(|| UpgradeError::UnknownDefinition { kind, name: name.to_string() })?;
+    let from = D::instance_level(&D::inventory(player)[index]);
+    let to = from.saturating_add(1);
+
+    let (Some(cost), Some(instance)) = (definition.upgrade_cost(to), definition.instance(to)) else {
+        return Err(UpgradeError::AtMaxLevel { kind, name: name.to_string(), level: from });
+    };
+    let max_level = catalog.player_level(player.player_level_mechanics.current_level).map(D::max_level);
+
+    player
+        .check_level_requirement(to, D::required_player_level(catalog, to), max_level)
+        .map_err(|error| UpgradeError::LevelRequirement { kind, name: name.to_string(), error })?;
+    player.wallet.spend(cost, LedgerReason::Upgrade { name: name.to_string(), level: to }).map_err(UpgradeError::InsufficientFunds)?;
+    D::inventory(player)[index] = instance;
+    bus.publish(GameEvent::Upgraded { kind, name: name.to_string(), level: to }, player, catalog);
+
+    Ok(Upgrade { kind, name: name.to_string(), from, to, cost: cost.clone() })
+}
+
+/// gives `upgrade` the same access to weapon and skill definitions, their level tables and the player's instances of them
+trait Upgradable {
+    /// what the player keeps in their inventory (e.g. a `Weapon` for a `WeaponDefinition`)
+    type Instance;
+
+    const KIND: UpgradeKind;
+
+    fn find<'a>(catalog: &'a GameCatalog, name: &str) -> Option<&'a Self>;
+    fn inventory(player: &mut Player) -> &mut Vec<Self::Instance>;
+    fn instance_name(instance: &Self::Instance) -> &str;
+    fn instance_level(instance: &Self::Instance) -> u8;
+    /// the instance at `level` (`None` if there are no mechanics for that level)
+    fn instance(&self, level: u8) -> Option<Self::Instance>;
+    /// the cost of upgrading to `level` (`None` if there are no mechanics for that level)
+    fn upgrade_cost(&self, level: u8) -> Option<&UpgradeCost>;
+    /// the minimum player level required for `level` (`None` if it has no entry in the level requirement table)
+    fn required_player_level(catalog: &GameCatalog, level: u8) -> Option<u8>;
+    /// the max level allowed at the player's level
+    fn max_level(stats: &PlayerLevelStats) -> u8;
+}
+
+impl Upgradable for WeaponDefinition {
+    type Instance = Weapon;
+
+    const KIND: UpgradeKind = UpgradeKind::Weapon;
+
+    fn find<'a>(catalog: &'a GameCatalog, name: &str) -> Option<&'a Self> {
+        catalog.weapon(name)
+    }
+
+    fn inventory(player: &mut Player) -> &mut Vec<Weapon> {
+        &mut player.inventory.weapons
+    }
+
+    fn instance_name(instance: &Weapon) -> &str {
+        &instance.name
+    }
+
+    fn instance_level(instance: &Weapon) -> u8 {
+        instance.level
+    }
+
+    fn instance(&self, level: u8) -> Option<Weapon> {
+        self.at_level(level)
+    }
+
+    fn upgrade_cost(&self, level: u8) -> Option<&UpgradeCost> {
+        self.level_mechanics(level).map(|mechanics| &mechanics.upgrade_cost)
+    }
+
+    fn required_player_level(catalog: &GameCatalog, level: u8) -> Option<u8> {
+        catalog.weapon_level_requirement(level).map(|requirement| requirement.min_player_level_required)
+    }
+
+    fn max_level(stats: &PlayerLevelStats) -> u8 {
+        stats.max_weapon_level
+    }
+}
+
+impl Upgradable for SkillDefinition {
+    type Instance = Skill;
+
+    const KIND: UpgradeKind = UpgradeKind::Skill;
+
+    fn find<'a>(catalog: &'a GameCatalog, name: &str) -> Option<&'a Self> {
+        catalog.skill(name)
+    }
+
+    fn inventory(player: &mut Player) -> &mut Vec<Skill> {
+        &mut player.inventory.skills
+    }
+
+    fn instance_name(instance: &Skill) -> &str {
+        &instance.name
+    }
+
+    fn instance_level(instance: &Skill) -> u8 {
+        instance.level
+    }
+
+    fn instance(&self, level: u8) -> Option<Skill> {
+        self.at_level(level)
+    }
+
+    fn upgrade_cost(&self, level: u8) -> Option<&UpgradeCost> {
+        self.level_mechanics(level).map(|mechanics| &mechanics.upgrade_cost)
+    }
+
+    fn required_player_level(catalog: &GameCatalog, level: u8) -> Option<u8> {
+        catalog.skill_level_requirement(level).map(|requirement| requirement.min_player_level_required)
+    }
+
+    fn max_level(stats: &PlayerLevelStats) -> u8 {
+        stats.max_skill_level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::{Currency, Shortfall},
+        utils::{load_catalog, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    const SWORD: &str = "Common Rusty Sword";
+    const FIREBALL: &str = "Fireball";
+
+    /// a player at `level` with the sword and fireball at level 1 and `coins` coins
+    fn player_at(catalog: &GameCatalog, level: u8, coins: u32) -> Player {
+        let mut player = Player::new(catalog.player_level(level).unwrap(), &catalog.player_levels);
+        player.inventory.weapons.push(catalog.weapon(SWORD).unwrap().at_level(1).unwrap());
+        player.inventory.skills.push(catalog.skill(FIREBALL).unwrap().at_level(1).unwrap());
+        player.wallet.deposit(Currency::Coins, coins, LedgerReason::Adjustment { note: "test".to_string() });
+
+        player
+    }
+
+    #[test]
+    fn upgrades_a_weapon_and_pays_for_it() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_at(&catalog, 2, 15);
+
+        let mut bus = EventBus::new();
+        let mut events = bus.subscribe_async();
+
+        let upgrade = upgrade_weapon(&mut player, &catalog, &mut bus, SWORD).unwrap();
+
+        assert_eq!((upgrade.kind, upgrade.from, upgrade.to, upgrade.cost.coins), (UpgradeKind::Weapon, 1, 2, 10));
+        assert_eq!(player.inventory.weapons[0].level, 2);
+        assert_eq!(player.inventory.weapons[0].base_damage, 25.0);
+        assert_eq!(player.wallet.balance(Currency::Coins), 5);
+        assert_eq!(events.try_recv(), Ok(GameEvent::Upgraded { kind: UpgradeKind::Weapon, name: SWORD.to_string(), level: 2 }));
+    }
+
+    #[test]
+    fn rejects_weapons_and_skills_outside_the_inventory_or_catalog() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_at(&catalog, 5, 1000);
+
+        assert_eq!(
+            upgrade_skill(&mut player, &catalog, &mut EventBus::new(), "Shockstream").unwrap_err(),
+            UpgradeError::NotInInventory { kind: UpgradeKind::Skill, name: "Shockstream".to_string() }
+        );
+
+        player.inventory.weapons[0].name = "Shiny Sword".to_string();
+
+        assert_eq!(
+            upgrade_weapon(&mut player, &catalog, &mut EventBus::new(), "Shiny Sword").unwrap_err(),
+            UpgradeError::UnknownDefinition { kind: UpgradeKind::Weapon, name: "Shiny Sword".to_string() }
+        );
+    }
+
+    #[test]
+    fn rejects_upgrades_past_the_max_level() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_at(&catalog, 150, 0);
+        player.inventory.weapons[0] = catalog.weapon(SWORD).unwrap().at_level(50).unwrap();
+
+        assert_eq!(
+            upgrade_weapon(&mut player, &catalog, &mut EventBus::new(), SWORD).unwrap_err(),
+            UpgradeError::AtMaxLevel { kind: UpgradeKind::Weapon, name: SWORD.to_string(), level: 50 }
+        );
+    }
+
+    #[test]
+    fn rejects_levels_the_player_isnt_allowed_yet() {
+        let mut catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_at(&catalog, 4, 1000);
+        let level_requirement = |name: &str, error| UpgradeError::LevelRequirement { kind: UpgradeKind::Skill, name: name.to_string(), error };
+
+        assert_eq!(
+            upgrade_skill(&mut player, &catalog, &mut EventBus::new(), FIREBALL).unwrap_err(),
+            level_requirement(FIREBALL, LevelRequirementError::LevelRequirementNotMet { level: 2, required_player_level: 5, player_level: 4 })
+        );
+
+        catalog.skill_level_requirements[1].min_player_level_required = 1;
+        assert_eq!(
+            upgrade_skill(&mut player, &catalog, &mut EventBus::new(), FIREBALL).unwrap_err(),
+            level_requirement(FIREBALL, LevelRequirementError::ExceedsMaxLevel { level: 2, max_level: 1 })
+        );
+
+        catalog.skill_level_requirements.remove(1);
+        assert_eq!(upgrade_skill(&mut player, &catalog, &mut EventBus::new(), FIREBALL).unwrap_err(), level_requirement(FIREBALL, LevelRequirementError::UnknownLevel { level: 2 }));
+
+        assert_eq!(player.inventory.skills[0].level, 1);
+        assert_eq!(player.wallet.balance(Currency::Coins), 1000);
+    }
+
+    #[test]
+    fn changes_nothing_if_the_player_cant_pay() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_at(&catalog, 5, 200);
+
+        let error = upgrade_skill(&mut player, &catalog, &mut EventBus::new(), FIREBALL).unwrap_err();
+
+        assert_eq!(
+            error,
+            UpgradeError::InsufficientFunds(InsufficientFunds {
+                shortfalls: vec![Shortfall { currency: Currency::EnergyCores, required: 1, available: 0 }],
+            })
+        );
+        assert_eq!(player.inventory.skills[0].level, 1);
+        assert_eq!(player.wallet.balance(Currency::Coins), 200);
+        assert_eq!(player.wallet.ledger().entries().len(), 1);
+    }
+}
@@ -0,0 +1,226 @@
+use std::{collections::VecDeque, fmt};
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::{
+    models::{Enemy, LevelUp, Player, Resource},
+    utils::{CombatEventKind, CombatReport, Combatant, GameCatalog, UpgradeKind},
+};
+
+/// How many events an async subscriber can fall behind before it starts missing them.
+pub const ASYNC_EVENT_CAPACITY: usize = 1024;
+
+/// A list of everything that happens in the game that other systems (e.g. quests, analytics or achievements) can react to.
+#[derive(Clone, PartialEq, Serialize, Debug)]
+#[serde(tag = "type")]
+pub enum GameEvent {
+    /// the player defeated `enemy` at level `level`
+    EnemyKilled { enemy: String, level: u8 },
+    /// the player picked up `amount` of an item
+    ItemPickedUp { item: String, amount: u32 },
+    /// the player gathered `amount` of a resource
+    ResourceGathered { resource: Resource, amount: u32 },
+    /// the player levelled up
+    LevelUp(LevelUp),
+    /// the player entered an area
+    AreaEntered { area: String },
+    /// the player entered a breathable area
+    BreathableAreaEntered { area: String },
+    /// the player talked to an NPC
+    NpcTalkedTo { npc: String },
+    /// the player completed a quest
+    QuestCompleted { chapter: u8, id: u8 },
+    /// the player purchased a weapon or skill
+    Purchased { kind: UpgradeKind, name: String },
+    /// the player upgraded a weapon or skill to `level`
+    Upgraded { kind: UpgradeKind, name: String, level: u8 },
+}
+
+/// Identifies a subscriber of an `EventBus`, so that it can unsubscribe.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SubscriptionId(u64);
+
+/// Represents the game state an event happened in, which synchronous subscribers can change (e.g. completing the player's quests).
+pub struct EventContext<'a> {
+    /// the player the event happened to
+    pub player: &'a mut Player,
+    /// the catalog the game runs on
+    pub catalog: &'a GameCatalog,
+    /// the events subscribers published while handling the current one
+    follow_ups: Vec<GameEvent>,
+}
+
+impl EventContext<'_> {
+    /// Publishes `event` once every subscriber has handled the current event (e.g. a `QuestCompleted` caused by an `EnemyKilled`).
+    pub fn publish(&mut self, event: GameEvent) {
+        self.follow_ups.push(event);
+    }
+}
+
+/// A function called with every event published to an `EventBus`.
+type Handler = Box<dyn FnMut(&GameEvent, &mut EventContext<'_>) + Send>;
+
+/// Represents a publish-subscribe bus for `GameEvent`s, so that the systems reacting to them don't have to be wired together.
+///
+/// Subscribers are either synchronous (called in order of subscription with the `EventContext`, before `publish` returns)
+/// or asynchronous (receiving a copy of every event through a channel).
+#[derive(Default)]
+pub struct EventBus {
+    /// the synchronous subscribers, in order of subscription
+    handlers: Vec<(SubscriptionId, Handler)>,
+    /// the ID the next synchronous subscriber gets
+    next_id: u64,
+    /// the channel to the asynchronous subscribers (created by the first one)
+    sender: Option<broadcast::Sender<GameEvent>>,
+}
+
+impl EventBus {
+    /// Creates a new bus without any subscribers.
+    pub fn new() -> Self {
+        EventBus::default()
+    }
+
+    /// Subscribes `handler` to every event published from now on.
+    pub fn subscribe(&mut self, handler: impl FnMut(&GameEvent, &mut EventContext<'_>) + Send + 'static) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+
+        self.next_id += 1;
+        self.handlers.push((id, Box::new(handler)));
+
+        id
+    }
+
+    /// Unsubscribes the synchronous subscriber `id`.
+    ///
+    /// Returns whether it was subscribed.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let count = self.handlers.len();
+
+        self.handlers.retain(|(handler_id, _)| *handler_id != id);
+
+        self.handlers.len() < count
+    }
+
+    /// Creates a receiver for every event published from now on, which can be awaited from async code.
+    ///
+    /// A receiver that falls more than `ASYNC_EVENT_CAPACITY` events behind skips the oldest ones (see `broadcast::Receiver::recv`).
+    /// Dropping the receiver unsubscribes it.
+    pub fn subscribe_async(&mut self) -> broadcast::Receiver<GameEvent> {
+        self.sender
+            .get_or_insert_with(|| broadcast::channel(ASYNC_EVENT_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Gets the amount of synchronous and asynchronous subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.handlers.len() + self.sender.as_ref().map_or(0, |sender| sender.receiver_count())
+    }
+
+    /// Publishes `event`, which happened to `player`, to every synchronous subscriber (in order of subscription) and then to every asynchronous one.
+    ///
+    /// Events the subscribers publish while handling it (see `EventContext::publish`) are published right after, in the order they were published.
+    pub fn publish(&mut self, event: GameEvent, player: &mut Player, catalog: &GameCatalog) {
+        let mut context = EventContext { player, catalog, follow_ups: Vec::new() };
+        let mut queue = VecDeque::from([event]);
+
+        while let Some(event) = queue.pop_front() {
+            for (_, handler) in &mut self.handlers {
+                handler(&event, &mut context);
+            }
+
+            queue.extend(context.follow_ups.drain(..));
+
+            if let Some(sender) = &self.sender {
+                // sending only fails if there are no receivers left, in which case nobody is missing the event
+                let _ = sender.send(event);
+            }
+        }
+    }
+
+    /// Publishes an `EnemyKilled` event for every enemy `player` defeated in `report`, in the order they were defeated.
+    ///
+    /// `enemies` must be the enemies the fight was simulated with.
+    pub fn publish_kills(&mut self, report: &CombatReport, enemies: &[Enemy], player: &mut Player, catalog: &GameCatalog) {
+        for event in &report.timeline {
+            let CombatEventKind::Death { combatant: Combatant::Enemy(index) } = event.kind else {
+                continue;
+            };
+            let Some(enemy) = enemies.get(index) else {
+                continue;
+            };
+
+            self.publish(GameEvent::EnemyKilled { enemy: enemy.name.clone(), level: enemy.level }, player, catalog);
+        }
+    }
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBus")
+            .field("handlers", &self.handlers.len())
+            .field("async_subscribers", &self.sender.as_ref().map_or(0, |sender| sender.receiver_count()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        models::{Currency, LedgerReason},
+        utils::{load_catalog, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    fn talk_to(npc: &str) -> GameEvent {
+        GameEvent::NpcTalkedTo { npc: npc.to_string() }
+    }
+
+    #[test]
+    fn calls_sync_subscribers_in_order_until_they_unsubscribe() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = Player::new(&catalog.player_levels[0], &catalog.player_levels);
+        let mut bus = EventBus::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let ids: Vec<SubscriptionId> = (0..3)
+            .map(|subscriber| {
+                let calls = Arc::clone(&calls);
+                bus.subscribe(move |_, _| calls.lock().unwrap().push(subscriber))
+            })
+            .collect();
+
+        bus.publish(talk_to("Pfufu"), &mut player, &catalog);
+        assert!(bus.unsubscribe(ids[1]));
+        assert!(!bus.unsubscribe(ids[1]));
+        bus.publish(talk_to("Pfufu"), &mut player, &catalog);
+
+        assert_eq!(*calls.lock().unwrap(), vec![0, 1, 2, 0, 2]);
+        assert_eq!(bus.subscriber_count(), 2);
+    }
+
+    #[test]
+    fn subscribers_can_change_the_player_and_publish_follow_ups() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = Player::new(&catalog.player_levels[0], &catalog.player_levels);
+        let mut bus = EventBus::new();
+        let mut events = bus.subscribe_async();
+
+        bus.subscribe(|event, context| {
+            if let GameEvent::EnemyKilled { enemy, level } = event {
+                context.player.wallet.deposit(Currency::Coins, 5, LedgerReason::Kill { enemy: enemy.clone(), level: *level });
+                context.publish(talk_to("Pfufu"));
+            }
+        });
+
+        bus.publish(GameEvent::EnemyKilled { enemy: "Trufo".to_string(), level: 1 }, &mut player, &catalog);
+
+        assert_eq!(player.wallet.balance(Currency::Coins), 5);
+        assert_eq!(events.try_recv(), Ok(GameEvent::EnemyKilled { enemy: "Trufo".to_string(), level: 1 }));
+        assert_eq!(events.try_recv(), Ok(talk_to("Pfufu")));
+        assert!(events.try_recv().is_err());
+    }
+}
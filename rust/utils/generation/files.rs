@@ -0,0 +1,80 @@
+use std::{fs::read_to_string, path::PathBuf};
+
+use crate::utils::{to_js_json, GameCatalog, JsonFileError, PLAYER_LEVELS_FILE};
+
+use super::{generate_enemy, generate_player_levels, generate_skill, generate_weapon, GenerationError};
+
+/// Represents a file within `mechanics/` generated from the catalog's growth parameters.
+#[derive(Clone, Debug)]
+pub struct GeneratedFile {
+    /// where the file is written to
+    pub path: PathBuf,
+    /// the file's contents
+    pub contents: String,
+}
+
+impl GeneratedFile {
+    /// Checks whether the file at `path` already has exactly the generated contents.
+    pub fn is_up_to_date(&self) -> bool {
+        read_to_string(&self.path).is_ok_and(|contents| contents == self.contents)
+    }
+
+    /// Writes the generated contents to `path`.
+    pub fn write(&self) -> Result<(), JsonFileError> {
+        std::fs::write(&self.path, &self.contents).map_err(|source| JsonFileError::Write { path: self.path.clone(), source })
+    }
+}
+
+/// Generates the player's level table (`player/playerLevelMechanics.json`), every enemy's file (`enemies/<file>`) and the files of every
+/// weapon and skill with growth parameters (where they were loaded from) from `catalog.growth`.
+/// 
+/// The files are written the same way the TypeScript scripts wrote them, so regenerating unchanged parameters leaves `mechanics/` untouched.
+pub fn generate_level_tables(catalog: &GameCatalog) -> Result<Vec<GeneratedFile>, GenerationError> {
+    let growth = catalog.growth.as_ref().ok_or(GenerationError::MissingGrowth)?;
+
+    let player_levels = generate_player_levels(&growth.player, &catalog.weapon_level_requirements, &catalog.skill_level_requirements)?;
+    let mut files = vec![GeneratedFile {
+        path: catalog.root.join("player").join(PLAYER_LEVELS_FILE),
+        contents: to_js_json(&player_levels),
+    }];
+
+    for enemy in &growth.enemies {
+        files.push(GeneratedFile {
+            path: catalog.root.join("enemies").join(&enemy.file),
+            contents: to_js_json(&generate_enemy(enemy, &catalog.attributes)?),
+        });
+    }
+
+    for growth in &growth.weapons {
+        let weapon = catalog.weapon(&growth.name).ok_or_else(|| GenerationError::UnknownWeapon(growth.name.clone()))?;
+
+        files.push(GeneratedFile { path: weapon.source.clone(), contents: to_js_json(&generate_weapon(growth, weapon)?) });
+    }
+
+    for growth in &growth.skills {
+        let skill = catalog.skill(&growth.name).ok_or_else(|| GenerationError::UnknownSkill(growth.name.clone()))?;
+
+        files.push(GeneratedFile { path: skill.source.clone(), contents: to_js_json(&generate_skill(growth, skill)?) });
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{load_catalog, CATALOG_ROOT};
+
+    use super::*;
+
+    #[test]
+    fn generated_files_match_the_catalog() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let files = generate_level_tables(&catalog).unwrap();
+
+        assert_eq!(files.len(), 1 + catalog.enemies.len() + catalog.weapons.len() + catalog.skills.len());
+
+        for file in files {
+            assert!(file.is_up_to_date(), "{} is outdated", file.path.display());
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use std::{fmt, path::PathBuf};
+
+use crate::{
+    models::{
+        Attribute, EnemyDefinition, EnemyGrowth, Formula, FormulaError, EnemyLevelMechanics, EnemySkillDefinition, EnemySkillLevelMechanics, KillRewards,
+        PlayerGrowth, PlayerLevelStats, ResourceCost, SkillDefinition, SkillGrowth, SkillLevelRequirement, UpgradeCost, UpgradeCostGrowth, WeaponDefinition,
+        WeaponGrowth, WeaponLevelRequirement,
+    },
+    utils::JsonFileError,
+};
+
+/// Generates the player's stats level by level (from level 1 up to `growth.max_level`) by evaluating `growth`'s formulas at each level.
+/// 
+/// The max weapon/skill levels are the highest levels whose minimum player level required is at most this level (0 if there are none).
+pub fn generate_player_levels(
+    growth: &PlayerGrowth,
+    weapon_level_requirements: &[WeaponLevelRequirement],
+    skill_level_requirements: &[SkillLevelRequirement],
+) -> Result<Vec<PlayerLevelStats>, GenerationError> {
+    let evaluate = |field: &str, formula: &Formula, variables: &[(&str, f64)]| {
+        formula.evaluate(variables).map_err(|source| GenerationError::Formula { field: format!("player.{}", field), source })
+    };
+    let mut levels = Vec::new();
+
+    for level in 1..=growth.max_level {
+        let level_variable = ("level", f64::from(level));
+        let base_hp = evaluate("hp", &growth.hp, &[level_variable])?;
+        let variables = [level_variable, ("hp", base_hp)];
+
+        levels.push(PlayerLevelStats {
+            level,
+            base_hp,
+            base_hp_regen: evaluate("hpRegen", &growth.hp_regen, &variables)?,
+            base_movement_speed: evaluate("movementSpeed", &growth.movement_speed, &variables)?,
+            level_up_xp_required: evaluate("levelUpXpRequired", &growth.level_up_xp_required, &variables)?.round() as u32,
+            max_skill_level: max_unlocked_level(skill_level_requirements.iter().map(|requirement| (requirement.level, requirement.min_player_level_required)), level),
+            max_weapon_level: max_unlocked_level(weapon_level_requirements.iter().map(|requirement| (requirement.level, requirement.min_player_level_required)), level),
+        });
+    }
+
+    Ok(levels)
+}
+
+/// Generates an enemy's full data (from level 1 up to `growth.max_level`) by evaluating `growth`'s formulas at each level,
+/// with its skill's attribute looked up by name from `attributes`.
+/// 
+/// The skill stays the same at every level.
+pub fn generate_enemy(growth: &EnemyGrowth, attributes: &[Attribute]) -> Result<EnemyDefinition, GenerationError> {
+    let attribute = attributes
+        .iter()
+        .find(|attribute| attribute.name == growth.skill.attribute)
+        .ok_or_else(|| GenerationError::UnknownAttribute { enemy: growth.name.clone(), attribute: growth.skill.attribute.clone() })?;
+
+    let evaluate = |field: &str, formula: &Formula, variables: &[(&str, f64)]| {
+        formula.evaluate(variables).map_err(|source| GenerationError::Formula { field: format!("{}.{}", growth.name, field), source })
+    };
+    let stats = &growth.stats;
+    let mut level_mechanics = Vec::new();
+
+    for level in 1..=growth.max_level {
+        let level_variable = ("level", f64::from(level));
+        let base_hp = evaluate("stats.hp", &stats.hp, &[level_variable])?;
+        let variables = [level_variable, ("hp", base_hp)];
+
+        level_mechanics.push(EnemyLevelMechanics {
+            level,
+            base_hp,
+            base_hp_regen: evaluate("stats.hpRegen", &stats.hp_regen, &variables)?,
+            base_movement_speed: evaluate("stats.movementSpeed", &stats.movement_speed, &variables)?,
+            base_damage: evaluate("stats.damage", &stats.damage, &variables)?,
+            base_projectile_velocity: evaluate("stats.projectileVelocity", &stats.projectile_velocity, &variables)?,
+            base_attack_range: evaluate("stats.attackRange", &stats.attack_range, &variables)?,
+            base_attack_time: evaluate("stats.attackTime", &stats.attack_time, &variables)?,
+            crit_chance: evaluate("stats.critChance", &stats.crit_chance, &variables)?,
+            // enemy skills only store the mechanics of the enemy's current level
+            skill: EnemySkillDefinition {
+                attributes: vec![attribute.clone()],
+                add_description: growth.skill.add_description.clone(),
+                level_mechanics: vec![EnemySkillLevelMechanics {
+                    level,
+                    duration: growth.skill.duration,
+                    modifier: growth.skill.modifier.clone(),
+                    chance: growth.skill.chance,
+                    radius: growth.skill.radius,
+                }],
+            },
+            kill_rewards: KillRewards {
+                coins: evaluate("killRewards.coins", &growth.kill_rewards.coins, &[level_variable])?.round() as u16,
+                xp: evaluate("killRewards.xp", &growth.kill_rewards.xp, &[level_variable])?.round() as u16,
+            },
+        });
+    }
+
+    Ok(EnemyDefinition {
+        name: growth.name.clone(),
+        description: growth.description.clone(),
+        range_type: growth.range_type,
+        level_mechanics,
+        source: PathBuf::new(),
+    })
+}
+
+/// Regenerates a weapon's base damage, base attack time and upgrade cost at each of its levels by evaluating `growth`'s formulas,
+/// keeping the rest of `weapon` as is.
+pub fn generate_weapon(growth: &WeaponGrowth, weapon: &WeaponDefinition) -> Result<WeaponDefinition, GenerationError> {
+    let evaluate = |field: &str, formula: &Formula, level: u8| {
+        formula
+            .evaluate(&[("level", f64::from(level))])
+            .map_err(|source| GenerationError::Formula { field: format!("{}.{}", growth.name, field), source })
+    };
+    let mut weapon = weapon.clone();
+
+    for mechanics in &mut weapon.level_mechanics {
+        mechanics.base_damage = evaluate("damage", &growth.damage, mechanics.level)?;
+        mechanics.base_attack_time = evaluate("attackTime", &growth.attack_time, mechanics.level)?;
+        mechanics.upgrade_cost = generate_upgrade_cost(&growth.name, &growth.upgrade_cost, mechanics.level)?;
+    }
+
+    Ok(weapon)
+}
+
+/// Regenerates a skill's base damage and upgrade cost at each of its levels by evaluating `growth`'s formulas,
+/// keeping the rest of `skill` as is.
+/// 
+/// The skill has no base damage (i.e. it's non-damaging) if `growth` has no damage formula.
+pub fn generate_skill(growth: &SkillGrowth, skill: &SkillDefinition) -> Result<SkillDefinition, GenerationError> {
+    let mut skill = skill.clone();
+
+    for mechanics in &mut skill.level_mechanics {
+        mechanics.base_damage = growth
+            .damage
+            .as_ref()
+            .map(|damage| damage.evaluate(&[("level", f64::from(mechanics.level))]))
+            .transpose()
+            .map_err(|source| GenerationError::Formula { field: format!("{}.damage", growth.name), source })?;
+        mechanics.upgrade_cost = generate_upgrade_cost(&growth.name, &growth.upgrade_cost, mechanics.level)?;
+    }
+
+    Ok(skill)
+}
+
+/// evaluates the upgrade cost of `name` (a weapon or skill) at `level`, leaving out resources that aren't required at that level
+fn generate_upgrade_cost(name: &str, growth: &UpgradeCostGrowth, level: u8) -> Result<UpgradeCost, GenerationError> {
+    let evaluate = |field: &str, formula: &Formula| {
+        formula
+            .evaluate(&[("level", f64::from(level))])
+            .map(|amount| amount.round() as u32)
+            .map_err(|source| GenerationError::Formula { field: format!("{}.upgradeCost.{}", name, field), source })
+    };
+    let mut resources = Vec::new();
+
+    for resource in &growth.resources {
+        let amount = evaluate(&format!("resources.{:?}", resource.resource), &resource.amount)?;
+
+        if amount > 0 {
+            resources.push(ResourceCost { resource: resource.resource, amount });
+        }
+    }
+
+    Ok(UpgradeCost {
+        coins: evaluate("coins", &growth.coins)?,
+        energy_cores: evaluate("energyCores", &growth.energy_cores)?,
+        resources: (!resources.is_empty()).then_some(resources),
+    })
+}
+
+/// gets the highest level out of `(level, min player level required)` pairs that is unlocked at `player_level` (0 if none are)
+fn max_unlocked_level(requirements: impl Iterator<Item = (u8, u8)>, player_level: u8) -> u8 {
+    requirements
+        .filter(|&(_, min_player_level_required)| min_player_level_required <= player_level)
+        .map(|(level, _)| level)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Represents an error that occurred while generating the level tables in `mechanics/`.
+#[derive(Debug)]
+pub enum GenerationError {
+    /// the catalog has no growth parameters to generate the level tables from
+    MissingGrowth,
+    /// an enemy's skill refers to an attribute that doesn't exist in `attributes.json`
+    UnknownAttribute { enemy: String, attribute: String },
+    /// a weapon's growth refers to a weapon that doesn't exist in `mechanics/weapons`
+    UnknownWeapon(String),
+    /// a skill's growth refers to a skill that doesn't exist in `mechanics/skills`
+    UnknownSkill(String),
+    /// a formula (e.g. `Golem.stats.hp`) couldn't be evaluated
+    Formula { field: String, source: FormulaError },
+    /// a generated file couldn't be written
+    File(JsonFileError),
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerationError::MissingGrowth => write!(f, "the catalog has no growth parameters (growth.json)"),
+            GenerationError::UnknownAttribute { enemy, attribute } => {
+                write!(f, "enemy `{}` has a skill with an unknown attribute `{}`", enemy, attribute)
+            }
+            GenerationError::UnknownWeapon(name) => write!(f, "weapon `{}` doesn't exist", name),
+            GenerationError::UnknownSkill(name) => write!(f, "skill `{}` doesn't exist", name),
+            GenerationError::Formula { field, source } => write!(f, "failed to evaluate {}: {}", field, source),
+            GenerationError::File(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GenerationError::Formula { source, .. } => Some(source),
+            GenerationError::File(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<JsonFileError> for GenerationError {
+    fn from(err: JsonFileError) -> Self {
+        GenerationError::File(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::Formula,
+        utils::{generate_level_tables, load_catalog, GeneratedFile, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    fn growth(hp: &str, hp_regen: &str) -> PlayerGrowth {
+        PlayerGrowth {
+            max_level: 3,
+            hp: Formula::parse(hp).unwrap(),
+            hp_regen: Formula::parse(hp_regen).unwrap(),
+            movement_speed: Formula::constant(5.0),
+            level_up_xp_required: Formula::parse("level * 10.4").unwrap(),
+        }
+    }
+
+    #[test]
+    fn player_and_enemy_tables_match_mechanics() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let files: Vec<GeneratedFile> = generate_level_tables(&catalog)
+            .unwrap()
+            .into_iter()
+            .filter(|file| file.path.starts_with(catalog.root.join("player")) || file.path.starts_with(catalog.root.join("enemies")))
+            .collect();
+
+        assert_eq!(files.len(), 1 + catalog.enemies.len());
+
+        for file in files {
+            assert!(file.is_up_to_date(), "{} is outdated", file.path.display());
+        }
+    }
+
+    #[test]
+    fn player_hp_regen_chains_off_the_hp_of_the_same_level() {
+        let levels = generate_player_levels(&growth("100 + level * 50", "hp / 100"), &[], &[]).unwrap();
+        let stats: Vec<(u8, f64, f64, u32)> = levels.iter().map(|level| (level.level, level.base_hp, level.base_hp_regen, level.level_up_xp_required)).collect();
+
+        assert_eq!(stats, [(1, 150.0, 1.5, 10), (2, 200.0, 2.0, 21), (3, 250.0, 2.5, 31)]);
+    }
+
+    #[test]
+    fn player_hp_cant_refer_to_itself() {
+        let error = generate_player_levels(&growth("hp * 2", "1"), &[], &[]).unwrap_err();
+
+        assert!(matches!(error, GenerationError::Formula { ref field, .. } if field == "player.hp"), "{}", error);
+    }
+
+    #[test]
+    fn max_levels_are_the_highest_unlocked_at_each_player_level() {
+        let weapons = [(1, 1), (2, 2), (3, 2), (4, 5)].map(|(level, min_player_level_required)| WeaponLevelRequirement { level, min_player_level_required });
+        let skills = [(1, 2), (2, 3)].map(|(level, min_player_level_required)| SkillLevelRequirement { level, min_player_level_required });
+        let levels = generate_player_levels(&growth("100", "1"), &weapons, &skills).unwrap();
+        let max_levels: Vec<(u8, u8)> = levels.iter().map(|level| (level.max_weapon_level, level.max_skill_level)).collect();
+
+        assert_eq!(max_levels, [(1, 0), (3, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn max_unlocked_level_ignores_the_order_of_requirements() {
+        assert_eq!(max_unlocked_level([(3, 4), (1, 1), (2, 2)].into_iter(), 3), 2);
+        assert_eq!(max_unlocked_level([(3, 4), (1, 1), (2, 2)].into_iter(), 10), 3);
+        assert_eq!(max_unlocked_level([(1, 2)].into_iter(), 1), 0);
+        assert_eq!(max_unlocked_level(std::iter::empty(), 1), 0);
+    }
+}
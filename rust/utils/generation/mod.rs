@@ -0,0 +1,5 @@
+pub mod levels;
+pub mod files;
+
+pub use levels::*;
+pub use files::*;
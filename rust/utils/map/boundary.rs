@@ -1,6 +1,6 @@
 use crate::models::{Point};
 
-use super::load::load_map;
+use super::load::cached_map;
 
 /// Map boundary is a set of points that make up a polygon to represent the boundaries of the map.
 /// 
@@ -10,7 +10,7 @@ use super::load::load_map;
 pub fn get_map_boundary() -> Vec<Point> {
     // create an empty vector to store the map boundary points
     let mut map_boundary_points: Vec<Point> = Vec::new();
-    let map_data = load_map();
+    let map_data = cached_map();
 
     // get the layers of the map, find a layer called `Map Boundaries`
     let map_boundaries = map_data.layers.iter().find(|layer| layer.name == "Map Boundaries").unwrap();
@@ -35,7 +35,6 @@ pub fn get_map_boundary() -> Vec<Point> {
         map_boundary_points.push(new_point);
     }
 
-    println!("map boundary points: {:?}", map_boundary_points);
     map_boundary_points
 }
 
@@ -43,6 +42,11 @@ pub fn get_map_boundary() -> Vec<Point> {
 ///
 /// This is mainly used to check whether the player is still within the map boundary.
 pub fn is_point_inside_map_boundary(point: &Point, polygon: &[Point]) -> bool {
+    is_point_inside_polygon(point, polygon)
+}
+
+/// Checks if any given point (x, y) is inside `polygon` (whose points are in map coordinates), by casting a ray from the point and counting how many edges it crosses.
+pub fn is_point_inside_polygon(point: &Point, polygon: &[Point]) -> bool {
     let px = point.x;
     let py = point.y;
 
@@ -62,7 +66,5 @@ pub fn is_point_inside_map_boundary(point: &Point, polygon: &[Point]) -> bool {
         }
     }
 
-    println!("counter: {}", counter);
-
     counter % 2 == 1
 }
\ No newline at end of file
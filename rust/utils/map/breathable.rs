@@ -0,0 +1,384 @@
+use std::fmt;
+
+use crate::{
+    models::{BreathableAreaParameters, GameTime, InsufficientFunds, LedgerReason, Player, Point, SuffocationPenalty, UnlockRequirement},
+    utils::{is_quest_completed, EventBus, GameCatalog, GameEvent},
+};
+
+use super::{calc::Shape, registry::LocationRegistry};
+
+/// Represents an area of the map in which the player can breathe once it's unlocked.
+#[derive(Clone, Debug)]
+pub struct BreathableArea {
+    /// the name of the area's object within the map (e.g. `Beacon 1 Breathable Area 2`)
+    pub name: String,
+    /// the name of the layer the area belongs to (e.g. `Beacon 1 Breathable Area`)
+    pub layer: String,
+    /// the area's shape
+    pub shape: Shape,
+    /// what unlocks the area (`None` if it is unlocked from the start)
+    pub unlocked_by: Option<UnlockRequirement>,
+}
+
+/// Represents the player's breathing between updates of `BreathableZones::breathe`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct BreathingState {
+    /// when the player's breathing was last updated (`None` before the first update)
+    pub last_update: Option<GameTime>,
+    /// when the player left the last unlocked breathable area (`None` while within one)
+    pub outside_since: Option<GameTime>,
+}
+
+/// Represents the outcome of the player breathing at a position.
+#[derive(Clone, Debug)]
+pub struct Breath {
+    /// the name of the unlocked breathable area the player is within (`None` if outside of every one)
+    pub area: Option<String>,
+    /// whether the player just entered an unlocked breathable area from outside of every one
+    pub entered: bool,
+    /// the damage the player took from being outside since the last update
+    pub damage: f64,
+}
+
+/// Represents why a breathable area couldn't be unlocked.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum UnlockError {
+    /// there is no breathable area with the name
+    UnknownArea(String),
+    /// the area's object doesn't exist within the map
+    MissingObject(String),
+    /// the area is already unlocked
+    AlreadyUnlocked(String),
+    /// the area is unlocked by completing a quest rather than by paying for it
+    QuestRequired { area: String, description: String },
+    /// the player can't pay the area's cost
+    InsufficientFunds(InsufficientFunds),
+}
+
+impl fmt::Display for UnlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnlockError::UnknownArea(area) => write!(f, "breathable area `{}` doesn't exist", area),
+            UnlockError::MissingObject(area) => write!(f, "breathable area `{}` has no object within the map", area),
+            UnlockError::AlreadyUnlocked(area) => write!(f, "breathable area `{}` is already unlocked", area),
+            UnlockError::QuestRequired { area, description } => write!(f, "breathable area `{}` is unlocked by completing `{}`", area, description),
+            UnlockError::InsufficientFunds(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for UnlockError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            UnlockError::InsufficientFunds(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Represents every breathable area of the map with how it is unlocked, and the penalty for being outside of them.
+#[derive(Clone, Debug)]
+pub struct BreathableZones {
+    /// every breathable area, in the order of `breathableAreas.json`
+    areas: Vec<BreathableArea>,
+    /// what happens to the player outside of every unlocked area
+    penalty: SuffocationPenalty,
+}
+
+impl BreathableZones {
+    /// Creates the breathable areas described by `parameters` out of their objects within `registry` (normally `location_registry`).
+    ///
+    /// Fails if an area has no object within the map.
+    pub fn new(registry: &LocationRegistry, parameters: &BreathableAreaParameters) -> Result<Self, UnlockError> {
+        let areas = parameters.areas
+            .iter()
+            .map(|unlock| {
+                let location = registry.get(&unlock.area).ok_or_else(|| UnlockError::MissingObject(unlock.area.clone()))?;
+
+                Ok(BreathableArea {
+                    name: unlock.area.clone(),
+                    layer: location.layer.clone(),
+                    shape: location.shape.clone(),
+                    unlocked_by: unlock.unlocked_by.clone(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(BreathableZones { areas, penalty: parameters.outside_penalty.clone() })
+    }
+
+    /// Gets every breathable area (locked or not), in the order of `breathableAreas.json`.
+    pub fn areas(&self) -> &[BreathableArea] {
+        &self.areas
+    }
+
+    /// Gets the penalty for being outside of every unlocked area.
+    pub fn penalty(&self) -> &SuffocationPenalty {
+        &self.penalty
+    }
+
+    /// Gets the breathable area named `name`.
+    pub fn area(&self, name: &str) -> Option<&BreathableArea> {
+        self.areas.iter().find(|area| area.name == name)
+    }
+
+    /// Checks whether `area` is unlocked for the player, i.e. it has no requirement, its quest is completed or the player paid for it.
+    pub fn is_unlocked(&self, area: &BreathableArea, player: &Player, catalog: &GameCatalog) -> bool {
+        match &area.unlocked_by {
+            None => true,
+            Some(UnlockRequirement::Quest { description }) => is_quest_completed(player, catalog, description),
+            Some(UnlockRequirement::Cost { .. }) => player.unlocked_areas.contains(&area.name),
+        }
+    }
+
+    /// Gets every breathable area unlocked for the player.
+    pub fn unlocked_areas<'a>(&'a self, player: &'a Player, catalog: &'a GameCatalog) -> impl Iterator<Item = &'a BreathableArea> + 'a {
+        self.areas.iter().filter(move |area| self.is_unlocked(area, player, catalog))
+    }
+
+    /// Gets the first unlocked breathable area containing `point` (`None` if the player can't breathe there).
+    pub fn area_at(&self, point: &Point, player: &Player, catalog: &GameCatalog) -> Option<&BreathableArea> {
+        self.areas.iter().find(|area| area.shape.contains(point) && self.is_unlocked(area, player, catalog))
+    }
+
+    /// Checks whether the player can breathe at `point`, i.e. it is within an unlocked breathable area.
+    pub fn is_breathable(&self, point: &Point, player: &Player, catalog: &GameCatalog) -> bool {
+        self.area_at(point, player, catalog).is_some()
+    }
+
+    /// Unlocks the breathable area named `name` for the player, paying its cost out of the player's wallet.
+    ///
+    /// Only areas unlocked by a cost can be unlocked this way; the others follow their quest. Nothing changes if unlocking fails.
+    pub fn unlock(&self, player: &mut Player, catalog: &GameCatalog, name: &str) -> Result<(), UnlockError> {
+        let area = self.area(name).ok_or_else(|| UnlockError::UnknownArea(name.to_string()))?;
+
+        if self.is_unlocked(area, player, catalog) {
+            return Err(UnlockError::AlreadyUnlocked(name.to_string()));
+        }
+
+        match &area.unlocked_by {
+            Some(UnlockRequirement::Cost { cost }) => {
+                player.wallet.spend(cost, LedgerReason::Purchase { name: name.to_string() }).map_err(UnlockError::InsufficientFunds)?;
+                player.unlocked_areas.insert(name.to_string());
+
+                Ok(())
+            }
+            Some(UnlockRequirement::Quest { description }) => {
+                Err(UnlockError::QuestRequired { area: name.to_string(), description: description.clone() })
+            }
+            None => Err(UnlockError::AlreadyUnlocked(name.to_string())),
+        }
+    }
+
+    /// Updates the player's breathing at `position` at `now`, accounting for the time that passed since the last update of `state`.
+    ///
+    /// Outside of every unlocked area, the player takes damage according to the penalty once its grace time is over.
+    /// Entering an unlocked area from outside of every one publishes a `BreathableAreaEntered` event to `bus`.
+    pub fn breathe(&self, state: &mut BreathingState, player: &mut Player, catalog: &GameCatalog, bus: &mut EventBus, position: &Point, now: GameTime) -> Breath {
+        let last_update = state.last_update.replace(now).unwrap_or(now);
+        let area = self.area_at(position, player, catalog).map(|area| area.name.clone());
+
+        if let Some(name) = &area {
+            let entered = state.outside_since.take().is_some();
+
+            if entered {
+                bus.publish(GameEvent::BreathableAreaEntered { area: name.clone() }, player, catalog);
+            }
+
+            return Breath { area, entered, damage: 0.0 };
+        }
+
+        // the player is assumed to have left right after the last update
+        let outside_since = *state.outside_since.get_or_insert(last_update);
+        let seconds = now.seconds_since(last_update).max(0.0);
+        let outside_for = last_update.seconds_since(outside_since).max(0.0);
+
+        Breath { area, entered: false, damage: self.penalty.damage(player.base_hp, outside_for, seconds) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        models::{BreathableAreaUnlock, Currency, PurchaseCost},
+        utils::{load_catalog, location_registry, GameClock, SimulatedClock, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    /// the area that is unlocked by paying for it in `zones`
+    const PAID_AREA: &str = "Beacon 1 Breathable Area 1";
+
+    /// the catalog's breathable areas, except that `PAID_AREA` costs 100 coins and 2 energy cores instead of requiring a quest
+    fn zones(catalog: &GameCatalog) -> BreathableZones {
+        let mut parameters = catalog.breathable_areas.clone().unwrap();
+
+        parameters.areas.iter_mut().find(|unlock| unlock.area == PAID_AREA).unwrap().unlocked_by =
+            Some(UnlockRequirement::Cost { cost: PurchaseCost { coins: 100, energy_cores: 2, resources: None } });
+
+        BreathableZones::new(location_registry(), &parameters).unwrap()
+    }
+
+    fn player(catalog: &GameCatalog) -> Player {
+        Player::new(&catalog.player_levels[0], &catalog.player_levels)
+    }
+
+    /// a point within the area named `name`, but outside of every other area except its later expansions (the areas of a layer grow around each other)
+    fn point_in(zones: &BreathableZones, name: &str) -> Point {
+        let index = zones.areas().iter().position(|area| area.name == name).unwrap();
+        let area = &zones.areas()[index];
+        let bounds = area.shape.bounds();
+
+        (0..=20)
+            .flat_map(|x| (0..=20).map(move |y| (x, y)))
+            .map(|(x, y)| Point { x: bounds.min.x + bounds.width() * f64::from(x) / 20.0, y: bounds.min.y + bounds.height() * f64::from(y) / 20.0 })
+            .filter(|point| area.shape.contains(point))
+            .find(|point| {
+                zones.areas()
+                    .iter()
+                    .enumerate()
+                    // the area itself and its later expansions are the only ones allowed to contain the point
+                    .all(|(other_index, other)| (other_index >= index && other.layer == area.layer) || !other.shape.contains(point))
+            })
+            .unwrap_or_else(|| panic!("{} is covered by other areas", name))
+    }
+
+    #[test]
+    fn areas_without_an_object_are_rejected() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut parameters = catalog.breathable_areas.clone().unwrap();
+
+        parameters.areas.push(BreathableAreaUnlock { area: "Beacon 9 Breathable Area 1".to_string(), unlocked_by: None });
+
+        assert_eq!(
+            BreathableZones::new(location_registry(), &parameters).unwrap_err(),
+            UnlockError::MissingObject("Beacon 9 Breathable Area 1".to_string())
+        );
+    }
+
+    #[test]
+    fn areas_unlock_from_the_start_or_through_their_quest() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let zones = zones(&catalog);
+        let mut player = player(&catalog);
+        let start = point_in(&zones, "Pfufu Breathable Area Start");
+        let first = point_in(&zones, "Pfufu Breathable Area 1");
+
+        assert!(zones.is_breathable(&start, &player, &catalog));
+        assert!(!zones.is_breathable(&first, &player, &catalog));
+        assert!(!zones.is_breathable(&Point { x: -1000.0, y: -1000.0 }, &player, &catalog));
+
+        // `Feed Pfufu 6 Berries` is quest 1-2
+        player.quest_log.completed.insert((1, 2));
+
+        assert_eq!(zones.area_at(&first, &player, &catalog).map(|area| area.name.as_str()), Some("Pfufu Breathable Area 1"));
+        assert_eq!(
+            zones.unlocked_areas(&player, &catalog).map(|area| area.name.as_str()).collect::<Vec<_>>(),
+            ["Pfufu Breathable Area Start", "Pfufu Breathable Area 1"]
+        );
+    }
+
+    #[test]
+    fn unlocking_pays_the_cost_out_of_the_wallet() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let zones = zones(&catalog);
+        let mut player = player(&catalog);
+        let area = point_in(&zones, PAID_AREA);
+
+        player.wallet.deposit(Currency::Coins, 150, LedgerReason::Adjustment { note: "test".to_string() });
+
+        assert!(matches!(zones.unlock(&mut player, &catalog, PAID_AREA), Err(UnlockError::InsufficientFunds(_))));
+        assert_eq!(player.wallet.balance(Currency::Coins), 150);
+        assert!(!zones.is_breathable(&area, &player, &catalog));
+
+        player.wallet.deposit(Currency::EnergyCores, 2, LedgerReason::Adjustment { note: "test".to_string() });
+
+        assert_eq!(zones.unlock(&mut player, &catalog, PAID_AREA), Ok(()));
+        assert_eq!((player.wallet.balance(Currency::Coins), player.wallet.balance(Currency::EnergyCores)), (50, 0));
+        assert_eq!(player.wallet.ledger().entries().last().map(|entry| &entry.reason), Some(&LedgerReason::Purchase { name: PAID_AREA.to_string() }));
+        assert!(zones.is_breathable(&area, &player, &catalog));
+        assert_eq!(zones.unlock(&mut player, &catalog, PAID_AREA), Err(UnlockError::AlreadyUnlocked(PAID_AREA.to_string())));
+    }
+
+    #[test]
+    fn quest_and_starting_areas_cant_be_paid_for() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let zones = zones(&catalog);
+        let mut player = player(&catalog);
+
+        assert_eq!(
+            zones.unlock(&mut player, &catalog, "Pfufu Breathable Area 1"),
+            Err(UnlockError::QuestRequired { area: "Pfufu Breathable Area 1".to_string(), description: "Feed Pfufu 6 Berries".to_string() })
+        );
+        assert_eq!(
+            zones.unlock(&mut player, &catalog, "Pfufu Breathable Area Start"),
+            Err(UnlockError::AlreadyUnlocked("Pfufu Breathable Area Start".to_string()))
+        );
+        assert_eq!(zones.unlock(&mut player, &catalog, "Nowhere"), Err(UnlockError::UnknownArea("Nowhere".to_string())));
+        assert!(player.unlocked_areas.is_empty());
+    }
+
+    #[test]
+    fn suffocating_starts_after_the_grace_time() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let zones = zones(&catalog);
+        let mut player = player(&catalog);
+        let mut bus = EventBus::new();
+        let mut state = BreathingState::default();
+        let mut clock = SimulatedClock::default();
+        let outside = Point { x: -1000.0, y: -1000.0 };
+        // 5% of the player's base HP per second once the 3 seconds of grace time are over
+        let per_second = player.base_hp * 0.05;
+        let mut damage_after = |seconds: f64, player: &mut Player, state: &mut BreathingState| {
+            clock.advance(seconds);
+            zones.breathe(state, player, &catalog, &mut bus, &outside, clock.now()).damage
+        };
+
+        assert_eq!(damage_after(0.0, &mut player, &mut state), 0.0);
+        assert_eq!(damage_after(2.0, &mut player, &mut state), 0.0);
+        // 3 seconds of grace time, then 1 second of suffocating
+        assert!((damage_after(2.0, &mut player, &mut state) - per_second).abs() < 1e-9);
+        assert!((damage_after(0.5, &mut player, &mut state) - per_second * 0.5).abs() < 1e-9);
+        assert!((damage_after(10.0, &mut player, &mut state) - per_second * 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn entering_an_area_from_outside_publishes_it_and_resets_the_grace_time() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let zones = zones(&catalog);
+        let mut player = player(&catalog);
+        let mut bus = EventBus::new();
+        let mut state = BreathingState::default();
+        let mut clock = SimulatedClock::default();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let start = point_in(&zones, "Pfufu Breathable Area Start");
+        let outside = Point { x: -1000.0, y: -1000.0 };
+
+        bus.subscribe({
+            let events = Arc::clone(&events);
+            move |event, _| events.lock().unwrap().push(event.clone())
+        });
+
+        let breath = zones.breathe(&mut state, &mut player, &catalog, &mut bus, &start, clock.now());
+        assert_eq!(breath.area.as_deref(), Some("Pfufu Breathable Area Start"));
+        assert!(!breath.entered);
+
+        clock.advance(1.0);
+        zones.breathe(&mut state, &mut player, &catalog, &mut bus, &outside, clock.now());
+        clock.advance(1.0);
+        let breath = zones.breathe(&mut state, &mut player, &catalog, &mut bus, &start, clock.now());
+        assert!(breath.entered);
+        assert_eq!(breath.damage, 0.0);
+
+        // staying inside doesn't publish it again
+        clock.advance(1.0);
+        assert!(!zones.breathe(&mut state, &mut player, &catalog, &mut bus, &start, clock.now()).entered);
+        assert_eq!(*events.lock().unwrap(), [GameEvent::BreathableAreaEntered { area: "Pfufu Breathable Area Start".to_string() }]);
+
+        // leaving again starts a new grace time
+        clock.advance(3.0);
+        assert_eq!(zones.breathe(&mut state, &mut player, &catalog, &mut bus, &outside, clock.now()).damage, 0.0);
+    }
+}
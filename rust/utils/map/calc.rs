@@ -0,0 +1,377 @@
+use crate::models::{Object, Point};
+
+use super::boundary::is_point_inside_polygon;
+
+/// Represents an axis-aligned bounding box in map coordinates.
+#[derive(Clone, Copy, Debug)]
+pub struct Bounds {
+    /// the bottom left corner (lowest x and y)
+    pub min: Point,
+    /// the top right corner (highest x and y)
+    pub max: Point,
+}
+
+impl Bounds {
+    /// Gets the smallest bounds containing every point of `points` (`None` if there are none).
+    pub fn of_points(points: &[Point]) -> Option<Self> {
+        let first = *points.first()?;
+
+        Some(points.iter().fold(Bounds { min: first, max: first }, |bounds, point| Bounds {
+            min: Point { x: bounds.min.x.min(point.x), y: bounds.min.y.min(point.y) },
+            max: Point { x: bounds.max.x.max(point.x), y: bounds.max.y.max(point.y) },
+        }))
+    }
+
+    /// Gets the width of the bounds.
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    /// Gets the height of the bounds.
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    /// Gets the middle of the bounds.
+    pub fn center(&self) -> Point {
+        Point { x: (self.min.x + self.max.x) / 2.0, y: (self.min.y + self.max.y) / 2.0 }
+    }
+
+    /// Checks whether `point` is within the bounds (edges included).
+    pub fn contains(&self, point: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+}
+
+/// Represents the true shape of a map object in map coordinates.
+///
+/// In `invertedMapData.json`, an object's `x` and `y` are its top left corner; polygon points are relative to it,
+/// and rectangles and ellipses extend `width` to the right and `height` downwards from it (since the y axis is inverted, see `convert_coord_system`).
+#[derive(Clone, Debug)]
+pub enum Shape {
+    /// a single point (an object without a size, e.g. the starting point)
+    Point(Point),
+    /// a polygon made up of these points
+    Polygon(Vec<Point>),
+    /// a rectangle
+    Rectangle(Bounds),
+    /// an ellipse around `center` with a horizontal radius of `radius_x` and a vertical radius of `radius_y`
+    Ellipse { center: Point, radius_x: f64, radius_y: f64 },
+}
+
+impl Shape {
+    /// Gets the shape of `object`.
+    pub fn of(object: &Object) -> Self {
+        if let Some(polygon) = object.polygon.as_ref().filter(|polygon| !polygon.is_empty()) {
+            return Shape::Polygon(polygon.iter().map(|point| Point { x: object.x + point.x, y: object.y + point.y }).collect());
+        }
+
+        if object.width == 0.0 && object.height == 0.0 {
+            return Shape::Point(Point { x: object.x, y: object.y });
+        }
+
+        let bounds = Bounds {
+            min: Point { x: object.x, y: object.y - object.height },
+            max: Point { x: object.x + object.width, y: object.y },
+        };
+
+        if object.ellipse.unwrap_or(false) {
+            Shape::Ellipse { center: bounds.center(), radius_x: object.width / 2.0, radius_y: object.height / 2.0 }
+        } else {
+            Shape::Rectangle(bounds)
+        }
+    }
+
+    /// Gets the centroid (center of mass) of the shape.
+    ///
+    /// Polygons without an area (e.g. all of their points are on a line) use the average of their points instead.
+    pub fn centroid(&self) -> Point {
+        match self {
+            Shape::Point(point) => *point,
+            Shape::Rectangle(bounds) => bounds.center(),
+            Shape::Ellipse { center, .. } => *center,
+            Shape::Polygon(points) => {
+                let signed_area = polygon_signed_area(points);
+
+                if signed_area.abs() < f64::EPSILON {
+                    let count = points.len().max(1) as f64;
+
+                    return Point {
+                        x: points.iter().map(|point| point.x).sum::<f64>() / count,
+                        y: points.iter().map(|point| point.y).sum::<f64>() / count,
+                    };
+                }
+
+                let (x, y) = polygon_edges(points).fold((0.0, 0.0), |(x, y), (a, b)| {
+                    let cross = a.x * b.y - b.x * a.y;
+
+                    (x + (a.x + b.x) * cross, y + (a.y + b.y) * cross)
+                });
+
+                Point { x: x / (6.0 * signed_area), y: y / (6.0 * signed_area) }
+            }
+        }
+    }
+
+    /// Gets the axis-aligned bounding box of the shape.
+    pub fn bounds(&self) -> Bounds {
+        match self {
+            Shape::Point(point) => Bounds { min: *point, max: *point },
+            Shape::Polygon(points) => Bounds::of_points(points).unwrap_or(Bounds { min: Point { x: 0.0, y: 0.0 }, max: Point { x: 0.0, y: 0.0 } }),
+            Shape::Rectangle(bounds) => *bounds,
+            Shape::Ellipse { center, radius_x, radius_y } => Bounds {
+                min: Point { x: center.x - radius_x, y: center.y - radius_y },
+                max: Point { x: center.x + radius_x, y: center.y + radius_y },
+            },
+        }
+    }
+
+    /// Gets the area of the shape (in square map units).
+    pub fn area(&self) -> f64 {
+        match self {
+            Shape::Point(_) => 0.0,
+            Shape::Polygon(points) => polygon_signed_area(points).abs(),
+            Shape::Rectangle(bounds) => bounds.width() * bounds.height(),
+            Shape::Ellipse { radius_x, radius_y, .. } => std::f64::consts::PI * radius_x * radius_y,
+        }
+    }
+
+    /// Checks whether `point` is inside the shape.
+    pub fn contains(&self, point: &Point) -> bool {
+        match self {
+            Shape::Point(other) => other.x == point.x && other.y == point.y,
+            Shape::Polygon(points) => is_point_inside_polygon(point, points),
+            Shape::Rectangle(bounds) => bounds.contains(point),
+            Shape::Ellipse { center, radius_x, radius_y } => {
+                if *radius_x <= 0.0 || *radius_y <= 0.0 {
+                    return false;
+                }
+
+                ((point.x - center.x) / radius_x).powi(2) + ((point.y - center.y) / radius_y).powi(2) <= 1.0
+            }
+        }
+    }
+
+    /// Gets the distance between `point` and the closest point of the shape (0 if the shape contains `point`).
+    pub fn distance_to(&self, point: &Point) -> f64 {
+        if self.contains(point) {
+            return 0.0;
+        }
+
+        match self {
+            Shape::Point(other) => other.distance_to(point),
+            Shape::Polygon(points) => polygon_edges(points)
+                .map(|(a, b)| distance_to_segment(point, a, b))
+                .fold(f64::INFINITY, f64::min),
+            Shape::Rectangle(bounds) => {
+                let dx = (bounds.min.x - point.x).max(point.x - bounds.max.x).max(0.0);
+                let dy = (bounds.min.y - point.y).max(point.y - bounds.max.y).max(0.0);
+
+                dx.hypot(dy)
+            }
+            Shape::Ellipse { center, radius_x, radius_y } => distance_to_ellipse(point, center, *radius_x, *radius_y),
+        }
+    }
+}
+
+/// iterates over every edge of a polygon, including the one closing it
+fn polygon_edges(points: &[Point]) -> impl Iterator<Item = (&Point, &Point)> {
+    points.iter().zip(points.iter().cycle().skip(1))
+}
+
+/// gets the area of a polygon through the shoelace formula (positive if its points go counterclockwise)
+fn polygon_signed_area(points: &[Point]) -> f64 {
+    polygon_edges(points).map(|(a, b)| a.x * b.y - b.x * a.y).sum::<f64>() / 2.0
+}
+
+/// gets the distance between `point` and the closest point of the segment between `a` and `b`
+fn distance_to_segment(point: &Point, a: &Point, b: &Point) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_squared = dx * dx + dy * dy;
+
+    if length_squared == 0.0 {
+        return point.distance_to(a);
+    }
+
+    let t = (((point.x - a.x) * dx + (point.y - a.y) * dy) / length_squared).clamp(0.0, 1.0);
+
+    point.distance_to(&Point { x: a.x + t * dx, y: a.y + t * dy })
+}
+
+/// gets the distance between `point` (outside of the ellipse) and the closest point on the ellipse's outline
+///
+/// the closest point has no closed form, so it is approximated by iteratively refining the angle towards it
+/// (3 iterations are accurate to well below a map unit for any ellipse on the map)
+fn distance_to_ellipse(point: &Point, center: &Point, radius_x: f64, radius_y: f64) -> f64 {
+    if radius_x <= 0.0 || radius_y <= 0.0 {
+        // the ellipse is flattened into a line
+        let (radius_x, radius_y) = (radius_x.max(0.0), radius_y.max(0.0));
+        let a = Point { x: center.x - radius_x, y: center.y - radius_y };
+        let b = Point { x: center.x + radius_x, y: center.y + radius_y };
+
+        return distance_to_segment(point, &a, &b);
+    }
+
+    // by symmetry, the closest point can be searched for in the first quadrant
+    let (px, py) = ((point.x - center.x).abs(), (point.y - center.y).abs());
+    let (mut tx, mut ty) = (std::f64::consts::FRAC_1_SQRT_2, std::f64::consts::FRAC_1_SQRT_2);
+
+    for _ in 0..3 {
+        let (x, y) = (radius_x * tx, radius_y * ty);
+        // the center of curvature of the ellipse at (x, y)
+        let ex = (radius_x * radius_x - radius_y * radius_y) * tx.powi(3) / radius_x;
+        let ey = (radius_y * radius_y - radius_x * radius_x) * ty.powi(3) / radius_y;
+        let r = (x - ex).hypot(y - ey);
+        let q = (px - ex).hypot(py - ey);
+
+        if q == 0.0 {
+            break;
+        }
+
+        tx = ((px - ex) * r / q + ex) / radius_x;
+        ty = ((py - ey) * r / q + ey) / radius_y;
+        tx = tx.clamp(0.0, 1.0);
+        ty = ty.clamp(0.0, 1.0);
+
+        let length = tx.hypot(ty).max(f64::MIN_POSITIVE);
+
+        tx /= length;
+        ty /= length;
+    }
+
+    (px - radius_x * tx).hypot(py - radius_y * ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    fn object(x: f64, y: f64, width: f64, height: f64, ellipse: bool, polygon: Option<&[(f64, f64)]>) -> Object {
+        Object {
+            name: String::new(),
+            ellipse: ellipse.then_some(true),
+            height,
+            width,
+            polygon: polygon.map(|points| points.iter().map(|&(x, y)| Point { x, y }).collect()),
+            x,
+            y,
+            properties: None,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "expected {}, got {}", expected, actual);
+    }
+
+    fn assert_point(actual: Point, expected: (f64, f64)) {
+        assert_close(actual.x, expected.0);
+        assert_close(actual.y, expected.1);
+    }
+
+    fn assert_bounds(actual: Bounds, min: (f64, f64), max: (f64, f64)) {
+        assert_point(actual.min, min);
+        assert_point(actual.max, max);
+    }
+
+    #[test]
+    fn rectangles_extend_down_from_their_top_left_corner() {
+        let shape = Shape::of(&object(10.0, 20.0, 4.0, 6.0, false, None));
+
+        assert!(matches!(shape, Shape::Rectangle(_)));
+        assert_bounds(shape.bounds(), (10.0, 14.0), (14.0, 20.0));
+        assert_point(shape.centroid(), (12.0, 17.0));
+        assert_close(shape.area(), 24.0);
+        assert!(shape.contains(&Point { x: 12.0, y: 15.0 }));
+        assert!(shape.contains(&Point { x: 14.0, y: 20.0 }));
+        assert!(!shape.contains(&Point { x: 12.0, y: 21.0 }));
+        assert_close(shape.distance_to(&Point { x: 12.0, y: 15.0 }), 0.0);
+        assert_close(shape.distance_to(&Point { x: 12.0, y: 10.0 }), 4.0);
+        // 3 to the right of and 4 above the top right corner
+        assert_close(shape.distance_to(&Point { x: 17.0, y: 24.0 }), 5.0);
+    }
+
+    #[test]
+    fn ellipses_fill_their_bounding_rectangle() {
+        let shape = Shape::of(&object(0.0, 10.0, 8.0, 4.0, true, None));
+
+        assert!(matches!(shape, Shape::Ellipse { radius_x: 4.0, radius_y: 2.0, .. }));
+        assert_bounds(shape.bounds(), (0.0, 6.0), (8.0, 10.0));
+        assert_point(shape.centroid(), (4.0, 8.0));
+        assert_close(shape.area(), 8.0 * PI);
+        assert!(shape.contains(&Point { x: 7.0, y: 8.0 }));
+        // (3 / 4)² + (1.5 / 2)² = 1.125, so the corner of the bounding rectangle is outside of the ellipse
+        assert!(!shape.contains(&Point { x: 7.0, y: 9.5 }));
+        assert_close(shape.distance_to(&Point { x: 7.0, y: 8.0 }), 0.0);
+        assert_close(shape.distance_to(&Point { x: 10.0, y: 8.0 }), 2.0);
+        assert_close(shape.distance_to(&Point { x: 4.0, y: 13.0 }), 3.0);
+    }
+
+    #[test]
+    fn ellipse_distances_match_a_dense_sampling_of_the_outline() {
+        let (center, radius_x, radius_y) = (Point { x: 4.0, y: 8.0 }, 4.0, 2.0);
+        let shape = Shape::Ellipse { center, radius_x, radius_y };
+
+        for point in [Point { x: 7.0, y: 9.5 }, Point { x: 12.0, y: 14.0 }, Point { x: -3.0, y: 9.0 }] {
+            let sampled = (0..100_000)
+                .map(|step| f64::from(step) / 100_000.0 * 2.0 * PI)
+                .map(|angle| point.distance_to(&Point { x: center.x + radius_x * angle.cos(), y: center.y + radius_y * angle.sin() }))
+                .fold(f64::INFINITY, f64::min);
+
+            assert!((shape.distance_to(&point) - sampled).abs() < 1e-3, "{:?}: {} vs {}", point, shape.distance_to(&point), sampled);
+        }
+    }
+
+    #[test]
+    fn concave_polygons_use_their_true_centroid_and_area() {
+        // an L going down from (10, 20): a 4x2 bar on top and a 2x2 leg below its left half, leaving a notch at the bottom right
+        let points = [(0.0, 0.0), (4.0, 0.0), (4.0, -2.0), (2.0, -2.0), (2.0, -4.0), (0.0, -4.0)];
+        let shape = Shape::of(&object(10.0, 20.0, 0.0, 0.0, false, Some(&points)));
+
+        assert_bounds(shape.bounds(), (10.0, 16.0), (14.0, 20.0));
+        assert_close(shape.area(), 12.0);
+        // the bar (area 8, centroid (12, 19)) and the leg (area 4, centroid (11, 17)) weighted by area
+        assert_point(shape.centroid(), (10.0 + 5.0 / 3.0, 20.0 - 5.0 / 3.0));
+        assert!(shape.contains(&shape.centroid()));
+        assert!(shape.contains(&Point { x: 13.0, y: 19.0 }));
+        assert!(shape.contains(&Point { x: 11.0, y: 17.0 }));
+        assert!(!shape.contains(&Point { x: 13.0, y: 17.0 }));
+        // the middle of the notch is 1 away from both of its edges
+        assert_close(shape.distance_to(&Point { x: 13.0, y: 17.0 }), 1.0);
+        assert_close(shape.distance_to(&Point { x: 11.0, y: 17.0 }), 0.0);
+        assert_close(shape.distance_to(&Point { x: 8.0, y: 20.0 }), 2.0);
+    }
+
+    #[test]
+    fn polygon_orientation_doesnt_change_the_centroid_or_area() {
+        let clockwise = [(0.0, 0.0), (0.0, 2.0), (6.0, 2.0), (6.0, 0.0)];
+        let counterclockwise = [(0.0, 0.0), (6.0, 0.0), (6.0, 2.0), (0.0, 2.0)];
+
+        for points in [clockwise, counterclockwise] {
+            let shape = Shape::of(&object(0.0, 0.0, 0.0, 0.0, false, Some(&points)));
+
+            assert_close(shape.area(), 12.0);
+            assert_point(shape.centroid(), (3.0, 1.0));
+        }
+    }
+
+    #[test]
+    fn flat_polygons_use_the_average_of_their_points() {
+        let shape = Shape::Polygon(vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, Point { x: 5.0, y: 5.0 }]);
+
+        assert_close(shape.area(), 0.0);
+        assert_point(shape.centroid(), (2.0, 2.0));
+    }
+
+    #[test]
+    fn objects_without_a_size_are_points() {
+        let shape = Shape::of(&object(3.0, 4.0, 0.0, 0.0, false, None));
+
+        assert!(matches!(shape, Shape::Point(_)));
+        assert_bounds(shape.bounds(), (3.0, 4.0), (3.0, 4.0));
+        assert_close(shape.area(), 0.0);
+        assert!(shape.contains(&Point { x: 3.0, y: 4.0 }));
+        assert_close(shape.distance_to(&Point { x: 0.0, y: 0.0 }), 5.0);
+    }
+}
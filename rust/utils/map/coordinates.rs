@@ -1,13 +1,11 @@
 use crate::utils::load_map_uninverted;
 
-use super::load::load_map;
-
 /// By default, the coordinate system has top left as the origin (0, 0). This means that the y value goes up as you go down the map.
 /// 
 /// To simplify mathematical processes, we will convert the coordinate system to have bottom left as the origin (0, 0). This means that the y value goes up as you go up the map.
 /// 
 /// The process is to just get the "true y value" by reducing the map height with the current y value within the map.
-pub fn convert_coord_system() -> () {
+pub fn convert_coord_system() {
     // load the map data
     let mut map_data = load_map_uninverted();
 
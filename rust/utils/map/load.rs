@@ -1,4 +1,4 @@
-use std::fs::read_to_string;
+use std::{fs::read_to_string, sync::OnceLock};
 use serde_json::from_str;
 
 use crate::models::{Map, Layer};
@@ -7,23 +7,28 @@ use crate::models::{Map, Layer};
 pub fn load_map() -> Map {
     // reads the JSON file containing the map data into a string
     let json_data = read_to_string("src/map/invertedMapData.json").expect("Failed to read invertedMapData.json");
-    let map_data = from_str(&json_data).expect("Failed to parse mapData.json");
-
-    return map_data
+    from_str(&json_data).expect("Failed to parse mapData.json")
 }
 
 /// Loads the contents of `mapData.json` where the starting coordinates is top left (0, 0) into a Map instance.
 pub fn load_map_uninverted() -> Map {
     // reads the JSON file containing the map data into a string
     let json_data = read_to_string("src/map/mapData.json").expect("Failed to read mapData.json");
-    let map_data = from_str(&json_data).expect("Failed to parse mapData.json");
+    from_str(&json_data).expect("Failed to parse mapData.json")
+}
+
+/// Gets the contents of `invertedMapData.json`, which are only loaded (see `load_map`) the first time this is called.
+/// 
+/// Use this instead of `load_map` whenever the map is queried repeatedly.
+pub fn cached_map() -> &'static Map {
+    static MAP: OnceLock<Map> = OnceLock::new();
 
-    return map_data
+    MAP.get_or_init(load_map)
 }
 
-/// Loads a specific layer with name `layer_name` from the map.
+/// Loads a specific layer with name `layer_name` from the (cached) map.
 pub fn load_map_layer(layer_name: &str) -> Layer {
-    let map_data = load_map();
+    let map_data = cached_map();
     let layer = map_data.layers
         .iter()
         .find(|layer| layer.name == layer_name)
@@ -2,8 +2,18 @@ pub mod load;
 pub mod boundary;
 pub mod coordinates;
 pub mod locations;
+pub mod spatial;
+pub mod calc;
+pub mod registry;
+pub mod spawner;
+pub mod breathable;
 
 pub use load::*;
 pub use boundary::*;
 pub use coordinates::*;
-pub use locations::*;
\ No newline at end of file
+pub use locations::*;
+pub use spatial::*;
+pub use calc::*;
+pub use registry::*;
+pub use spawner::*;
+pub use breathable::*;
\ No newline at end of file
@@ -0,0 +1,178 @@
+use std::sync::OnceLock;
+
+use crate::models::{Map, Point};
+
+use super::{calc::Shape, load::cached_map};
+
+/// Represents a named object of the map (e.g. an NPC like `Pfufu` or a landmark like `Beacon 1`).
+#[derive(Clone, Debug)]
+pub struct NamedLocation {
+    /// the object's name
+    pub name: String,
+    /// the name of the layer the object belongs to
+    pub layer: String,
+    /// the index of the layer within the map (to tell layers with the same name apart)
+    pub layer_index: usize,
+    /// the object's shape
+    pub shape: Shape,
+    /// the object's location (the centroid of its shape)
+    pub position: Point,
+}
+
+/// Represents a layer name that is used by more than one layer of the map.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DuplicateLayer {
+    /// the layer name
+    pub name: String,
+    /// the index of every layer with that name within the map
+    pub layer_indices: Vec<usize>,
+}
+
+/// Represents every named object of every layer of the map, for looking up locations by name or by layer.
+///
+/// Layers with a single object (e.g. `Pfufu`) can also be looked up by the layer's name, even if the object is named differently.
+#[derive(Clone, Default, Debug)]
+pub struct LocationRegistry {
+    /// every location, in the order of their layers and then of their objects
+    locations: Vec<NamedLocation>,
+    /// the name of every layer (with objects) within the map, with its index
+    layers: Vec<(usize, String)>,
+}
+
+impl LocationRegistry {
+    /// Creates a registry of every object of every layer of `map`.
+    pub fn from_map(map: &Map) -> Self {
+        let mut registry = LocationRegistry::default();
+
+        for (layer_index, layer) in map.layers.iter().enumerate() {
+            let Some(objects) = layer.objects.as_ref().filter(|objects| !objects.is_empty()) else {
+                continue;
+            };
+
+            registry.layers.push((layer_index, layer.name.clone()));
+            registry.locations.extend(objects.iter().map(|object| {
+                let shape = Shape::of(object);
+
+                NamedLocation { name: object.name.clone(), layer: layer.name.clone(), layer_index, position: shape.centroid(), shape }
+            }));
+        }
+
+        registry
+    }
+
+    /// Gets every location, in the order of their layers and then of their objects.
+    pub fn locations(&self) -> &[NamedLocation] {
+        &self.locations
+    }
+
+    /// Gets the location named `name`, or the only location of the layer named `name`.
+    ///
+    /// If several match (e.g. the layer name is a duplicate), the first one in the map's order is returned;
+    /// use `all_named` to get every match.
+    pub fn get(&self, name: &str) -> Option<&NamedLocation> {
+        self.locations.iter().find(|location| self.matches(location, name))
+    }
+
+    /// Gets every location named `name` and the only location of every layer named `name`, in the map's order.
+    pub fn all_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a NamedLocation> + 'a {
+        self.locations.iter().filter(move |location| self.matches(location, name))
+    }
+
+    /// Gets every location of every layer named `layer` (several layers if the name is a duplicate), in the map's order.
+    pub fn layer<'a>(&'a self, layer: &'a str) -> impl Iterator<Item = &'a NamedLocation> + 'a {
+        self.locations.iter().filter(move |location| location.layer == layer)
+    }
+
+    /// Gets the names of every layer with objects, in the map's order (duplicates included).
+    pub fn layer_names(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|(_, name)| name.as_str())
+    }
+
+    /// Gets every layer name used by more than one layer (with objects), in the order they first appear.
+    pub fn duplicate_layers(&self) -> Vec<DuplicateLayer> {
+        let mut duplicates: Vec<DuplicateLayer> = Vec::new();
+
+        for (index, name) in &self.layers {
+            if let Some(duplicate) = duplicates.iter_mut().find(|duplicate| &duplicate.name == name) {
+                duplicate.layer_indices.push(*index);
+            } else {
+                duplicates.push(DuplicateLayer { name: name.clone(), layer_indices: vec![*index] });
+            }
+        }
+
+        duplicates.retain(|duplicate| duplicate.layer_indices.len() > 1);
+
+        duplicates
+    }
+
+    /// checks whether `location` is named `name` or is the only location of a layer named `name`
+    fn matches(&self, location: &NamedLocation, name: &str) -> bool {
+        location.name == name || (location.layer == name && self.is_single_object_layer(location.layer_index))
+    }
+
+    /// checks whether the layer at `layer_index` has exactly one object
+    fn is_single_object_layer(&self, layer_index: usize) -> bool {
+        self.locations.iter().filter(|location| location.layer_index == layer_index).count() == 1
+    }
+}
+
+/// Gets the registry of every object of the (cached) map, which is only built the first time this is called.
+pub fn location_registry() -> &'static LocationRegistry {
+    static REGISTRY: OnceLock<LocationRegistry> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| LocationRegistry::from_map(cached_map()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{get_pfufu_location, get_starting_point, load_map_layer};
+
+    use super::*;
+
+    fn assert_point(actual: Point, expected: (f64, f64)) {
+        assert!((actual.x - expected.0).abs() < 1e-6 && (actual.y - expected.1).abs() < 1e-6, "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn the_two_razer_layers_are_duplicates() {
+        let registry = location_registry();
+
+        assert_eq!(registry.duplicate_layers(), [DuplicateLayer { name: "Razer".to_string(), layer_indices: vec![11, 20] }]);
+        assert_eq!(registry.layer_names().filter(|name| *name == "Razer").count(), 2);
+
+        let razers: Vec<(usize, Point)> = registry.layer("Razer").map(|location| (location.layer_index, location.position)).collect();
+
+        assert_eq!(razers.len(), 2);
+        assert_eq!(registry.all_named("Razer").count(), 2);
+        // the first Razer in the map's order wins
+        assert_eq!(registry.get("Razer").map(|location| location.layer_index), Some(11));
+        assert_point(razers[0].1, (763.25 + 4.5, 526.0 - 3.375));
+        assert_point(razers[1].1, (761.818 + 4.363635, 527.636 - 4.0));
+    }
+
+    #[test]
+    fn pfufu_and_the_starting_point_resolve_to_where_they_used_to() {
+        let registry = location_registry();
+        // the locations used to be the centroid of the first object of their layer
+        let first_object_centroid = |layer: &str| Shape::of(&load_map_layer(layer).objects.unwrap()[0]).centroid();
+
+        for (name, expected) in [("Pfufu", (827.1579872052026, 41.605076574656195)), ("Starting Point", (861.667, 29.66700000000003))] {
+            let location = registry.get(name).unwrap();
+            let layer: Vec<&NamedLocation> = registry.layer(name).collect();
+
+            assert_point(location.position, expected);
+            assert_point(first_object_centroid(name), expected);
+            assert_eq!(layer.len(), 1);
+            assert_point(layer[0].position, expected);
+        }
+
+        assert_point(get_pfufu_location(), (827.1579872052026, 41.605076574656195));
+        assert_point(get_starting_point(), (861.667, 29.66700000000003));
+    }
+
+    #[test]
+    fn unknown_names_resolve_to_nothing() {
+        assert!(location_registry().get("Nowhere").is_none());
+        assert_eq!(location_registry().layer("Nowhere").count(), 0);
+    }
+}
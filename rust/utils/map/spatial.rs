@@ -0,0 +1,383 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::models::{Layer, Map, Object, Point};
+
+use super::{boundary::is_point_inside_polygon, calc::{Bounds, Shape}, load::cached_map};
+
+/// The default size (in map units) of a cell of a `SpatialGrid`, i.e. 2 by 2 tiles.
+pub const DEFAULT_CELL_SIZE: f64 = 64.0;
+
+/// Identifies an entry of a `SpatialGrid`, so that it can be moved or removed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct EntryId(usize);
+
+/// Represents a value found by a `SpatialGrid` query.
+#[derive(Clone, Copy, Debug)]
+pub struct SpatialHit<'a, T> {
+    /// the entry's ID
+    pub id: EntryId,
+    /// the entry's value
+    pub value: &'a T,
+    /// the entry's position
+    pub position: Point,
+    /// the distance between the entry's position and the query's point (0 for polygon queries)
+    pub distance: f64,
+}
+
+/// Represents a map object within the map it was loaded from.
+#[derive(Clone, Copy, Debug)]
+pub struct MapObjectRef<'a> {
+    /// the layer the object belongs to
+    pub layer: &'a Layer,
+    /// the object itself
+    pub object: &'a Object,
+}
+
+/// Represents a uniform grid of square cells over the map, which indexes values by their position so that range queries
+/// only have to look at nearby cells instead of every value.
+///
+/// Values can be anything with a position (e.g. map objects or live entities like enemies); live entities are kept up to date with `move_to`.
+#[derive(Clone, Debug)]
+pub struct SpatialGrid<T> {
+    /// the size (in map units) of each cell
+    cell_size: f64,
+    /// every entry (`None` once removed), indexed by their ID
+    entries: Vec<Option<(Point, T)>>,
+    /// the IDs of the entries within each cell
+    cells: HashMap<(i64, i64), Vec<EntryId>>,
+}
+
+impl<T> Default for SpatialGrid<T> {
+    fn default() -> Self {
+        SpatialGrid::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+impl<T> SpatialGrid<T> {
+    /// Creates an empty grid with cells of `cell_size` by `cell_size` map units.
+    pub fn new(cell_size: f64) -> Self {
+        assert!(cell_size > 0.0, "cell size must be positive");
+
+        SpatialGrid { cell_size, entries: Vec::new(), cells: HashMap::new() }
+    }
+
+    /// Gets the amount of entries in the grid.
+    pub fn len(&self) -> usize {
+        self.cells.values().map(Vec::len).sum()
+    }
+
+    /// Checks whether the grid has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds `value` at `position`.
+    pub fn insert(&mut self, position: Point, value: T) -> EntryId {
+        let id = EntryId(self.entries.len());
+
+        self.entries.push(Some((position, value)));
+        self.cells.entry(self.cell_of(&position)).or_default().push(id);
+
+        id
+    }
+
+    /// Gets the position and value of entry `id` (`None` if it was removed).
+    pub fn get(&self, id: EntryId) -> Option<(Point, &T)> {
+        self.entries.get(id.0)?.as_ref().map(|(position, value)| (*position, value))
+    }
+
+    /// Moves entry `id` to `position`.
+    ///
+    /// Returns whether the entry exists.
+    pub fn move_to(&mut self, id: EntryId, position: Point) -> bool {
+        let Some(Some((current, _))) = self.entries.get(id.0) else {
+            return false;
+        };
+        let (from, to) = (self.cell_of(current), self.cell_of(&position));
+
+        if from != to {
+            self.remove_from_cell(from, id);
+            self.cells.entry(to).or_default().push(id);
+        }
+
+        if let Some(Some((current, _))) = self.entries.get_mut(id.0) {
+            *current = position;
+        }
+
+        true
+    }
+
+    /// Removes entry `id`, returning its value (`None` if it was already removed).
+    pub fn remove(&mut self, id: EntryId) -> Option<T> {
+        let (position, value) = self.entries.get_mut(id.0)?.take()?;
+
+        self.remove_from_cell(self.cell_of(&position), id);
+
+        Some(value)
+    }
+
+    /// Gets every entry within `radius` of `center`, closest first.
+    pub fn within_radius(&self, center: &Point, radius: f64) -> Vec<SpatialHit<'_, T>> {
+        let (min, max) = (self.cell_of(&Point { x: center.x - radius, y: center.y - radius }), self.cell_of(&Point { x: center.x + radius, y: center.y + radius }));
+        let mut hits: Vec<SpatialHit<'_, T>> = self.hits_within(min, max, center)
+            .filter(|hit| hit.distance <= radius)
+            .collect();
+
+        sort_by_distance(&mut hits);
+
+        hits
+    }
+
+    /// Gets the (up to) `count` entries closest to `center`, closest first.
+    pub fn nearest(&self, center: &Point, count: usize) -> Vec<SpatialHit<'_, T>> {
+        let Some((min, max)) = self.occupied_cells() else {
+            return Vec::new();
+        };
+        let origin = self.cell_of(center);
+        // the amount of rings of cells around `origin` that have to be searched to cover every occupied cell
+        let max_ring = [origin.0 - min.0, max.0 - origin.0, origin.1 - min.1, max.1 - origin.1].into_iter().max().unwrap_or_default().max(0);
+        let mut hits = Vec::new();
+
+        for ring in 0..=max_ring {
+            hits.extend(ring_cells(origin, ring).flat_map(|cell| self.hits_in(cell, center)));
+
+            // every entry beyond this ring is at least `ring` cells away from `center`
+            sort_by_distance(&mut hits);
+
+            if count == 0 || hits.get(count - 1).is_some_and(|hit| hit.distance <= ring as f64 * self.cell_size) {
+                break;
+            }
+        }
+
+        hits.truncate(count);
+
+        hits
+    }
+
+    /// Gets every entry inside `polygon` (whose points are in map coordinates).
+    pub fn within_polygon(&self, polygon: &[Point]) -> Vec<SpatialHit<'_, T>> {
+        let Some(bounds) = Bounds::of_points(polygon) else {
+            return Vec::new();
+        };
+
+        self.hits_within(self.cell_of(&bounds.min), self.cell_of(&bounds.max), &bounds.min)
+            .filter(|hit| is_point_inside_polygon(&hit.position, polygon))
+            .map(|hit| SpatialHit { distance: 0.0, ..hit })
+            .collect()
+    }
+
+    /// gets the cell `position` is in
+    fn cell_of(&self, position: &Point) -> (i64, i64) {
+        ((position.x / self.cell_size).floor() as i64, (position.y / self.cell_size).floor() as i64)
+    }
+
+    /// removes `id` from the IDs of `cell`, dropping the cell once it's empty
+    fn remove_from_cell(&mut self, cell: (i64, i64), id: EntryId) {
+        if let Some(ids) = self.cells.get_mut(&cell) {
+            ids.retain(|other| *other != id);
+
+            if ids.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    /// gets the lowest and highest cell coordinates that have entries (`None` if the grid is empty)
+    fn occupied_cells(&self) -> Option<((i64, i64), (i64, i64))> {
+        self.cells.keys().fold(None, |bounds, &(x, y)| match bounds {
+            None => Some(((x, y), (x, y))),
+            Some((min, max)) => Some(((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))),
+        })
+    }
+
+    /// gets the entries of every cell between `min` and `max` (inclusive), measuring their distance to `center`
+    ///
+    /// the range is narrowed down to the occupied cells first, so that large ranges don't visit countless empty cells
+    fn hits_within<'a>(&'a self, min: (i64, i64), max: (i64, i64), center: &Point) -> impl Iterator<Item = SpatialHit<'a, T>> + 'a {
+        let center = *center;
+        let (min, max) = match self.occupied_cells() {
+            Some((occupied_min, occupied_max)) => (
+                (min.0.max(occupied_min.0), min.1.max(occupied_min.1)),
+                (max.0.min(occupied_max.0), max.1.min(occupied_max.1)),
+            ),
+            None => ((0, 0), (-1, -1)),
+        };
+
+        (min.0..=max.0)
+            .flat_map(move |x| (min.1..=max.1).map(move |y| (x, y)))
+            .flat_map(move |cell| self.hits_in(cell, &center))
+    }
+
+    /// gets the entries of `cell`, measuring their distance to `center`
+    fn hits_in<'a>(&'a self, cell: (i64, i64), center: &Point) -> impl Iterator<Item = SpatialHit<'a, T>> + 'a {
+        let center = *center;
+
+        self.cells
+            .get(&cell)
+            .into_iter()
+            .flatten()
+            .filter_map(move |&id| {
+                let (position, value) = self.get(id)?;
+
+                Some(SpatialHit { id, value, position, distance: position.distance_to(&center) })
+            })
+    }
+}
+
+impl<'a> SpatialGrid<MapObjectRef<'a>> {
+    /// Creates a grid of every object of every layer of `map`, positioned at the object's centroid (see `Shape::centroid`).
+    pub fn from_map(map: &'a Map) -> Self {
+        let mut grid = SpatialGrid::default();
+
+        for layer in &map.layers {
+            for object in layer.objects.iter().flatten() {
+                grid.insert(Shape::of(object).centroid(), MapObjectRef { layer, object });
+            }
+        }
+
+        grid
+    }
+}
+
+/// Gets the grid of every object of the (cached) map, which is only built the first time this is called.
+pub fn map_object_index() -> &'static SpatialGrid<MapObjectRef<'static>> {
+    static INDEX: OnceLock<SpatialGrid<MapObjectRef<'static>>> = OnceLock::new();
+
+    INDEX.get_or_init(|| SpatialGrid::from_map(cached_map()))
+}
+
+/// gets the cells exactly `ring` cells away from `origin` (i.e. the border of the square of cells around it), walking only the border
+fn ring_cells(origin: (i64, i64), ring: i64) -> impl Iterator<Item = (i64, i64)> {
+    let (x, y) = origin;
+    let bottom = (-ring..=ring).map(move |dx| (x + dx, y - ring));
+    // ring 0 is only `origin`, which the bottom row already covers
+    let top = (-ring..=ring).filter(move |_| ring > 0).map(move |dx| (x + dx, y + ring));
+    let sides = (1 - ring..ring).flat_map(move |dy| [(x - ring, y + dy), (x + ring, y + dy)]);
+
+    bottom.chain(top).chain(sides)
+}
+
+/// sorts hits by their distance, closest first
+fn sort_by_distance<T>(hits: &mut [SpatialHit<'_, T>]) {
+    hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::utils::GameRng;
+
+    use super::*;
+
+    /// a grid of 500 points scattered over (-300, -300) to (300, 300), along with the points themselves
+    fn scattered_grid() -> (SpatialGrid<usize>, Vec<Point>) {
+        let mut rng = GameRng::new(17);
+        let points: Vec<Point> = (0..500).map(|_| Point { x: rng.range(-300.0, 300.0), y: rng.range(-300.0, 300.0) }).collect();
+        let mut grid = SpatialGrid::new(32.0);
+
+        for (index, point) in points.iter().enumerate() {
+            grid.insert(*point, index);
+        }
+
+        (grid, points)
+    }
+
+    /// gets the indices of every point matching `filter`, closest to `center` first
+    fn brute_force(points: &[Point], center: &Point, filter: impl Fn(&Point) -> bool) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..points.len()).filter(|&index| filter(&points[index])).collect();
+
+        indices.sort_by(|&a, &b| points[a].distance_to(center).total_cmp(&points[b].distance_to(center)));
+
+        indices
+    }
+
+    fn values<T: Copy>(hits: &[SpatialHit<'_, T>]) -> Vec<T> {
+        hits.iter().map(|hit| *hit.value).collect()
+    }
+
+    #[test]
+    fn nearest_matches_a_brute_force_scan() {
+        let (grid, points) = scattered_grid();
+
+        for center in [Point { x: 0.0, y: 0.0 }, Point { x: 250.0, y: -290.0 }, Point { x: -1000.0, y: 40.0 }] {
+            let expected = brute_force(&points, &center, |_| true);
+
+            for count in [0, 1, 7, 50, 500, 600] {
+                let hits = grid.nearest(&center, count);
+
+                assert_eq!(values(&hits), expected[..count.min(points.len())], "{} nearest to {:?}", count, center);
+                assert!(hits.windows(2).all(|pair| pair[0].distance <= pair[1].distance));
+            }
+        }
+    }
+
+    #[test]
+    fn within_radius_matches_a_brute_force_scan() {
+        let (grid, points) = scattered_grid();
+
+        for (center, radius) in [(Point { x: 10.0, y: -20.0 }, 75.0), (Point { x: -300.0, y: 300.0 }, 200.0), (Point { x: 0.0, y: 0.0 }, 0.0)] {
+            let expected = brute_force(&points, &center, |point| point.distance_to(&center) <= radius);
+
+            assert_eq!(values(&grid.within_radius(&center, radius)), expected);
+        }
+    }
+
+    #[test]
+    fn within_radius_includes_entries_exactly_on_the_boundary() {
+        let mut grid = SpatialGrid::new(10.0);
+
+        grid.insert(Point { x: 30.0, y: 40.0 }, "on the boundary");
+        grid.insert(Point { x: 30.0, y: 40.001 }, "just outside");
+        grid.insert(Point { x: -3.0, y: -4.0 }, "inside");
+
+        assert_eq!(values(&grid.within_radius(&Point { x: 0.0, y: 0.0 }, 50.0)), ["inside", "on the boundary"]);
+    }
+
+    #[test]
+    fn within_polygon_matches_a_brute_force_scan() {
+        let (grid, points) = scattered_grid();
+        // a concave arrowhead pointing right
+        let polygon = [(-200.0, -150.0), (250.0, 0.0), (-200.0, 150.0), (-50.0, 0.0)].map(|(x, y)| Point { x, y });
+        let mut expected: Vec<usize> = (0..points.len()).filter(|&index| is_point_inside_polygon(&points[index], &polygon)).collect();
+        let mut actual = values(&grid.within_polygon(&polygon));
+
+        expected.sort();
+        actual.sort();
+
+        assert!(!expected.is_empty());
+        assert_eq!(actual, expected);
+        assert!(grid.within_polygon(&polygon).iter().all(|hit| hit.distance == 0.0));
+    }
+
+    #[test]
+    fn moved_and_removed_entries_are_found_where_they_are() {
+        let mut grid = SpatialGrid::new(10.0);
+        let first = grid.insert(Point { x: 0.0, y: 0.0 }, 1);
+        let second = grid.insert(Point { x: 5.0, y: 5.0 }, 2);
+
+        assert!(grid.move_to(first, Point { x: 100.0, y: 100.0 }));
+        assert_eq!(grid.remove(second), Some(2));
+        assert_eq!(grid.remove(second), None);
+        assert!(!grid.move_to(second, Point { x: 0.0, y: 0.0 }));
+
+        assert_eq!(grid.len(), 1);
+        assert!(grid.within_radius(&Point { x: 0.0, y: 0.0 }, 20.0).is_empty());
+        assert_eq!(values(&grid.nearest(&Point { x: 0.0, y: 0.0 }, 5)), [1]);
+    }
+
+    #[test]
+    fn ring_cells_are_the_border_of_the_square() {
+        for ring in 0..6 {
+            let cells: Vec<(i64, i64)> = ring_cells((3, -2), ring).collect();
+            let border: HashSet<(i64, i64)> = (-ring..=ring)
+                .flat_map(|dx| (-ring..=ring).map(move |dy| (dx, dy)))
+                .filter(|&(dx, dy)| dx.abs() == ring || dy.abs() == ring)
+                .map(|(dx, dy)| (3 + dx, -2 + dy))
+                .collect();
+
+            assert_eq!(cells.len(), if ring == 0 { 1 } else { 8 * ring as usize });
+            assert_eq!(cells.into_iter().collect::<HashSet<_>>(), border);
+        }
+    }
+}
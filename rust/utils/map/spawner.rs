@@ -0,0 +1,336 @@
+use std::fmt;
+
+use crate::{
+    models::{Enemy, Layer, Map, Point},
+    utils::{GameCatalog, GameClock},
+};
+
+use super::{calc::Shape, registry::NamedLocation, spatial::SpatialGrid};
+
+/// The name of the map layer whose objects are the enemies' spawn points.
+pub const ENEMIES_LAYER: &str = "Enemies";
+
+/// The name of the custom object property that sets the level of the enemy spawning at an object.
+pub const LEVEL_PROPERTY: &str = "level";
+
+/// Represents an area of the map in which enemies spawn at a specific level.
+#[derive(Clone, Debug)]
+pub struct SpawnZone {
+    /// the zone's name
+    pub name: String,
+    /// the zone's shape
+    pub shape: Shape,
+    /// the level of the enemies spawning within the zone
+    pub level: u8,
+}
+
+impl SpawnZone {
+    /// Creates a zone covering the shape of `location` in which enemies spawn at level `level`.
+    pub fn from_location(location: &NamedLocation, level: u8) -> Self {
+        SpawnZone { name: location.name.clone(), shape: location.shape.clone(), level }
+    }
+}
+
+/// Represents how the spawn points of the map are turned into enemies.
+#[derive(Clone, Debug)]
+pub struct SpawnConfig {
+    /// the level of enemies spawning outside of every zone
+    pub default_level: u8,
+    /// the zones setting the level of the enemies spawning within them (the first zone containing a spawn point wins)
+    pub zones: Vec<SpawnZone>,
+    /// how long (in seconds) a defeated enemy takes to respawn
+    pub respawn_time: u8,
+}
+
+impl Default for SpawnConfig {
+    /// Spawns every enemy at level 1, respawning 30 seconds after being defeated (the mechanics files don't define respawn times yet).
+    fn default() -> Self {
+        SpawnConfig { default_level: 1, zones: Vec::new(), respawn_time: 30 }
+    }
+}
+
+impl SpawnConfig {
+    /// Gets the level of enemies spawning at `position` (i.e. the level of the first zone containing it, or `default_level`).
+    pub fn level_at(&self, position: &Point) -> u8 {
+        self.zones
+            .iter()
+            .find(|zone| zone.shape.contains(position))
+            .map_or(self.default_level, |zone| zone.level)
+    }
+}
+
+/// Represents a spot on the map where an enemy spawns.
+#[derive(Clone, Debug)]
+pub struct SpawnPoint {
+    /// the name of the enemy that spawns
+    pub enemy: String,
+    /// where the enemy spawns (the centroid of the spawn point's object)
+    pub position: Point,
+    /// the level the enemy spawns at
+    pub level: u8,
+}
+
+/// Represents why the spawn points couldn't be turned into enemies.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SpawnError {
+    /// the map has no layer with the enemies' spawn points
+    MissingLayer(String),
+    /// a spawn point's enemy doesn't exist in the catalog
+    UnknownEnemy { enemy: String },
+    /// a spawn point's enemy has no mechanics for the level it spawns at
+    UnknownLevel { enemy: String, level: u8 },
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::MissingLayer(layer) => write!(f, "the map has no `{}` layer", layer),
+            SpawnError::UnknownEnemy { enemy } => write!(f, "enemy `{}` doesn't exist", enemy),
+            SpawnError::UnknownLevel { enemy, level } => write!(f, "enemy `{}` has no level {}", enemy, level),
+        }
+    }
+}
+
+impl std::error::Error for SpawnError {}
+
+/// Gets the spawn point of every object of `layer` (normally the `Enemies` layer), where the object's name is the enemy's name.
+///
+/// An enemy spawns at the level set by the object's `level` property, or else at the level `config` gives its position.
+pub fn spawn_table(layer: &Layer, config: &SpawnConfig) -> Vec<SpawnPoint> {
+    layer.objects
+        .iter()
+        .flatten()
+        .map(|object| {
+            let position = Shape::of(object).centroid();
+            let level = object.property(LEVEL_PROPERTY)
+                .and_then(|value| value.as_u64())
+                .and_then(|level| u8::try_from(level).ok())
+                .unwrap_or_else(|| config.level_at(&position));
+
+            SpawnPoint { enemy: object.name.clone(), position, level }
+        })
+        .collect()
+}
+
+/// Represents every enemy of the map, which respawn over time once defeated.
+#[derive(Clone, Debug)]
+pub struct EnemySpawner {
+    /// every enemy, in the order of their spawn points
+    enemies: Vec<Enemy>,
+}
+
+impl EnemySpawner {
+    /// Creates an enemy for every spawn point of `table`, which respawns `respawn_time` seconds after being defeated.
+    pub fn new(table: &[SpawnPoint], catalog: &GameCatalog, respawn_time: u8) -> Result<Self, SpawnError> {
+        let enemies = table
+            .iter()
+            .map(|spawn| {
+                let definition = catalog.enemy(&spawn.enemy).ok_or_else(|| SpawnError::UnknownEnemy { enemy: spawn.enemy.clone() })?;
+
+                definition
+                    .at_level(spawn.level, spawn.position, respawn_time)
+                    .ok_or_else(|| SpawnError::UnknownLevel { enemy: spawn.enemy.clone(), level: spawn.level })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(EnemySpawner { enemies })
+    }
+
+    /// Creates an enemy for every spawn point of the `Enemies` layer of `map` (see `spawn_table`).
+    pub fn from_map(map: &Map, catalog: &GameCatalog, config: &SpawnConfig) -> Result<Self, SpawnError> {
+        let layer = map.layers
+            .iter()
+            .find(|layer| layer.name == ENEMIES_LAYER)
+            .ok_or_else(|| SpawnError::MissingLayer(ENEMIES_LAYER.to_string()))?;
+
+        EnemySpawner::new(&spawn_table(layer, config), catalog, config.respawn_time)
+    }
+
+    /// Gets every enemy (alive or not), in the order of their spawn points.
+    pub fn enemies(&self) -> &[Enemy] {
+        &self.enemies
+    }
+
+    /// Gets every enemy (alive or not) mutably, e.g. to run a fight with them.
+    pub fn enemies_mut(&mut self) -> &mut [Enemy] {
+        &mut self.enemies
+    }
+
+    /// Gets every enemy that is alive, with its index.
+    pub fn alive(&self) -> impl Iterator<Item = (usize, &Enemy)> {
+        self.enemies.iter().enumerate().filter(|(_, enemy)| !enemy.live_state.is_dead)
+    }
+
+    /// Creates a spatial index of every enemy that is alive at its current position, with the enemy's index as the value.
+    ///
+    /// The index is a snapshot, so it has to be recreated (or moved with `SpatialGrid::move_to`) as enemies move, die and respawn.
+    pub fn alive_index(&self) -> SpatialGrid<usize> {
+        let mut grid = SpatialGrid::default();
+
+        for (index, enemy) in self.alive() {
+            grid.insert(enemy.live_state.current_position, index);
+        }
+
+        grid
+    }
+
+    /// Gets the index and position of every enemy that is alive at `clock`'s current time, respawning the enemies that are due first (see `update`).
+    pub fn alive_at(&mut self, clock: &impl GameClock) -> Vec<(usize, Point)> {
+        self.update(clock);
+
+        self.alive().map(|(index, enemy)| (index, enemy.live_state.current_position)).collect()
+    }
+
+    /// Marks the enemy at `index` as defeated at `clock`'s current time.
+    ///
+    /// Returns the enemy if it was alive.
+    pub fn kill(&mut self, index: usize, clock: &impl GameClock) -> Option<&Enemy> {
+        let enemy = self.enemies.get_mut(index).filter(|enemy| !enemy.live_state.is_dead)?;
+
+        enemy.die(clock.now());
+
+        Some(enemy)
+    }
+
+    /// Respawns every defeated enemy whose `respawn_time` has passed at `clock`'s current time.
+    ///
+    /// Returns the indices of the enemies that respawned.
+    pub fn update(&mut self, clock: &impl GameClock) -> Vec<usize> {
+        let now = clock.now();
+        let mut respawned = Vec::new();
+
+        for (index, enemy) in self.enemies.iter_mut().enumerate() {
+            if enemy.can_respawn(now) {
+                enemy.respawn();
+                respawned.push(index);
+            }
+        }
+
+        respawned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::{GameTime, Object, ObjectProperty},
+        utils::{cached_map, load_catalog, Bounds, SimulatedClock, CATALOG_ROOT},
+    };
+
+    use super::*;
+
+    /// a 2 by 2 spawn point of `enemy` with its top left corner at (`x`, `y`), at level `level` if given
+    fn spawn_object(enemy: &str, x: f64, y: f64, level: Option<u8>) -> Object {
+        Object {
+            name: enemy.to_string(),
+            ellipse: None,
+            height: 2.0,
+            width: 2.0,
+            polygon: None,
+            x,
+            y,
+            properties: level.map(|level| vec![ObjectProperty { name: LEVEL_PROPERTY.to_string(), property_type: "int".to_string(), value: level.into() }]),
+        }
+    }
+
+    /// a config spawning enemies at level 2 within (0, 0) to (10, 10) and at level 1 elsewhere
+    fn zoned_config() -> SpawnConfig {
+        let zone = Shape::Rectangle(Bounds { min: Point { x: 0.0, y: 0.0 }, max: Point { x: 10.0, y: 10.0 } });
+
+        SpawnConfig { zones: vec![SpawnZone { name: "Camp".to_string(), shape: zone, level: 2 }], ..SpawnConfig::default() }
+    }
+
+    fn map_spawner() -> EnemySpawner {
+        EnemySpawner::from_map(cached_map(), &load_catalog(CATALOG_ROOT).unwrap(), &SpawnConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn levels_come_from_the_property_then_the_zone() {
+        let layer = Layer {
+            name: ENEMIES_LAYER.to_string(),
+            objects: Some(vec![
+                spawn_object("Trufo", 4.0, 6.0, None),
+                spawn_object("Trufo", 4.0, 6.0, Some(3)),
+                spawn_object("Golem", 40.0, 60.0, None),
+                spawn_object("Golem", 40.0, 60.0, Some(3)),
+            ]),
+        };
+        let table = spawn_table(&layer, &zoned_config());
+        let spawns: Vec<(&str, u8)> = table.iter().map(|spawn| (spawn.enemy.as_str(), spawn.level)).collect();
+
+        assert_eq!(spawns, [("Trufo", 2), ("Trufo", 3), ("Golem", 1), ("Golem", 3)]);
+        // spawn points are the centroids of their objects
+        assert_eq!((table[0].position.x, table[0].position.y), (5.0, 5.0));
+    }
+
+    #[test]
+    fn unknown_enemies_and_levels_are_rejected() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let spawn = |enemy: &str, level: u8| SpawnPoint { enemy: enemy.to_string(), position: Point { x: 0.0, y: 0.0 }, level };
+
+        assert_eq!(EnemySpawner::new(&[spawn("Dragon", 1)], &catalog, 30).unwrap_err(), SpawnError::UnknownEnemy { enemy: "Dragon".to_string() });
+        assert_eq!(
+            EnemySpawner::new(&[spawn("Golem", 0)], &catalog, 30).unwrap_err(),
+            SpawnError::UnknownLevel { enemy: "Golem".to_string(), level: 0 }
+        );
+    }
+
+    #[test]
+    fn every_enemy_of_the_map_spawns_alive_at_level_1() {
+        let spawner = map_spawner();
+
+        assert_eq!(spawner.enemies().len(), 86);
+        assert_eq!(spawner.alive().count(), 86);
+        assert_eq!(spawner.alive_index().len(), 86);
+        assert!(spawner.enemies().iter().all(|enemy| enemy.level == 1 && enemy.respawn_time == 30));
+    }
+
+    #[test]
+    fn defeated_enemies_respawn_after_30_seconds_by_default() {
+        let mut spawner = map_spawner();
+        let mut clock = SimulatedClock::new(GameTime::from_seconds(10.0));
+        let spawn_position = spawner.enemies()[4].spawn_position;
+
+        spawner.enemies_mut()[4].live_state.current_position = Point { x: 0.0, y: 0.0 };
+
+        assert!(spawner.kill(4, &clock).is_some());
+        assert!(spawner.kill(4, &clock).is_none());
+        assert!(spawner.enemies()[4].live_state.is_dead);
+        assert_eq!(spawner.alive().count(), 85);
+
+        clock.advance(29.9);
+        assert!(spawner.update(&clock).is_empty());
+
+        clock.advance(0.1);
+        assert_eq!(spawner.update(&clock), [4]);
+
+        let enemy = &spawner.enemies()[4];
+
+        assert!(!enemy.live_state.is_dead);
+        assert_eq!(enemy.live_state.current_hp, enemy.base_hp);
+        assert_eq!((enemy.live_state.current_position.x, enemy.live_state.current_position.y), (spawn_position.x, spawn_position.y));
+    }
+
+    #[test]
+    fn alive_at_respawns_the_enemies_that_are_due() {
+        let mut spawner = map_spawner();
+        let mut clock = SimulatedClock::default();
+
+        spawner.kill(0, &clock);
+        clock.advance(15.0);
+        spawner.kill(1, &clock);
+
+        let alive: Vec<usize> = spawner.alive_at(&clock).into_iter().map(|(index, _)| index).collect();
+        assert_eq!(alive.len(), 84);
+        assert!(!alive.contains(&0) && !alive.contains(&1));
+
+        clock.advance(15.0);
+        let alive = spawner.alive_at(&clock);
+        assert_eq!(alive.len(), 85);
+        assert_eq!(alive[0].0, 0);
+        assert_eq!((alive[0].1.x, alive[0].1.y), (spawner.enemies()[0].spawn_position.x, spawner.enemies()[0].spawn_position.y));
+
+        clock.advance(15.0);
+        assert_eq!(spawner.alive_at(&clock).len(), 86);
+    }
+}
@@ -1,5 +1,23 @@
 pub mod map;
 pub mod serialization;
+pub mod catalog;
+pub mod combat;
+pub mod clock;
+pub mod rng;
+pub mod analytics;
+pub mod generation;
+pub mod economy;
+pub mod quests;
+pub mod events;
 
 pub use map::*;
-pub use serialization::*;
\ No newline at end of file
+pub use serialization::*;
+pub use catalog::*;
+pub use combat::*;
+pub use clock::*;
+pub use rng::*;
+pub use analytics::*;
+pub use generation::*;
+pub use economy::*;
+pub use quests::*;
+pub use events::*;
\ No newline at end of file
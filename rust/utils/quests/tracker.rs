@@ -0,0 +1,398 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    models::{LevelUp, MiscReward, Player, Quest, QuestProgress, QuestTarget, Resource},
+    utils::{EventBus, GameCatalog, GameEvent, SubscriptionId},
+};
+
+/// A list of everything that happens in the game that can make progress towards a quest.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum QuestEvent {
+    /// the player defeated an enemy
+    EnemyKilled { enemy: String },
+    /// the player obtained `amount` of an item
+    ItemGained { item: String, amount: u32 },
+    /// the player obtained `amount` of a resource
+    ResourceGained { resource: Resource, amount: u32 },
+    /// the player entered an area
+    AreaEntered { area: String },
+    /// the player talked to an NPC
+    NpcTalkedTo { npc: String },
+}
+
+impl QuestEvent {
+    /// Gets the quest event `event` amounts to (`None` if it can't make progress towards a quest).
+    ///
+    /// Entering a breathable area counts as entering an area.
+    pub fn from_game_event(event: &GameEvent) -> Option<Self> {
+        match event {
+            GameEvent::EnemyKilled { enemy, .. } => Some(QuestEvent::EnemyKilled { enemy: enemy.clone() }),
+            GameEvent::ItemPickedUp { item, amount } => Some(QuestEvent::ItemGained { item: item.clone(), amount: *amount }),
+            GameEvent::ResourceGathered { resource, amount } => Some(QuestEvent::ResourceGained { resource: *resource, amount: *amount }),
+            GameEvent::AreaEntered { area } | GameEvent::BreathableAreaEntered { area } => Some(QuestEvent::AreaEntered { area: area.clone() }),
+            GameEvent::NpcTalkedTo { npc } => Some(QuestEvent::NpcTalkedTo { npc: npc.clone() }),
+            _ => None,
+        }
+    }
+}
+
+/// Represents a weapon or skill the player was given as a quest reward.
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+pub enum QuestUnlock {
+    Weapon { name: String, level: u8 },
+    Skill { name: String, level: u8 },
+}
+
+/// Represents a quest the player completed.
+#[derive(Clone, Debug)]
+pub struct QuestCompletion {
+    /// the chapter of the quest
+    pub chapter: u8,
+    /// the ID of the quest within its chapter
+    pub id: u8,
+    /// the name of the quest
+    pub name: String,
+    /// the level up the quest's XP caused (if any)
+    pub level_up: Option<LevelUp>,
+    /// the weapons and skills the quest gave the player
+    pub unlocks: Vec<QuestUnlock>,
+}
+
+/// Represents why a quest event couldn't be recorded.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum QuestError {
+    /// a completed quest rewards a weapon or skill (at a level) that doesn't exist in the catalog
+    UnknownReward { chapter: u8, id: u8, reward_type: MiscReward, name: String, level: u8 },
+}
+
+impl fmt::Display for QuestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuestError::UnknownReward { chapter, id, reward_type, name, level } => write!(
+                f,
+                "quest {}-{} rewards {:?} `{}` level {}, which doesn't exist",
+                chapter, id, reward_type, name, level
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QuestError {}
+
+/// Gets the quest the player is currently working towards (`None` once every quest is completed; see `QuestLog::active_quest`).
+pub fn active_quest<'a>(player: &Player, catalog: &'a GameCatalog) -> Option<&'a Quest> {
+    player.quest_log.active_quest(&catalog.quests)
+}
+
+/// Checks whether the player completed the (first) quest with the description `description`.
+pub fn is_quest_completed(player: &Player, catalog: &GameCatalog, description: &str) -> bool {
+    catalog.quests
+        .iter()
+        .find(|quest| quest.description == description)
+        .is_some_and(|quest| player.quest_log.is_completed(quest))
+}
+
+/// Records `event` in the player's quest log, completing the active quest once all of its requirements are met.
+///
+/// Completing a quest grants its rewards (see `Player::claim_quest`), gives the player the weapons and skills it rewards
+/// and unlocks the next quest by chapter, then by ID. Since kills count towards `killsRequired` across quests,
+/// several quests can be completed by a single event.
+///
+/// Quests without a type are completed by entering the area or talking to the NPC their description names (see `Quest::target`)
+/// while they are active.
+///
+/// Returns the quests that were completed, in order. Nothing changes if a quest's rewards can't be granted.
+pub fn record_quest_event(player: &mut Player, catalog: &GameCatalog, event: &QuestEvent) -> Result<Vec<QuestCompletion>, QuestError> {
+    let mut log = player.quest_log.clone();
+
+    if let QuestEvent::EnemyKilled { .. } = event {
+        log.total_kills = log.total_kills.saturating_add(1);
+    }
+
+    if let Some(quest) = log.active_quest(&catalog.quests) {
+        let progress = &mut log.progress;
+
+        match event {
+            QuestEvent::EnemyKilled { enemy } => *progress.enemies.entry(enemy.clone()).or_default() += 1,
+            QuestEvent::ItemGained { item, amount } => *progress.items.entry(item.clone()).or_default() += amount,
+            QuestEvent::ResourceGained { resource, amount } => *progress.resources.entry(*resource).or_default() += amount,
+            QuestEvent::AreaEntered { area } => progress.reached |= quest.target() == Some(QuestTarget::Area(area.clone())),
+            QuestEvent::NpcTalkedTo { npc } => progress.reached |= quest.target() == Some(QuestTarget::Npc(npc.clone())),
+        }
+    }
+
+    let mut completed = Vec::new();
+
+    while let Some(quest) = log.active_quest(&catalog.quests) {
+        if !log.progress.meets(quest, log.total_kills) {
+            break;
+        }
+
+        for misc in quest.completion_rewards.misc.iter().flatten() {
+            check_reward(catalog, quest, misc.reward_type, &misc.name, misc.level)?;
+        }
+
+        completed.push(quest);
+        log.completed.insert((quest.chapter, quest.id));
+        log.progress = QuestProgress::default();
+    }
+
+    player.quest_log = log;
+
+    Ok(completed.into_iter().map(|quest| complete_quest(player, catalog, quest)).collect())
+}
+
+/// Subscribes the player's quest log to `bus`: every event that can make progress towards a quest (see `QuestEvent::from_game_event`)
+/// is recorded with `record_quest_event`, and every quest it completes is published as a `QuestCompleted` event
+/// (followed by a `LevelUp` event if the quest's XP levelled the player up).
+///
+/// An event that would complete a quest whose rewards can't be granted changes nothing (`validate` reports such quests).
+pub fn subscribe_quest_log(bus: &mut EventBus) -> SubscriptionId {
+    bus.subscribe(|event, context| {
+        let Some(event) = QuestEvent::from_game_event(event) else {
+            return;
+        };
+        let Ok(completions) = record_quest_event(context.player, context.catalog, &event) else {
+            return;
+        };
+
+        for completion in completions {
+            context.publish(GameEvent::QuestCompleted { chapter: completion.chapter, id: completion.id });
+
+            if let Some(level_up) = completion.level_up {
+                context.publish(GameEvent::LevelUp(level_up));
+            }
+        }
+    })
+}
+
+/// checks whether the weapon or skill a quest rewards exists at the rewarded level (currencies always exist)
+fn check_reward(catalog: &GameCatalog, quest: &Quest, reward_type: MiscReward, name: &str, level: u8) -> Result<(), QuestError> {
+    let exists = match reward_type {
+        MiscReward::Weapon => catalog.weapon(name).and_then(|weapon| weapon.level_mechanics(level)).is_some(),
+        MiscReward::Skill => catalog.skill(name).and_then(|skill| skill.level_mechanics(level)).is_some(),
+        _ => true,
+    };
+
+    if !exists {
+        return Err(QuestError::UnknownReward { chapter: quest.chapter, id: quest.id, reward_type, name: name.to_string(), level });
+    }
+
+    Ok(())
+}
+
+/// grants the rewards of a quest whose rewards were already checked
+fn complete_quest(player: &mut Player, catalog: &GameCatalog, quest: &Quest) -> QuestCompletion {
+    let level_up = player.claim_quest(quest, &catalog.player_levels);
+    let mut unlocks = Vec::new();
+
+    for misc in quest.completion_rewards.misc.iter().flatten() {
+        let (name, level) = (misc.name.clone(), misc.level);
+
+        match misc.reward_type {
+            MiscReward::Weapon => {
+                let Some(weapon) = catalog.weapon(&name).and_then(|weapon| weapon.at_level(level)) else {
+                    continue;
+                };
+                let weapons = &mut player.inventory.weapons;
+
+                match weapons.iter_mut().find(|owned| owned.name == name) {
+                    Some(owned) if owned.level >= level => continue,
+                    Some(owned) => *owned = weapon,
+                    None => weapons.push(weapon),
+                }
+
+                unlocks.push(QuestUnlock::Weapon { name, level });
+            }
+            MiscReward::Skill => {
+                let Some(skill) = catalog.skill(&name).and_then(|skill| skill.at_level(level)) else {
+                    continue;
+                };
+                let skills = &mut player.inventory.skills;
+
+                match skills.iter_mut().find(|owned| owned.name == name) {
+                    Some(owned) if owned.level >= level => continue,
+                    Some(owned) => *owned = skill,
+                    None => skills.push(skill),
+                }
+
+                unlocks.push(QuestUnlock::Skill { name, level });
+            }
+            _ => {}
+        }
+    }
+
+    QuestCompletion { chapter: quest.chapter, id: quest.id, name: quest.name.clone(), level_up, unlocks }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{load_catalog, CATALOG_ROOT};
+
+    use super::*;
+
+    /// a level 1 player working on the quest at `index` (in the order quests are defined, which is by chapter, then by ID)
+    fn player_on_quest(catalog: &GameCatalog, index: usize) -> Player {
+        let mut player = Player::new(&catalog.player_levels[0], &catalog.player_levels);
+        player.quest_log.completed = catalog.quests[..index].iter().map(|quest| (quest.chapter, quest.id)).collect();
+
+        player
+    }
+
+    /// the chapter and ID of the player's active quest
+    fn active(player: &Player, catalog: &GameCatalog) -> Option<(u8, u8)> {
+        active_quest(player, catalog).map(|quest| (quest.chapter, quest.id))
+    }
+
+    /// the events that complete `quest` once it is active, for a player who has defeated `total_kills` enemies so far
+    fn events_completing(quest: &Quest, total_kills: u32) -> Vec<QuestEvent> {
+        let Some(quest_type) = &quest.quest_type else {
+            return match quest.target().unwrap() {
+                QuestTarget::Area(area) => vec![enter(&area)],
+                QuestTarget::Npc(npc) => vec![talk_to(&npc)],
+            };
+        };
+        let mut events = Vec::new();
+
+        for required in quest_type.items_required.iter().flatten() {
+            events.push(QuestEvent::ItemGained { item: required.item.clone(), amount: required.amount });
+        }
+
+        for required in quest_type.resources_required.iter().flatten() {
+            events.push(QuestEvent::ResourceGained { resource: required.resource, amount: required.amount });
+        }
+
+        for required in quest_type.enemies_required.iter().flatten() {
+            events.extend((0..required.amount).map(|_| QuestEvent::EnemyKilled { enemy: required.enemy.clone() }));
+        }
+
+        let kills = total_kills + events.iter().filter(|event| matches!(event, QuestEvent::EnemyKilled { .. })).count() as u32;
+        let missing_kills = quest_type.kills_required.map_or(0, |kills_required| kills_required.saturating_sub(kills));
+        events.extend((0..missing_kills).map(|_| QuestEvent::EnemyKilled { enemy: "Trufo".to_string() }));
+
+        events
+    }
+
+    /// completes every quest of `catalog` one after the other and returns the chapter and ID of each completed quest, in order
+    fn walk_quests(catalog: &GameCatalog) -> Vec<(u8, u8)> {
+        let mut player = player_on_quest(catalog, 0);
+        let mut walked = Vec::new();
+
+        while let Some(quest) = active_quest(&player, catalog) {
+            let events = events_completing(quest, player.quest_log.total_kills);
+            let (last, rest) = events.split_last().unwrap();
+
+            for event in rest {
+                assert!(record_quest_event(&mut player, catalog, event).unwrap().is_empty(), "`{}` completed too early", quest.description);
+            }
+
+            let completed = record_quest_event(&mut player, catalog, last).unwrap();
+
+            assert_eq!(completed.len(), 1, "`{}` wasn't completed on its own", quest.description);
+            walked.push((completed[0].chapter, completed[0].id));
+        }
+
+        assert_eq!(player.quest_log.completed.len(), walked.len());
+        assert!(player.inventory.skills.iter().any(|skill| skill.name == "Restorative Touch"));
+
+        walked
+    }
+
+    fn talk_to(npc: &str) -> QuestEvent {
+        QuestEvent::NpcTalkedTo { npc: npc.to_string() }
+    }
+
+    fn enter(area: &str) -> QuestEvent {
+        QuestEvent::AreaEntered { area: area.to_string() }
+    }
+
+    #[test]
+    fn reads_the_target_of_quests_without_a_type() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let target = |description: &str| catalog.quests.iter().find(|quest| quest.description == description).unwrap().target();
+
+        assert_eq!(target("Talk to Pfufu"), Some(QuestTarget::Npc("Pfufu".to_string())));
+        assert_eq!(target("Find Razer"), Some(QuestTarget::Npc("Razer".to_string())));
+        assert_eq!(target("Reach Beacon 1"), Some(QuestTarget::Area("Beacon 1".to_string())));
+        assert_eq!(target("Feed Pfufu 6 Berries"), None);
+    }
+
+    #[test]
+    fn only_the_named_npc_completes_a_talk_quest() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_on_quest(&catalog, 0);
+
+        assert!(record_quest_event(&mut player, &catalog, &talk_to("Razer")).unwrap().is_empty());
+        assert!(record_quest_event(&mut player, &catalog, &enter("Pfufu")).unwrap().is_empty());
+        assert_eq!(active(&player, &catalog), Some((1, 1)));
+
+        let completed = record_quest_event(&mut player, &catalog, &talk_to("Pfufu")).unwrap();
+
+        assert_eq!(completed.iter().map(|completion| (completion.chapter, completion.id)).collect::<Vec<_>>(), vec![(1, 1)]);
+        assert_eq!(active(&player, &catalog), Some((1, 2)));
+        assert_eq!(player.quest_log.completed.iter().copied().collect::<Vec<_>>(), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn only_the_named_area_completes_a_reach_quest() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_on_quest(&catalog, 4);
+
+        for event in [enter("Beacon 1 Breathable Area 2"), enter("Beacon 2"), talk_to("Beacon 1")] {
+            assert!(record_quest_event(&mut player, &catalog, &event).unwrap().is_empty());
+        }
+
+        assert!(!player.quest_log.progress.reached);
+        assert_eq!(record_quest_event(&mut player, &catalog, &enter("Beacon 1")).unwrap().len(), 1);
+        assert_eq!(active(&player, &catalog), Some((2, 2)));
+    }
+
+    #[test]
+    fn entering_areas_doesnt_complete_quests_with_requirements() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_on_quest(&catalog, 1);
+
+        assert!(record_quest_event(&mut player, &catalog, &enter("Beacon 1")).unwrap().is_empty());
+        assert!(record_quest_event(&mut player, &catalog, &QuestEvent::ItemGained { item: "Blueberry".to_string(), amount: 5 }).unwrap().is_empty());
+        assert_eq!(record_quest_event(&mut player, &catalog, &QuestEvent::ItemGained { item: "Blueberry".to_string(), amount: 1 }).unwrap().len(), 1);
+        assert_eq!(active(&player, &catalog), Some((1, 3)));
+    }
+
+    #[test]
+    fn the_quest_log_completes_quests_through_the_event_bus() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let mut player = player_on_quest(&catalog, 0);
+        let mut bus = EventBus::new();
+        let mut events = bus.subscribe_async();
+
+        subscribe_quest_log(&mut bus);
+        bus.publish(GameEvent::NpcTalkedTo { npc: "Pfufu".to_string() }, &mut player, &catalog);
+
+        assert_eq!(active(&player, &catalog), Some((1, 2)));
+        assert_eq!(events.try_recv(), Ok(GameEvent::NpcTalkedTo { npc: "Pfufu".to_string() }));
+        assert_eq!(events.try_recv(), Ok(GameEvent::QuestCompleted { chapter: 1, id: 1 }));
+    }
+
+    #[test]
+    fn walks_every_quest_once_by_chapter_and_id() {
+        let catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let walked = walk_quests(&catalog);
+        let mut expected: Vec<(u8, u8)> = catalog.quests.iter().map(|quest| (quest.chapter, quest.id)).collect();
+        expected.sort();
+        expected.dedup();
+
+        assert_eq!(walked, expected);
+        assert_eq!(walked.len(), catalog.quests.len());
+    }
+
+    #[test]
+    fn quests_unlock_by_chapter_and_id_regardless_of_their_order() {
+        let mut catalog = load_catalog(CATALOG_ROOT).unwrap();
+        let in_order = walk_quests(&catalog);
+        catalog.quests.reverse();
+
+        assert_eq!(walk_quests(&catalog), in_order);
+    }
+}
@@ -0,0 +1,149 @@
+/// The single source of randomness for every probabilistic mechanic (crits, misses, attribute and skill procs).
+/// 
+/// The generator is seeded, so a simulation run can be replayed exactly from its seed. It implements xoshiro256**,
+/// seeded through SplitMix64, so the sequence of numbers for a given seed never changes between versions or platforms.
+#[derive(Clone, Debug)]
+pub struct GameRng {
+    /// the seed the generator was created with
+    seed: u64,
+    /// the generator's internal state
+    state: [u64; 4],
+}
+
+impl GameRng {
+    /// Creates a generator seeded with `seed`.
+    pub fn new(seed: u64) -> Self {
+        let mut splitmix_state = seed;
+        let state = [(); 4].map(|_| splitmix64(&mut splitmix_state));
+
+        GameRng { seed, state }
+    }
+
+    /// Gets the seed the generator was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Generates the next random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    /// Generates a random `f64` from 0 (inclusive) to 1 (exclusive).
+    pub fn next_f64(&mut self) -> f64 {
+        // use the 53 most significant bits, which is the precision of an f64
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Generates a random `f64` from `min` (inclusive) to `max` (exclusive).
+    pub fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + self.next_f64() * (max - min)
+    }
+
+    /// Rolls an outcome with a `chance` probability (in a ratio from 0 to 1) and returns whether it happened.
+    /// 
+    /// A chance of 0 (or less) never happens and a chance of 1 (or more) always happens.
+    pub fn roll(&mut self, chance: f64) -> bool {
+        self.next_f64() < chance
+    }
+
+    /// Creates a new, independent generator from this one (e.g. to give each simulation in a batch its own stream of numbers).
+    pub fn fork(&mut self) -> GameRng {
+        GameRng::new(self.next_u64())
+    }
+}
+
+/// Generates the next number of the SplitMix64 sequence, which is used to expand a single seed into xoshiro256**'s state.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_generates_the_same_numbers() {
+        let mut first = GameRng::new(42);
+        let mut second = GameRng::new(42);
+
+        for _ in 0..1000 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+    }
+
+    #[test]
+    fn different_seeds_generate_different_numbers() {
+        let first: Vec<u64> = (0..8).scan(GameRng::new(1), |rng, _| Some(rng.next_u64())).collect();
+        let second: Vec<u64> = (0..8).scan(GameRng::new(2), |rng, _| Some(rng.next_u64())).collect();
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn sequence_never_changes_for_a_seed() {
+        let mut rng = GameRng::new(0);
+        let numbers: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+
+        assert_eq!(numbers, [11091344671253066420, 13793997310169335082, 1900383378846508768]);
+        assert_eq!(rng.seed(), 0);
+    }
+
+    #[test]
+    fn forks_are_deterministic_and_independent() {
+        let mut first = GameRng::new(7);
+        let mut second = GameRng::new(7);
+        let mut first_fork = first.fork();
+        let mut second_fork = second.fork();
+
+        assert_eq!(first_fork.seed(), second_fork.seed());
+        assert_eq!(first_fork.next_u64(), second_fork.next_u64());
+        assert_ne!(first_fork.seed(), first.seed());
+    }
+
+    #[test]
+    fn floats_stay_within_their_range() {
+        let mut rng = GameRng::new(3);
+
+        for _ in 0..10_000 {
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+            assert!((-2.0..5.0).contains(&rng.range(-2.0, 5.0)));
+        }
+    }
+
+    #[test]
+    fn rolls_never_happen_at_0_and_always_happen_at_1() {
+        let mut rng = GameRng::new(5);
+
+        for _ in 0..10_000 {
+            assert!(!rng.roll(0.0));
+            assert!(!rng.roll(-1.0));
+            assert!(rng.roll(1.0));
+            assert!(rng.roll(2.0));
+        }
+    }
+
+    #[test]
+    fn rolls_happen_about_as_often_as_their_chance() {
+        let mut rng = GameRng::new(11);
+        let hits = (0..100_000).filter(|_| rng.roll(0.25)).count();
+
+        assert!((24_000..26_000).contains(&hits), "{} hits out of 100000 rolls at 25%", hits);
+    }
+}
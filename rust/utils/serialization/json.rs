@@ -0,0 +1,112 @@
+use std::{fmt, fs::read_to_string, io, path::{Path, PathBuf}};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{from_str, ser::{Formatter, PrettyFormatter}, Serializer};
+
+/// Represents an error that occurred while reading a JSON file into a Rust type.
+#[derive(Debug)]
+pub enum JsonFileError {
+    /// the file couldn't be read
+    Io { path: PathBuf, source: io::Error },
+    /// the file was read but its contents couldn't be deserialized
+    Parse { path: PathBuf, source: serde_json::Error },
+    /// the file couldn't be written
+    Write { path: PathBuf, source: io::Error },
+}
+
+impl JsonFileError {
+    /// the path of the file that caused the error
+    pub fn path(&self) -> &Path {
+        match self {
+            JsonFileError::Io { path, .. } | JsonFileError::Parse { path, .. } | JsonFileError::Write { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for JsonFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonFileError::Io { path, source } => write!(f, "failed to read {}: {}", path.display(), source),
+            JsonFileError::Parse { path, source } => write!(f, "failed to parse {}: {}", path.display(), source),
+            JsonFileError::Write { path, source } => write!(f, "failed to write {}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for JsonFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonFileError::Io { source, .. } => Some(source),
+            JsonFileError::Parse { source, .. } => Some(source),
+            JsonFileError::Write { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Reads the JSON file at `path` and deserializes its contents into `T`.
+pub fn read_json_file<T: DeserializeOwned>(path: &Path) -> Result<T, JsonFileError> {
+    let json_data = read_to_string(path).map_err(|source| JsonFileError::Io { path: path.to_path_buf(), source })?;
+
+    from_str(&json_data).map_err(|source| JsonFileError::Parse { path: path.to_path_buf(), source })
+}
+
+/// Serializes `value` the same way `JSON.stringify(value, null, 4)` does, which is how the TypeScript scripts wrote `mechanics/`.
+/// 
+/// This keeps regenerated files diffable against the existing ones (e.g. whole numbers are written as `350` rather than `350.0`).
+pub fn to_js_json<T: Serialize>(value: &T) -> String {
+    let mut json_data = Vec::new();
+    let mut serializer = Serializer::with_formatter(&mut json_data, JsFormatter(PrettyFormatter::with_indent(b"    ")));
+    value.serialize(&mut serializer).expect("Failed to serialize JSON file");
+
+    String::from_utf8(json_data).expect("Serialized JSON is not valid UTF-8")
+}
+
+/// A pretty formatter that writes floats like JavaScript does (whole numbers without a fraction, non-finite numbers as `null`).
+struct JsFormatter<'a>(PrettyFormatter<'a>);
+
+impl Formatter for JsFormatter<'_> {
+    fn write_f64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        if value.is_finite() {
+            // unlike serde_json, `Display` doesn't add `.0` to whole numbers
+            write!(writer, "{}", value)
+        } else {
+            writer.write_all(b"null")
+        }
+    }
+
+    fn begin_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + io::Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        self.0.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.end_object(writer)
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        self.0.begin_object_key(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.begin_object_value(writer)
+    }
+
+    fn end_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.end_object_value(writer)
+    }
+}
@@ -263,27 +263,6 @@ createQuests(
                 misc: null,
             }
         },
-        {
-            chapter: 3,
-            id: 2,
-            name: 'Razer: The Storage Collector',
-            description: 'Help Razer defeat the enemies around the storage (2 Golems)',
-            type: {
-                itemsRequired: null,
-                resourcesRequired: null,
-                enemiesRequired: [
-                    {
-                        type: AvailableEnemies.Golem,
-                        amount: 2,
-                    }
-                ],
-            },
-            completionRewards: {
-                coins: 5,
-                xp: 100,
-                misc: null,
-            }
-        },
         {
             chapter: 3,
             id: 3,
@@ -323,19 +302,6 @@ createQuests(
                 misc: null,
             }
         },
-        {
-            chapter: 4,
-            id: 1,
-            name: 'Beacon 2',
-            description: 'Reach Beacon 2',
-            // requires 55 wood and 25 stone
-            type: null,
-            completionRewards: {
-                coins: 0,
-                xp: 0,
-                misc: null,
-            }
-        },
         {
             chapter: 4,
             id: 2,
@@ -453,7 +419,7 @@ createQuests(
         },
         {
             chapter: 4,
-            id: 5,
+            id: 6,
             name: 'Beacon 2',
             description: 'Expand Beacon 2 (3/3)',
             // 80 wood, 55 stone, 2 energy cores
@@ -573,7 +539,7 @@ createQuests(
         },
         {
             chapter: 6,
-            id: 1,
+            id: 2,
             name: 'Beacon 3',
             description: 'Defeat the enemies guarding Beacon 3 (2 Trufos, 2 Fireflies, 3 Golems)',
             type: {
@@ -602,7 +568,7 @@ createQuests(
         },
         {
             chapter: 6,
-            id: 2,
+            id: 3,
             name: 'Beacon 3',
             description: 'Gather resources to repair Beacon 3',
             // requires 55 wood, 45 stone, 25 coal and 2 energy cores
@@ -637,7 +603,7 @@ createQuests(
         },
         {
             chapter: 6,
-            id: 2,
+            id: 4,
             name: 'Beacon 3',
             description: 'Expand Beacon 3 (1/3)',
             // requires 60 wood, 35 stone, 15 coal and 1 energy core
@@ -672,7 +638,7 @@ createQuests(
         },
         {
             chapter: 6,
-            id: 3,
+            id: 5,
             name: 'Beacon 3',
             description: 'Expand Beacon 3 (2/3)',
             // requires 65 wood, 40 stone, 25 coal and 2 energy cores
@@ -707,7 +673,7 @@ createQuests(
         },
         {
             chapter: 6,
-            id: 4,
+            id: 6,
             name: 'Beacon 3',
             description: 'Expand Beacon 3 (3/3)',
             // requires 75 wood, 45 stone, 35 coal and 2 energy cores